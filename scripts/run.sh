@@ -3,6 +3,7 @@
 # Diretórios
 INPUT_DIR="data/edges/"
 OUTPUT_DIR="data/results/"
+BEST_KNOWN="data/best_known.txt"
 
 # Parâmetros
 MAX_STAGNANT=112
@@ -69,7 +70,8 @@ done | sort -n | while read -r num_lines graph_file; do
 		--population "$POP_SIZE" \
 		--tournament "$TOURNAMENT_SIZE" \
 		--trials "$TRIALS" \
-		--output "$output_file"
+		--output "$output_file" \
+		--best-known "$BEST_KNOWN"
 
 	# Marcar o fim do tempo de execução
 	end_time=$(date +%s)
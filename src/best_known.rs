@@ -0,0 +1,264 @@
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{self, BufRead, BufReader},
+    path::Path,
+};
+
+/// A best-known value for a single benchmark instance.
+///
+/// # Fields
+/// - `value: usize`: The smallest total Roman domination weight known for the instance.
+/// - `proven: bool`: Whether `value` is known to be optimal (otherwise it is an upper bound).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BestKnown {
+    /// Best-known objective value.
+    pub value: usize,
+    /// `true` if the value is a proven optimum.
+    pub proven: bool,
+}
+
+/// How a result compares against the registry.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Standing {
+    /// The instance is not present in the registry.
+    Unknown,
+    /// The result equals the best-known value.
+    Matched,
+    /// The result is worse than the best-known value by `gap` percent.
+    Above {
+        /// Relative deviation from the best-known value, in percent.
+        gap: f64,
+    },
+    /// The result improves on a best-known value that was not proven optimal.
+    NewRecord {
+        /// The value that was beaten.
+        previous: usize,
+    },
+    /// The result is below a proven optimum, which means the solution is invalid.
+    BelowOptimum {
+        /// The proven optimum.
+        optimum: usize,
+    },
+}
+
+/// Registry of best-known values keyed by instance name.
+///
+/// The instance name is the file stem of the edge list (e.g. `C125-9` for
+/// `data/edges/DIMACS/C125-9.txt`).
+///
+/// # File Format
+/// Each non-empty line that does not start with `#` has the form
+/// `<instance> <value> [proven]`. If an instance appears more than once, the
+/// smallest value is kept.
+#[derive(Clone, Debug, Default)]
+pub struct BestKnownRegistry {
+    entries: HashMap<String, BestKnown>,
+}
+
+impl BestKnownRegistry {
+    /// Loads a registry from a file.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be read or a line is malformed.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::from_reader(BufReader::new(File::open(path)?))
+    }
+
+    /// Loads a registry from any buffered reader.
+    ///
+    /// # Errors
+    /// Returns an error with kind `InvalidData` if a line does not follow the
+    /// `<instance> <value> [proven]` format.
+    pub fn from_reader<R: BufRead>(reader: R) -> io::Result<Self> {
+        let mut registry = Self::default();
+
+        for (number, line) in reader.lines().enumerate() {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let invalid = |reason: &str| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("Invalid best-known entry on line {}: {reason}", number + 1),
+                )
+            };
+
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            let (name, value, proven) = match parts.as_slice() {
+                [name, value] => (*name, *value, false),
+                [name, value, "proven"] => (*name, *value, true),
+                _ => return Err(invalid(line)),
+            };
+            let value = value.parse().map_err(|_| invalid(value))?;
+
+            registry.insert(name, BestKnown { value, proven });
+        }
+
+        Ok(registry)
+    }
+
+    /// Inserts an entry, keeping the better of the existing and the new value.
+    pub fn insert(&mut self, instance: &str, entry: BestKnown) {
+        self.entries
+            .entry(instance.to_string())
+            .and_modify(|current| {
+                if entry.value < current.value {
+                    *current = entry;
+                } else if entry.value == current.value {
+                    current.proven |= entry.proven;
+                }
+            })
+            .or_insert(entry);
+    }
+
+    /// Returns the best-known entry for an instance, if any.
+    #[must_use]
+    pub fn get(&self, instance: &str) -> Option<BestKnown> {
+        self.entries.get(instance).copied()
+    }
+
+    /// Returns the number of instances in the registry.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the registry has no entries.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Compares a result for `instance` against its best-known value.
+    #[must_use]
+    pub fn standing(&self, instance: &str, value: usize) -> Standing {
+        let Some(best) = self.get(instance) else {
+            return Standing::Unknown;
+        };
+
+        match value.cmp(&best.value) {
+            std::cmp::Ordering::Equal => Standing::Matched,
+            std::cmp::Ordering::Greater => Standing::Above {
+                gap: gap(value, best.value),
+            },
+            std::cmp::Ordering::Less if best.proven => Standing::BelowOptimum {
+                optimum: best.value,
+            },
+            std::cmp::Ordering::Less => Standing::NewRecord {
+                previous: best.value,
+            },
+        }
+    }
+}
+
+/// Relative deviation of `value` from `best`, in percent.
+///
+/// Negative values mean `value` improves on `best`.
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn gap(value: usize, best: usize) -> f64 {
+    if best == 0 {
+        return 0.0;
+    }
+    (value as f64 - best as f64) / best as f64 * 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(text: &str) -> io::Result<BestKnownRegistry> {
+        BestKnownRegistry::from_reader(text.as_bytes())
+    }
+
+    #[test]
+    fn parser_skips_comments_and_reads_the_proven_flag() {
+        let registry =
+            parse("# instance value [proven]\n\nC125-9 86\n  # indented\nkarate 14 proven\n")
+                .unwrap();
+
+        assert_eq!(registry.len(), 2);
+        assert_eq!(
+            registry.get("C125-9"),
+            Some(BestKnown {
+                value: 86,
+                proven: false
+            })
+        );
+        assert_eq!(
+            registry.get("karate"),
+            Some(BestKnown {
+                value: 14,
+                proven: true
+            })
+        );
+        assert_eq!(registry.get("missing"), None);
+    }
+
+    #[test]
+    fn duplicate_keys_keep_the_smallest_value() {
+        let registry = parse("g 12\ng 10\ng 11 proven\nh 7\nh 7 proven\nh 7\n").unwrap();
+
+        // O valor 11 comprovado é pior que 10 e é descartado.
+        assert_eq!(
+            registry.get("g"),
+            Some(BestKnown {
+                value: 10,
+                proven: false
+            })
+        );
+        // Com valores iguais, a marca de otimalidade é preservada.
+        assert_eq!(
+            registry.get("h"),
+            Some(BestKnown {
+                value: 7,
+                proven: true
+            })
+        );
+    }
+
+    #[test]
+    fn malformed_lines_are_rejected_with_their_line_number() {
+        for text in [
+            "g\n",
+            "g 10 optimal\n",
+            "g ten\n",
+            "g -3\n",
+            "g 10 proven extra\n",
+        ] {
+            let err = parse(&format!("# header\n{text}")).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData, "{text:?}");
+            assert!(err.to_string().contains("line 2"), "{err}");
+        }
+    }
+
+    #[test]
+    fn standing_classifies_results_against_the_registry() {
+        let registry = parse("open 20\nclosed 20 proven\n").unwrap();
+
+        assert_eq!(registry.standing("other", 5), Standing::Unknown);
+        assert_eq!(registry.standing("open", 20), Standing::Matched);
+        assert_eq!(registry.standing("closed", 20), Standing::Matched);
+        assert_eq!(
+            registry.standing("open", 18),
+            Standing::NewRecord { previous: 20 }
+        );
+        assert_eq!(
+            registry.standing("closed", 18),
+            Standing::BelowOptimum { optimum: 20 }
+        );
+        assert_eq!(registry.standing("open", 25), Standing::Above { gap: 25.0 });
+    }
+
+    #[test]
+    fn gap_is_relative_to_the_best_value() {
+        assert!((gap(25, 20) - 25.0).abs() < 1e-12);
+        assert!((gap(15, 20) + 25.0).abs() < 1e-12);
+        assert!(gap(20, 20).abs() < 1e-12);
+        assert!(gap(3, 0).abs() < 1e-12);
+    }
+}
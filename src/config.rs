@@ -0,0 +1,303 @@
+use std::{fmt::Write, fs, path::Path, str::FromStr};
+
+use crate::{
+    genetic::HeuristicRegistry,
+    solver::{IslandParams, Mode, SolverConfig},
+};
+
+impl SolverConfig {
+    /// Reads a configuration file; see [`SolverConfig::apply_config`] for the format.
+    /// Keys missing from the file keep their default values.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be read or contains an invalid line.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+
+        let mut config = Self::default();
+        config.apply_config(&text)?;
+        Ok(config)
+    }
+
+    /// Applies the `key = value` lines of `text` to the configuration, in order.
+    ///
+    /// Blank lines and lines starting with `#` are ignored. Keys are the field names of
+    /// [`SolverConfig`] plus those of the island parameters (`islands`, `topology`,
+    /// `migration_interval`, `migration_size`), of the GRASP parameters (`alpha`) and of
+    /// the diversity parameters
+    /// (`eliminate_duplicates`, `diversity_threshold`, `diversity_response`); see
+    /// [`SolverConfig::set`].
+    ///
+    /// # Errors
+    /// Returns an error naming the line number if a line is malformed or has an invalid
+    /// key or value.
+    pub fn apply_config(&mut self, text: &str) -> Result<(), String> {
+        for (line_number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| format!("Line {}: expected key = value", line_number + 1))?;
+            self.set(key.trim(), value.trim())
+                .map_err(|e| format!("Line {}: {e}", line_number + 1))?;
+        }
+        Ok(())
+    }
+
+    /// Sets the parameter `key` from its textual `value`.
+    ///
+    /// Island parameters require the matching mode: `islands`, `topology`,
+    /// `migration_interval` and `migration_size` need `mode = island`. `alpha` applies to
+    /// the GRASP mode and to the `grasp` heuristic of `heuristics` in any mode.
+    /// `heuristics = default` and `seed = none` restore the defaults.
+    ///
+    /// # Errors
+    /// Returns an error if the key is unknown or the value is invalid.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match key {
+            "problem" => self.problem = value.parse()?,
+            "max_stagnant" => self.max_stagnant = parse(key, value)?,
+            "generations" => self.generations = parse(key, value)?,
+            "tournament_size" => self.tournament_size = positive(key, value)?,
+            "selection" => self.selection = value.parse()?,
+            "crossover_rate" => self.crossover_rate = probability(key, value)?,
+            "crossover" => self.crossover = value.parse()?,
+            "crossover_portfolio" => {
+                self.crossover_portfolio = value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|op| !op.is_empty())
+                    .map(str::parse)
+                    .collect::<Result<_, _>>()?;
+            }
+            "mutation_rate" => self.mutation_rate = probability(key, value)?,
+            "adaptive_rates" => self.adaptive_rates = parse(key, value)?,
+            "population_factor" => {
+                let factor: f64 = parse(key, value)?;
+                if factor <= 0.0 {
+                    return Err(format!("{key} must be greater than 0"));
+                }
+                self.population_factor = factor;
+            }
+            "heuristics" => {
+                self.heuristics = match value {
+                    "default" => None,
+                    mix => Some(HeuristicRegistry::default().parse_mix(mix)?),
+                }
+            }
+            "eliminate_duplicates" => self.diversity.eliminate_duplicates = parse(key, value)?,
+            "diversity_threshold" => self.diversity.threshold = probability(key, value)?,
+            "diversity_response" => self.diversity.response = value.parse()?,
+            "max_treewidth" => self.max_treewidth = parse(key, value)?,
+            "max_dp_states" => self.max_dp_states = parse(key, value)?,
+            "elimination" => self.elimination = value.parse()?,
+            "reduce" => self.reduce = parse(key, value)?,
+            "mode" => {
+                self.mode = match value {
+                    "generational" => Mode::Generational,
+                    "island" => Mode::Island(IslandParams::default()),
+                    "grasp" => Mode::Grasp,
+                    other => return Err(format!("Invalid mode: {other}")),
+                }
+            }
+            "islands" => self.island_params(key)?.islands = positive(key, value)?,
+            "topology" => self.island_params(key)?.topology = value.parse()?,
+            "migration_interval" => {
+                self.island_params(key)?.migration_interval = positive(key, value)?;
+            }
+            "migration_size" => self.island_params(key)?.migration_size = parse(key, value)?,
+            "alpha" => self.grasp.alpha = probability(key, value)?,
+            "seed" => {
+                self.seed = match value {
+                    "none" => None,
+                    seed => Some(parse(key, seed)?),
+                }
+            }
+            _ => return Err(format!("Unknown configuration key: {key}")),
+        }
+        Ok(())
+    }
+
+    fn island_params(&mut self, key: &str) -> Result<&mut IslandParams, String> {
+        match &mut self.mode {
+            Mode::Island(params) => Ok(params),
+            _ => Err(format!("{key} requires mode = island")),
+        }
+    }
+
+    /// Formats the configuration as `key = value` lines readable by
+    /// [`SolverConfig::apply_config`].
+    #[must_use]
+    pub fn to_config(&self) -> String {
+        let mut text = String::new();
+        let mut line = |key: &str, value: &dyn std::fmt::Display| {
+            // Escrever em uma `String` nunca falha.
+            let _ = writeln!(text, "{key} = {value}");
+        };
+
+        line("problem", &self.problem);
+        match &self.mode {
+            Mode::Generational => line("mode", &"generational"),
+            Mode::Island(params) => {
+                line("mode", &"island");
+                line("islands", &params.islands);
+                line("topology", &params.topology);
+                line("migration_interval", &params.migration_interval);
+                line("migration_size", &params.migration_size);
+            }
+            Mode::Grasp => line("mode", &"grasp"),
+        }
+        line("alpha", &self.grasp.alpha);
+        line("max_stagnant", &self.max_stagnant);
+        line("generations", &self.generations);
+        line("population_factor", &self.population_factor);
+        line("selection", &self.selection);
+        line("tournament_size", &self.tournament_size);
+        line("crossover", &self.crossover);
+        if !self.crossover_portfolio.is_empty() {
+            let portfolio: Vec<String> = self
+                .crossover_portfolio
+                .iter()
+                .map(ToString::to_string)
+                .collect();
+            line("crossover_portfolio", &portfolio.join(","));
+        }
+        line("crossover_rate", &self.crossover_rate);
+        line("mutation_rate", &self.mutation_rate);
+        line("adaptive_rates", &self.adaptive_rates);
+        match &self.heuristics {
+            Some(mix) => line("heuristics", mix),
+            None => line("heuristics", &"default"),
+        }
+        line("eliminate_duplicates", &self.diversity.eliminate_duplicates);
+        line("diversity_threshold", &self.diversity.threshold);
+        line("diversity_response", &self.diversity.response);
+        line("max_treewidth", &self.max_treewidth);
+        line("max_dp_states", &self.max_dp_states);
+        line("elimination", &self.elimination);
+        line("reduce", &self.reduce);
+        match self.seed {
+            Some(seed) => line("seed", &seed),
+            None => line("seed", &"none"),
+        }
+
+        text
+    }
+}
+
+fn parse<T: FromStr>(key: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("Invalid value for {key}: {value}"))
+}
+
+fn positive(key: &str, value: &str) -> Result<usize, String> {
+    match parse(key, value)? {
+        0 => Err(format!("{key} must be greater than 0")),
+        n => Ok(n),
+    }
+}
+
+fn probability(key: &str, value: &str) -> Result<f64, String> {
+    let p: f64 = parse(key, value)?;
+    if (0.0..=1.0).contains(&p) {
+        Ok(p)
+    } else {
+        Err(format!("{key} must be between 0 and 1"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        genetic::{CrossoverOperator, ProblemVariant, SelectionOperator, Topology},
+        solver::DiversityResponse,
+        treewidth::EliminationOrder,
+    };
+
+    fn round_trip(config: &SolverConfig) -> SolverConfig {
+        let mut read = SolverConfig::default();
+        read.apply_config(&config.to_config()).unwrap();
+        read
+    }
+
+    #[test]
+    fn configurations_round_trip_through_to_config() {
+        let mut config = SolverConfig {
+            problem: ProblemVariant::TotalItalian,
+            max_stagnant: 17,
+            generations: 321,
+            tournament_size: 5,
+            selection: SelectionOperator::LinearRank(1.7),
+            crossover_rate: 0.65,
+            crossover: CrossoverOperator::RegionPartition(3),
+            crossover_portfolio: vec![CrossoverOperator::KPoint(3), CrossoverOperator::BfsBall],
+            mutation_rate: 0.015,
+            adaptive_rates: true,
+            population_factor: 2.5,
+            mode: Mode::Island(IslandParams {
+                islands: 6,
+                topology: Topology::FullyConnected,
+                migration_interval: 7,
+                migration_size: 3,
+            }),
+            heuristics: Some(
+                HeuristicRegistry::default()
+                    .parse_mix("h1:0.5,h3:0.25,grasp:0.25")
+                    .unwrap(),
+            ),
+            max_treewidth: 4,
+            max_dp_states: 1000,
+            elimination: EliminationOrder::MinDegree,
+            reduce: false,
+            seed: Some(42),
+            ..SolverConfig::default()
+        };
+        config.grasp.alpha = 0.35;
+        config.diversity.eliminate_duplicates = true;
+        config.diversity.threshold = 0.1;
+        config.diversity.response = DiversityResponse::Restart;
+
+        assert_eq!(round_trip(&config), config);
+        assert_eq!(
+            round_trip(&SolverConfig::default()),
+            SolverConfig::default()
+        );
+
+        config.mode = Mode::Grasp;
+        config.heuristics = None;
+        config.crossover_portfolio.clear();
+        config.seed = None;
+        assert_eq!(round_trip(&config), config);
+    }
+
+    #[test]
+    fn island_keys_follow_the_mode_line() {
+        let config = SolverConfig {
+            mode: Mode::Island(IslandParams {
+                islands: 8,
+                ..IslandParams::default()
+            }),
+            ..SolverConfig::default()
+        };
+        let text = config.to_config();
+        let position = |key: &str| text.find(&format!("\n{key} = ")).unwrap();
+        assert!(position("mode") < position("islands"));
+
+        // `mode = island` restaura os parâmetros padrão das ilhas.
+        let mut read = SolverConfig::default();
+        read.apply_config("mode = island\nislands = 8\nmode = island")
+            .unwrap();
+        assert_eq!(read.mode, Mode::Island(IslandParams::default()));
+
+        let mut read = SolverConfig::default();
+        let err = read.apply_config("islands = 8\nmode = island").unwrap_err();
+        assert!(err.starts_with("Line 1:"), "{err}");
+    }
+}
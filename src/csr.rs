@@ -0,0 +1,339 @@
+use std::ops::Range;
+
+use kambo_graph::{graphs::simple::UndirectedGraph, Graph};
+
+/// Immutable compressed sparse row (CSR) view of an undirected graph.
+///
+/// The neighbours of vertex `v` are stored contiguously in
+/// `neighbors[offsets[v]..offsets[v + 1]]`, sorted in increasing order. The view is
+/// built once per run and shared by every chromosome, the repair operator and the
+/// heuristics, so looking up a neighbourhood is a slice access with no allocation.
+///
+/// Vertices are the integers `0..order`, as produced by [`crate::utils::build_graph`].
+///
+/// A graph may carry per-vertex costs for labels `1` and `2`; see [`CsrGraph::cost`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CsrGraph {
+    offsets: Vec<usize>,
+    neighbors: Vec<u32>,
+    costs: Option<Vec<[u32; 2]>>,
+}
+
+impl CsrGraph {
+    /// Builds a CSR graph with `order` vertices from a list of undirected edges.
+    ///
+    /// Self-loops and duplicate edges are ignored.
+    ///
+    /// # Panics
+    /// Panics if an edge references a vertex outside `0..order`.
+    #[must_use]
+    pub fn from_edges(order: usize, edges: &[(u32, u32)]) -> Self {
+        let mut adjacency = vec![Vec::new(); order];
+        for &(u, v) in edges {
+            assert!(
+                (u as usize) < order && (v as usize) < order,
+                "Edge ({u}, {v}) references a vertex outside 0..{order}"
+            );
+            if u != v {
+                adjacency[u as usize].push(v);
+                adjacency[v as usize].push(u);
+            }
+        }
+
+        Self::from_adjacency(adjacency)
+    }
+
+    /// Builds the circulant graph on `order` vertices where each vertex `v` is adjacent
+    /// to `v ± offset` for every offset.
+    #[cfg(test)]
+    pub(crate) fn circulant(order: u32, offsets: &[u32]) -> Self {
+        let edges: Vec<(u32, u32)> = (0..order)
+            .flat_map(|v| offsets.iter().map(move |&offset| (v, (v + offset) % order)))
+            .collect();
+        Self::from_edges(order as usize, &edges)
+    }
+
+    fn from_adjacency(mut adjacency: Vec<Vec<u32>>) -> Self {
+        let mut offsets = Vec::with_capacity(adjacency.len() + 1);
+        let mut neighbors = Vec::with_capacity(adjacency.iter().map(Vec::len).sum());
+
+        offsets.push(0);
+        for list in &mut adjacency {
+            list.sort_unstable();
+            list.dedup();
+            neighbors.extend_from_slice(list);
+            offsets.push(neighbors.len());
+        }
+
+        Self {
+            offsets,
+            neighbors,
+            costs: None,
+        }
+    }
+
+    /// Attaches the costs of labels `1` and `2` of every vertex, `costs[v] = [c1, c2]`.
+    ///
+    /// # Panics
+    /// Panics if `costs` does not have one entry per vertex.
+    #[must_use]
+    pub fn with_costs(mut self, costs: Vec<[u32; 2]>) -> Self {
+        assert_eq!(
+            costs.len(),
+            self.order(),
+            "There must be one cost entry per vertex"
+        );
+        self.costs = Some(costs);
+        self
+    }
+
+    /// Returns `true` if the graph carries vertex costs.
+    #[inline]
+    #[must_use]
+    pub fn is_weighted(&self) -> bool {
+        self.costs.is_some()
+    }
+
+    /// Returns the cost of giving `label` to `vertex`.
+    ///
+    /// Label `0` costs nothing. Without vertex costs a label costs its value, so the cost of
+    /// a labelling is the sum of its labels.
+    ///
+    /// # Panics
+    /// Panics if the graph carries vertex costs and `label` is greater than `2`.
+    #[inline]
+    #[must_use]
+    pub fn cost(&self, vertex: u32, label: u8) -> usize {
+        match (&self.costs, label) {
+            (_, 0) => 0,
+            (None, label) => usize::from(label),
+            (Some(costs), 1 | 2) => costs[vertex as usize][usize::from(label) - 1] as usize,
+            (Some(_), label) => {
+                panic!("Vertex costs are only defined for labels 1 and 2, not {label}")
+            }
+        }
+    }
+
+    /// Returns the number of vertices.
+    #[inline]
+    #[must_use]
+    pub fn order(&self) -> usize {
+        self.offsets.len().saturating_sub(1)
+    }
+
+    /// Returns the number of edges.
+    #[inline]
+    #[must_use]
+    pub fn edge_count(&self) -> usize {
+        self.neighbors.len() / 2
+    }
+
+    /// Returns the vertices of the graph.
+    #[inline]
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn vertices(&self) -> Range<u32> {
+        0..self.order() as u32
+    }
+
+    /// Returns the sorted neighbours of `vertex`.
+    #[inline]
+    #[must_use]
+    pub fn neighbors(&self, vertex: u32) -> &[u32] {
+        let v = vertex as usize;
+        &self.neighbors[self.offsets[v]..self.offsets[v + 1]]
+    }
+
+    /// Returns the degree of `vertex`.
+    #[inline]
+    #[must_use]
+    pub fn degree(&self, vertex: u32) -> usize {
+        let v = vertex as usize;
+        self.offsets[v + 1] - self.offsets[v]
+    }
+
+    /// Returns `true` if `u` and `v` are adjacent.
+    #[inline]
+    #[must_use]
+    pub fn contains_edge(&self, u: u32, v: u32) -> bool {
+        self.neighbors(u).binary_search(&v).is_ok()
+    }
+
+    /// Returns the connected components of the graph.
+    ///
+    /// Each component lists its vertices in increasing order; components are ordered by
+    /// their smallest vertex.
+    #[must_use]
+    pub fn components(&self) -> Vec<Vec<u32>> {
+        let mut seen = vec![false; self.order()];
+        let mut components = Vec::new();
+
+        for start in self.vertices() {
+            if seen[start as usize] {
+                continue;
+            }
+            seen[start as usize] = true;
+
+            // Busca em largura a partir do menor vértice ainda não visitado.
+            let mut component = vec![start];
+            let mut next = 0;
+            while let Some(&v) = component.get(next) {
+                next += 1;
+                for &n in self.neighbors(v) {
+                    if !seen[n as usize] {
+                        seen[n as usize] = true;
+                        component.push(n);
+                    }
+                }
+            }
+
+            component.sort_unstable();
+            components.push(component);
+        }
+
+        components
+    }
+
+    /// Returns the subgraph induced by `vertices`, renumbered `0..vertices.len()` in the
+    /// given order, with the vertex costs of the graph.
+    ///
+    /// # Panics
+    /// Panics if a vertex is outside `0..order` or appears twice.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn induced_subgraph(&self, vertices: &[u32]) -> Self {
+        let mut index = vec![None; self.order()];
+        for (i, &v) in vertices.iter().enumerate() {
+            assert!(
+                index[v as usize].replace(i as u32).is_none(),
+                "Vertex {v} appears twice"
+            );
+        }
+
+        let adjacency = vertices
+            .iter()
+            .map(|&v| {
+                self.neighbors(v)
+                    .iter()
+                    .filter_map(|&n| index[n as usize])
+                    .collect()
+            })
+            .collect();
+        let subgraph = Self::from_adjacency(adjacency);
+
+        match &self.costs {
+            Some(costs) => {
+                subgraph.with_costs(vertices.iter().map(|&v| costs[v as usize]).collect())
+            }
+            None => subgraph,
+        }
+    }
+}
+
+impl From<&UndirectedGraph<u32>> for CsrGraph {
+    /// Builds the CSR view of a graph whose vertices are `0..graph.order()`.
+    ///
+    /// Self-loops are dropped: a vertex never dominates itself.
+    ///
+    /// # Panics
+    /// Panics if a vertex is outside `0..graph.order()`; use
+    /// [`crate::utils::build_graph`] to obtain normalized graphs.
+    fn from(graph: &UndirectedGraph<u32>) -> Self {
+        let order = graph.order();
+        let mut adjacency = vec![Vec::new(); order];
+
+        for &vertex in graph.vertices() {
+            assert!(
+                (vertex as usize) < order,
+                "Vertex {vertex} is outside 0..{order}; the graph must be normalized"
+            );
+            adjacency[vertex as usize] = graph
+                .neighbors(&vertex)
+                .map(|n| n.copied().filter(|&n| n != vertex).collect())
+                .unwrap_or_default();
+        }
+
+        Self::from_adjacency(adjacency)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Triangle `0-1-2`, the edge `3-4`, and the isolated vertices `5` and `6`.
+    fn disconnected_graph() -> CsrGraph {
+        CsrGraph::from_edges(7, &[(0, 1), (1, 2), (2, 0), (3, 4)])
+    }
+
+    #[test]
+    fn from_edges_is_symmetric_sorted_and_deduplicated() {
+        let graph = CsrGraph::from_edges(5, &[(3, 0), (0, 1), (1, 0), (0, 3), (2, 2), (4, 0)]);
+
+        assert_eq!(graph.order(), 5);
+        assert_eq!(graph.edge_count(), 3);
+        assert_eq!(graph.neighbors(0), [1, 3, 4]);
+        assert_eq!(graph.neighbors(1), [0]);
+        // Laços são descartados.
+        assert!(graph.neighbors(2).is_empty());
+        assert_eq!(graph.degree(2), 0);
+        for u in graph.vertices() {
+            for &v in graph.neighbors(u) {
+                assert!(graph.contains_edge(v, u));
+            }
+        }
+        assert!(graph.contains_edge(4, 0));
+        assert!(!graph.contains_edge(1, 3));
+        assert!(!graph.contains_edge(2, 2));
+    }
+
+    #[test]
+    #[should_panic(expected = "outside 0..3")]
+    fn from_edges_rejects_vertices_outside_the_order() {
+        let _ = CsrGraph::from_edges(3, &[(0, 3)]);
+    }
+
+    #[test]
+    fn components_list_isolated_vertices_on_their_own() {
+        assert_eq!(
+            disconnected_graph().components(),
+            [vec![0, 1, 2], vec![3, 4], vec![5], vec![6]]
+        );
+        assert_eq!(
+            CsrGraph::from_edges(4, &[(3, 1), (1, 2), (2, 0)]).components(),
+            [vec![0, 1, 2, 3]]
+        );
+        assert!(CsrGraph::from_edges(0, &[]).components().is_empty());
+    }
+
+    #[test]
+    fn induced_subgraph_renumbers_in_the_given_order_and_keeps_costs() {
+        let graph = disconnected_graph().with_costs((0..7).map(|v| [v, 10 + v]).collect());
+        let subgraph = graph.induced_subgraph(&[4, 2, 0, 6, 3]);
+
+        assert_eq!(subgraph.order(), 5);
+        assert_eq!(subgraph.edge_count(), 2);
+        assert_eq!(subgraph.neighbors(0), [4]);
+        assert_eq!(subgraph.neighbors(1), [2]);
+        assert!(subgraph.neighbors(3).is_empty());
+        assert_eq!(subgraph.components(), [vec![0, 4], vec![1, 2], vec![3]]);
+        assert_eq!(subgraph.cost(0, 1), 4);
+        assert_eq!(subgraph.cost(3, 2), 16);
+        assert!(!disconnected_graph().induced_subgraph(&[1, 3]).is_weighted());
+    }
+
+    #[test]
+    #[should_panic(expected = "appears twice")]
+    fn induced_subgraph_rejects_repeated_vertices() {
+        let _ = disconnected_graph().induced_subgraph(&[1, 2, 1]);
+    }
+
+    #[test]
+    fn costs_default_to_the_label_values() {
+        let graph = disconnected_graph();
+        assert!(!graph.is_weighted());
+        assert_eq!(graph.cost(5, 0), 0);
+        assert_eq!(graph.cost(5, 2), 2);
+        assert_eq!(graph.cost(5, 3), 3);
+    }
+}
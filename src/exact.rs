@@ -0,0 +1,605 @@
+use std::{collections::VecDeque, fmt};
+
+use crate::{
+    csr::CsrGraph,
+    genetic::{Chromosome, Problem, ProblemVariant},
+};
+
+/// Largest graph order solved by [`brute_force`].
+pub const BRUTE_FORCE_ORDER: usize = 8;
+
+/// Finds a minimum-cost feasible labelling of `graph` by enumerating every labelling.
+///
+/// There are `(max_label + 1)^n` labellings, so this is meant for components of at most
+/// [`BRUTE_FORCE_ORDER`] vertices. Labellings that cost at least as much as the best
+/// feasible labelling found so far are skipped without checking feasibility.
+///
+/// # Parameters
+/// - `problem: &dyn Problem`: The problem to solve.
+/// - `graph: &CsrGraph`: The graph, possibly with vertex costs.
+///
+/// # Returns
+/// - An optimal chromosome, or `None` if no labelling is feasible, e.g. for an isolated
+///   vertex under the total variants.
+///
+/// # Panics
+/// Panics if the graph has more than [`BRUTE_FORCE_ORDER`] vertices.
+#[must_use]
+pub fn brute_force(problem: &dyn Problem, graph: &CsrGraph) -> Option<Chromosome> {
+    assert!(
+        graph.order() <= BRUTE_FORCE_ORDER,
+        "Brute force is limited to graphs with at most {BRUTE_FORCE_ORDER} vertices"
+    );
+
+    let max_label = problem.max_label();
+    let mut genes = vec![0u8; graph.order()];
+    let mut best: Option<Chromosome> = None;
+
+    loop {
+        let cost: usize = graph
+            .vertices()
+            .map(|v| graph.cost(v, genes[v as usize]))
+            .sum();
+        if best.as_ref().map_or(true, |best| cost < best.fitness()) {
+            let mut chromosome = Chromosome::new(genes.clone(), graph);
+            if problem.is_feasible(&chromosome, graph) {
+                chromosome.track_neighbors(graph);
+                best = Some(chromosome);
+            }
+        }
+
+        // Próxima rotulação, contando na base `max_label + 1`.
+        let Some(position) = genes.iter().position(|&label| label < max_label) else {
+            break;
+        };
+        genes[position] += 1;
+        for label in &mut genes[..position] {
+            *label = 0;
+        }
+    }
+
+    best
+}
+
+/// A graph class with a known optimum or a linear-time exact algorithm.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GraphClass {
+    /// A path with at least two vertices.
+    Path,
+    /// A star `K_{1,n}` with `n >= 2`.
+    Star,
+    /// A tree that is neither a path nor a star.
+    Tree,
+    /// A cycle.
+    Cycle,
+    /// A complete graph with at least two vertices.
+    Complete,
+    /// A complete bipartite graph `K_{m,n}` with `m, n >= 2`.
+    CompleteBipartite,
+}
+
+impl fmt::Display for GraphClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Path => "path",
+            Self::Star => "star",
+            Self::Tree => "tree",
+            Self::Cycle => "cycle",
+            Self::Complete => "complete",
+            Self::CompleteBipartite => "complete-bipartite",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Recognises the class of a connected graph.
+///
+/// Complete graphs take precedence over paths and cycles (`K_2`, `K_3`), and cycles over
+/// complete bipartite graphs (`K_{2,2}`).
+///
+/// # Returns
+/// - The class of the graph, or `None` if it belongs to none of the classes or has fewer
+///   than two vertices.
+#[must_use]
+pub fn recognize(graph: &CsrGraph) -> Option<GraphClass> {
+    let order = graph.order();
+    let size = graph.edge_count();
+    if order < 2 {
+        return None;
+    }
+
+    if size == order * (order - 1) / 2 {
+        Some(GraphClass::Complete)
+    } else if size == order - 1 {
+        if graph.vertices().all(|v| graph.degree(v) <= 2) {
+            Some(GraphClass::Path)
+        } else if graph.vertices().any(|v| graph.degree(v) == order - 1) {
+            Some(GraphClass::Star)
+        } else {
+            Some(GraphClass::Tree)
+        }
+    } else if size == order && graph.vertices().all(|v| graph.degree(v) == 2) {
+        Some(GraphClass::Cycle)
+    } else {
+        let (left, right) = bipartition(graph)?;
+        (left.len() >= 2 && right.len() >= 2 && size == left.len() * right.len())
+            .then_some(GraphClass::CompleteBipartite)
+    }
+}
+
+/// Solves a connected graph of a recognised class to optimality.
+///
+/// Trees, paths and stars are solved by a dynamic program over the rooted tree and cycles
+/// by a dynamic program over the cycle, both linear in the order and aware of vertex
+/// costs. Complete and complete bipartite graphs use closed forms, which hold only without
+/// vertex costs: `γtR(K_2) = 2`, `γtR(K_n) = 3` for `n >= 3` and `γtR(K_{m,n}) = 4` for
+/// `m, n >= 2`, and `γR(K_n) = 2`, `γR(K_{2,n}) = 3` and `γR(K_{m,n}) = 4` for
+/// `m, n >= 3`.
+///
+/// # Parameters
+/// - `problem: ProblemVariant`: The problem to solve. Only total Roman and Roman
+///   domination are supported.
+/// - `graph: &CsrGraph`: A connected graph.
+///
+/// # Returns
+/// - The class of the graph and an optimal chromosome with tracked neighbour counts, or
+///   `None` if the problem or the class is not supported.
+#[must_use]
+pub fn solve_class(problem: ProblemVariant, graph: &CsrGraph) -> Option<(GraphClass, Chromosome)> {
+    if !matches!(problem, ProblemVariant::TotalRoman | ProblemVariant::Roman) {
+        return None;
+    }
+    let class = recognize(graph)?;
+
+    let genes = match class {
+        GraphClass::Path | GraphClass::Star | GraphClass::Tree => tree_labelling(problem, graph),
+        GraphClass::Cycle => cycle_labelling(problem, graph),
+        GraphClass::Complete | GraphClass::CompleteBipartite if graph.is_weighted() => {
+            return None;
+        }
+        GraphClass::Complete => {
+            let mut genes = vec![0u8; graph.order()];
+            genes[0] = 2;
+            if problem == ProblemVariant::TotalRoman {
+                // Em K_2 os dois vértices recebem 1; nos demais, um vizinho do 2 recebe 1.
+                genes[0] = if graph.order() == 2 { 1 } else { 2 };
+                genes[1] = 1;
+            }
+            genes
+        }
+        GraphClass::CompleteBipartite => {
+            let (left, right) = bipartition(graph)?;
+            let (small, large) = if left.len() <= right.len() {
+                (left, right)
+            } else {
+                (right, left)
+            };
+            let mut genes = vec![0u8; graph.order()];
+            genes[small[0] as usize] = 2;
+            if problem == ProblemVariant::Roman && small.len() == 2 {
+                genes[small[1] as usize] = 1;
+            } else {
+                genes[large[0] as usize] = 2;
+            }
+            genes
+        }
+    };
+
+    let mut chromosome = Chromosome::new(genes, graph);
+    chromosome.track_neighbors(graph);
+    Some((class, chromosome))
+}
+
+/// Splits a connected graph into the two sides of a bipartition, or returns `None` if it
+/// is not bipartite.
+fn bipartition(graph: &CsrGraph) -> Option<(Vec<u32>, Vec<u32>)> {
+    let mut side = vec![None; graph.order()];
+    let mut queue = VecDeque::from([0u32]);
+    side[0] = Some(false);
+    while let Some(v) = queue.pop_front() {
+        let own = side[v as usize];
+        for &u in graph.neighbors(v) {
+            match side[u as usize] {
+                None => {
+                    side[u as usize] = own.map(|own| !own);
+                    queue.push_back(u);
+                }
+                Some(other) if Some(other) == own => return None,
+                Some(_) => {}
+            }
+        }
+    }
+
+    Some(
+        graph
+            .vertices()
+            .partition(|&v| side[v as usize] == Some(false)),
+    )
+}
+
+/// Smallest neighbour label that satisfies a vertex labelled `label`, or `None` if the
+/// vertex is satisfied by its own label.
+pub(crate) fn required(problem: ProblemVariant, label: u8) -> Option<u8> {
+    match (problem, label) {
+        (_, 0) => Some(2),
+        (ProblemVariant::TotalRoman, _) => Some(1),
+        _ => None,
+    }
+}
+
+/// Returns `true` if a vertex labelled `label` with neighbours labelled `first` and
+/// `second` is satisfied.
+fn satisfied(problem: ProblemVariant, label: u8, first: u8, second: u8) -> bool {
+    required(problem, label).map_or(true, |r| first >= r || second >= r)
+}
+
+/// Optimal labelling of a tree.
+///
+/// `cost[v][label][sat]` is the cost of the subtree rooted at `v` with `v` labelled `label`
+/// and every other vertex of the subtree satisfied; with `sat = 1`, `v` is also satisfied
+/// by its children, while with `sat = 0` it must be satisfied by its parent.
+fn tree_labelling(problem: ProblemVariant, graph: &CsrGraph) -> Vec<u8> {
+    let order = graph.order();
+    let mut parent = vec![u32::MAX; order];
+    let mut visit = Vec::with_capacity(order);
+    let mut queue = VecDeque::from([0u32]);
+    parent[0] = 0;
+    while let Some(v) = queue.pop_front() {
+        visit.push(v);
+        for &u in graph.neighbors(v) {
+            if parent[u as usize] == u32::MAX {
+                parent[u as usize] = v;
+                queue.push_back(u);
+            }
+        }
+    }
+    let parent = &parent;
+    let children = |v: u32| {
+        graph
+            .neighbors(v)
+            .iter()
+            .copied()
+            .filter(move |&u| parent[u as usize] == v && u != 0)
+    };
+
+    // Melhor estado de um filho `c` sob um pai rotulado `label`; com `helping`, só os
+    // rótulos do filho que satisfazem o pai.
+    let best_child = |cost: &[[[usize; 2]; 3]], c: u32, label: u8, helping: bool| {
+        let mut best = (usize::MAX, 0u8, 0usize);
+        for child_label in 0..=2u8 {
+            if helping && required(problem, label).map_or(true, |r| child_label < r) {
+                continue;
+            }
+            let parent_satisfies = required(problem, child_label).map_or(true, |r| label >= r);
+            for (sat, &value) in cost[c as usize][child_label as usize].iter().enumerate() {
+                if (sat == 1 || parent_satisfies) && value < best.0 {
+                    best = (value, child_label, sat);
+                }
+            }
+        }
+        best
+    };
+
+    let mut cost = vec![[[usize::MAX; 2]; 3]; order];
+    for &v in visit.iter().rev() {
+        for label in 0..=2u8 {
+            let mut total = graph.cost(v, label);
+            let mut delta = usize::MAX;
+            for c in children(v) {
+                let any = best_child(&cost, c, label, false).0;
+                let help = best_child(&cost, c, label, true).0;
+                total = total.saturating_add(any);
+                if help != usize::MAX {
+                    delta = delta.min(help - any);
+                }
+            }
+            cost[v as usize][label as usize][0] = total;
+            cost[v as usize][label as usize][1] = if required(problem, label).is_none() {
+                total
+            } else {
+                total.saturating_add(delta)
+            };
+        }
+    }
+
+    let root_label = (0..=2u8)
+        .min_by_key(|&label| cost[0][label as usize][1])
+        .unwrap_or(0);
+    let mut genes = vec![0u8; order];
+    let mut stack = vec![(0u32, root_label, 1usize)];
+    while let Some((v, label, sat)) = stack.pop() {
+        genes[v as usize] = label;
+        let helper = (sat == 1 && required(problem, label).is_some())
+            .then(|| {
+                children(v).min_by_key(|&c| {
+                    let any = best_child(&cost, c, label, false).0;
+                    best_child(&cost, c, label, true).0.saturating_sub(any)
+                })
+            })
+            .flatten();
+        for c in children(v) {
+            let (_, child_label, child_sat) = best_child(&cost, c, label, Some(c) == helper);
+            stack.push((c, child_label, child_sat));
+        }
+    }
+    genes
+}
+
+/// Optimal labelling of a cycle.
+///
+/// Walks the cycle from vertex `0`; a state holds the labels of the first two vertices
+/// and of the last two visited vertices, whose middle vertex is checked when the next
+/// label is chosen.
+#[allow(clippy::cast_possible_truncation)]
+fn cycle_labelling(problem: ProblemVariant, graph: &CsrGraph) -> Vec<u8> {
+    let order = graph.order();
+    let mut cycle = vec![0u32, graph.neighbors(0)[0]];
+    while cycle.len() < order {
+        let (previous, current) = (cycle[cycle.len() - 2], cycle[cycle.len() - 1]);
+        let next = graph.neighbors(current)[usize::from(graph.neighbors(current)[0] == previous)];
+        cycle.push(next);
+    }
+
+    let state = |first: u8, second: u8, previous: u8, current: u8| {
+        usize::from(((first * 3 + second) * 3 + previous) * 3 + current)
+    };
+    let labels = |state: usize| {
+        // Os quatro rótulos em base 3, do primeiro vértice ao atual.
+        [state / 27, state / 9 % 3, state / 3 % 3, state % 3].map(|label| label as u8)
+    };
+
+    let mut cost = vec![[usize::MAX; 81]; order];
+    let mut back = vec![[0u8; 81]; order];
+    for first in 0..=2u8 {
+        for second in 0..=2u8 {
+            cost[1][state(first, second, first, second)] =
+                graph.cost(cycle[0], first) + graph.cost(cycle[1], second);
+        }
+    }
+    for i in 1..order - 1 {
+        for s in 0..81 {
+            if cost[i][s] == usize::MAX {
+                continue;
+            }
+            let [first, second, previous, current] = labels(s);
+            for next in 0..=2u8 {
+                if !satisfied(problem, current, previous, next) {
+                    continue;
+                }
+                let t = state(first, second, current, next);
+                let candidate = cost[i][s] + graph.cost(cycle[i + 1], next);
+                if candidate < cost[i + 1][t] {
+                    cost[i + 1][t] = candidate;
+                    back[i + 1][t] = s as u8;
+                }
+            }
+        }
+    }
+
+    let last = (0..81)
+        .filter(|&s| {
+            let [first, second, previous, current] = labels(s);
+            satisfied(problem, current, previous, first)
+                && satisfied(problem, first, current, second)
+        })
+        .min_by_key(|&s| cost[order - 1][s])
+        .unwrap_or(0);
+
+    let mut genes = vec![0u8; order];
+    let mut s = last;
+    for i in (1..order).rev() {
+        let [first, _, _, current] = labels(s);
+        genes[cycle[i] as usize] = current;
+        genes[cycle[0] as usize] = first;
+        s = usize::from(back[i][s]);
+    }
+    genes
+}
+
+/// Helpers shared by the tests of the exact solvers.
+#[cfg(test)]
+pub(crate) mod testing {
+    use rand::Rng;
+
+    use super::*;
+
+    /// Attaches random costs to every vertex: label `1` costs `0..5` and label `2` costs
+    /// up to four more.
+    pub(crate) fn with_random_costs(graph: CsrGraph, rng: &mut impl Rng) -> CsrGraph {
+        let costs = graph
+            .vertices()
+            .map(|_| {
+                let one = rng.gen_range(0..5);
+                [one, one + rng.gen_range(0..5)]
+            })
+            .collect();
+        graph.with_costs(costs)
+    }
+
+    /// Asserts that `solution` is a feasible labelling of `graph` as cheap as the
+    /// [`brute_force`] optimum of `problem`, and that it is `None` only if no labelling is
+    /// feasible.
+    pub(crate) fn assert_brute_force_optimal(
+        problem: ProblemVariant,
+        graph: &CsrGraph,
+        solution: Option<&Chromosome>,
+    ) {
+        let optimum = brute_force(&*problem.build(), graph);
+        assert_eq!(
+            solution.map(Chromosome::fitness),
+            optimum.as_ref().map(Chromosome::fitness),
+            "{problem}"
+        );
+        if let Some(chromosome) = solution {
+            assert!(problem.build().is_feasible(chromosome, graph), "{problem}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::{testing::*, *};
+
+    const VARIANTS: [ProblemVariant; 8] = [
+        ProblemVariant::TotalRoman,
+        ProblemVariant::Roman,
+        ProblemVariant::DoubleRoman,
+        ProblemVariant::TotalDoubleRoman,
+        ProblemVariant::Italian,
+        ProblemVariant::TotalItalian,
+        ProblemVariant::IndependentRoman,
+        ProblemVariant::PerfectRoman,
+    ];
+
+    fn path(order: u32) -> CsrGraph {
+        let edges: Vec<(u32, u32)> = (1..order).map(|v| (v - 1, v)).collect();
+        CsrGraph::from_edges(order as usize, &edges)
+    }
+
+    fn cycle(order: u32) -> CsrGraph {
+        let mut edges: Vec<(u32, u32)> = (1..order).map(|v| (v - 1, v)).collect();
+        edges.push((0, order - 1));
+        CsrGraph::from_edges(order as usize, &edges)
+    }
+
+    fn complete(order: u32) -> CsrGraph {
+        let edges: Vec<(u32, u32)> = (0..order)
+            .flat_map(|u| (u + 1..order).map(move |v| (u, v)))
+            .collect();
+        CsrGraph::from_edges(order as usize, &edges)
+    }
+
+    fn complete_bipartite(left: u32, right: u32) -> CsrGraph {
+        let edges: Vec<(u32, u32)> = (0..left)
+            .flat_map(|u| (left..left + right).map(move |v| (u, v)))
+            .collect();
+        CsrGraph::from_edges((left + right) as usize, &edges)
+    }
+
+    fn random_tree(order: u32, rng: &mut StdRng) -> CsrGraph {
+        let edges: Vec<(u32, u32)> = (1..order).map(|v| (rng.gen_range(0..v), v)).collect();
+        CsrGraph::from_edges(order as usize, &edges)
+    }
+
+    /// Returns the class and the cost found by [`solve_class`], checking feasibility.
+    fn solve(problem: ProblemVariant, graph: &CsrGraph) -> (GraphClass, usize) {
+        let (class, chromosome) = solve_class(problem, graph).unwrap();
+        assert!(problem.build().is_feasible(&chromosome, graph));
+        let cost: usize = graph
+            .vertices()
+            .map(|v| graph.cost(v, chromosome.genes()[v as usize]))
+            .sum();
+        assert_eq!(chromosome.fitness(), cost);
+        (class, cost)
+    }
+
+    /// Compares [`solve_class`] with [`brute_force`] for every variant: the supported ones
+    /// must reach the optimum and the others must be declined.
+    fn assert_matches_brute_force(graph: &CsrGraph) {
+        for problem in VARIANTS {
+            match solve_class(problem, graph) {
+                Some((_, chromosome)) => {
+                    assert_brute_force_optimal(problem, graph, Some(&chromosome));
+                }
+                None => assert!(
+                    !matches!(problem, ProblemVariant::TotalRoman | ProblemVariant::Roman)
+                        || graph.is_weighted()
+                            && matches!(
+                                recognize(graph),
+                                Some(GraphClass::Complete | GraphClass::CompleteBipartite)
+                            ),
+                    "{problem} was declined"
+                ),
+            }
+        }
+    }
+
+    #[test]
+    fn known_values_of_paths_and_cycles() {
+        for n in 2..=40 {
+            let roman = (2 * n as usize).div_ceil(3);
+            // K_2 e K_3 são reconhecidos como grafos completos.
+            let path = path(n);
+            assert!(n < 3 || recognize(&path) == Some(GraphClass::Path));
+            assert_eq!(solve(ProblemVariant::TotalRoman, &path).1, n as usize);
+            assert_eq!(solve(ProblemVariant::Roman, &path).1, roman);
+
+            if n >= 3 {
+                let cycle = cycle(n);
+                assert!(n < 4 || recognize(&cycle) == Some(GraphClass::Cycle));
+                assert_eq!(solve(ProblemVariant::TotalRoman, &cycle).1, n as usize);
+                assert_eq!(solve(ProblemVariant::Roman, &cycle).1, roman);
+            }
+        }
+    }
+
+    #[test]
+    fn known_values_of_complete_graphs_and_stars() {
+        for n in 4..=20 {
+            let complete = complete(n);
+            assert_eq!(recognize(&complete), Some(GraphClass::Complete));
+            assert_eq!(solve(ProblemVariant::TotalRoman, &complete).1, 3);
+            assert_eq!(solve(ProblemVariant::Roman, &complete).1, 2);
+
+            let star = complete_bipartite(1, n);
+            assert_eq!(recognize(&star), Some(GraphClass::Star));
+            assert_eq!(solve(ProblemVariant::TotalRoman, &star).1, 3);
+            assert_eq!(solve(ProblemVariant::Roman, &star).1, 2);
+        }
+    }
+
+    #[test]
+    fn known_values_of_complete_bipartite_graphs() {
+        for m in 2..=8 {
+            for n in m.max(3)..=12 {
+                let graph = complete_bipartite(m, n);
+                assert_eq!(recognize(&graph), Some(GraphClass::CompleteBipartite));
+                assert_eq!(solve(ProblemVariant::TotalRoman, &graph).1, 4);
+                let roman = if m == 2 { 3 } else { 4 };
+                assert_eq!(
+                    solve(ProblemVariant::Roman, &graph),
+                    (GraphClass::CompleteBipartite, roman)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn matches_brute_force_on_small_classes() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut graphs = Vec::new();
+        for n in 2..=8 {
+            graphs.push(path(n));
+            graphs.push(complete(n));
+            graphs.push(complete_bipartite(1, n - 1));
+            if n >= 3 {
+                graphs.push(cycle(n));
+            }
+            for m in 2..=n / 2 {
+                graphs.push(complete_bipartite(m, n - m));
+            }
+            for _ in 0..5 {
+                graphs.push(random_tree(n, &mut rng));
+            }
+        }
+
+        for graph in graphs {
+            assert!(recognize(&graph).is_some());
+            assert_matches_brute_force(&graph);
+            assert_matches_brute_force(&with_random_costs(graph, &mut rng));
+        }
+    }
+
+    #[test]
+    fn other_graphs_are_not_recognized() {
+        let mut edges: Vec<(u32, u32)> = (1..5).map(|v| (v - 1, v)).collect();
+        edges.push((0, 2));
+        assert_eq!(recognize(&CsrGraph::from_edges(5, &edges)), None);
+        assert_eq!(
+            recognize(&complete(4).induced_subgraph(&[0, 1, 2])),
+            Some(GraphClass::Complete)
+        );
+    }
+}
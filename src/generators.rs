@@ -0,0 +1,641 @@
+use std::{
+    cmp::Reverse,
+    collections::{BTreeSet, BinaryHeap, HashSet},
+    fmt,
+    io::{self, Write},
+    str::FromStr,
+};
+
+use rand::{seq::SliceRandom, Rng, RngCore};
+
+use crate::csr::CsrGraph;
+
+/// Attempts of the pairing model before [`GraphFamily::generate`] gives up on a random
+/// regular graph.
+pub const MAX_PAIRING_ATTEMPTS: usize = 100_000;
+
+/// Draws of G(n, p) or G(n, m) before [`GraphFamily::generate`] gives up on a graph without
+/// isolated vertices.
+pub const MAX_ISOLATED_ATTEMPTS: usize = 1_000;
+
+/// A family of synthetic graphs.
+///
+/// Parsed from and formatted as `regular:N:R`, `gnp:N:P`, `gnm:N:M`, `ba:N:M`,
+/// `grid:ROWS:COLS`, `torus:ROWS:COLS`, `hypercube:D` and `tree:N`.
+///
+/// Generated graphs never have isolated vertices, which the edge-list format cannot
+/// represent: parameters forcing one are rejected, and random graphs with one are drawn
+/// again.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GraphFamily {
+    /// Random `degree`-regular graph on `order` vertices, from the pairing model with
+    /// rejection of loops and multiple edges.
+    Regular {
+        /// Number of vertices.
+        order: usize,
+        /// Degree of every vertex.
+        degree: usize,
+    },
+    /// Erdős–Rényi graph: every edge is present with probability `probability`.
+    Gnp {
+        /// Number of vertices.
+        order: usize,
+        /// Edge probability.
+        probability: f64,
+    },
+    /// Uniform random graph with exactly `size` edges.
+    Gnm {
+        /// Number of vertices.
+        order: usize,
+        /// Number of edges.
+        size: usize,
+    },
+    /// Barabási–Albert preferential attachment: starting from a star `K_{1,edges}`, every
+    /// new vertex is joined to `edges` distinct vertices chosen with probability
+    /// proportional to their degree.
+    BarabasiAlbert {
+        /// Number of vertices.
+        order: usize,
+        /// Edges added with each new vertex.
+        edges: usize,
+    },
+    /// Grid graph `P_rows □ P_cols`.
+    Grid {
+        /// Number of rows.
+        rows: usize,
+        /// Number of columns.
+        cols: usize,
+    },
+    /// Torus `C_rows □ C_cols`.
+    Torus {
+        /// Number of rows.
+        rows: usize,
+        /// Number of columns.
+        cols: usize,
+    },
+    /// Hypercube `Q_dimension` on `2^dimension` vertices.
+    Hypercube {
+        /// Dimension.
+        dimension: usize,
+    },
+    /// Uniform random labelled tree, decoded from a random Prüfer sequence.
+    Tree {
+        /// Number of vertices.
+        order: usize,
+    },
+}
+
+impl FromStr for GraphFamily {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split(':').collect();
+        let number = |index: usize| -> Result<usize, String> {
+            parts[index]
+                .parse()
+                .map_err(|_| format!("Invalid value in {s}: {}", parts[index]))
+        };
+
+        let family = match (parts[0], parts.len()) {
+            ("regular", 3) => Self::Regular {
+                order: number(1)?,
+                degree: number(2)?,
+            },
+            ("gnp", 3) => Self::Gnp {
+                order: number(1)?,
+                probability: parts[2]
+                    .parse()
+                    .map_err(|_| format!("Invalid value in {s}: {}", parts[2]))?,
+            },
+            ("gnm", 3) => Self::Gnm {
+                order: number(1)?,
+                size: number(2)?,
+            },
+            ("ba", 3) => Self::BarabasiAlbert {
+                order: number(1)?,
+                edges: number(2)?,
+            },
+            ("grid", 3) => Self::Grid {
+                rows: number(1)?,
+                cols: number(2)?,
+            },
+            ("torus", 3) => Self::Torus {
+                rows: number(1)?,
+                cols: number(2)?,
+            },
+            ("hypercube", 2) => Self::Hypercube {
+                dimension: number(1)?,
+            },
+            ("tree", 2) => Self::Tree { order: number(1)? },
+            _ => {
+                return Err(format!(
+                    "Invalid graph family: {s} (expected regular:N:R, gnp:N:P, gnm:N:M, ba:N:M, \
+                     grid:ROWS:COLS, torus:ROWS:COLS, hypercube:D or tree:N)"
+                ))
+            }
+        };
+        family.check()?;
+        Ok(family)
+    }
+}
+
+impl fmt::Display for GraphFamily {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Regular { order, degree } => write!(f, "regular:{order}:{degree}"),
+            Self::Gnp { order, probability } => write!(f, "gnp:{order}:{probability}"),
+            Self::Gnm { order, size } => write!(f, "gnm:{order}:{size}"),
+            Self::BarabasiAlbert { order, edges } => write!(f, "ba:{order}:{edges}"),
+            Self::Grid { rows, cols } => write!(f, "grid:{rows}:{cols}"),
+            Self::Torus { rows, cols } => write!(f, "torus:{rows}:{cols}"),
+            Self::Hypercube { dimension } => write!(f, "hypercube:{dimension}"),
+            Self::Tree { order } => write!(f, "tree:{order}"),
+        }
+    }
+}
+
+impl GraphFamily {
+    /// Checks that the parameters describe a graph that exists and can be drawn without
+    /// isolated vertices.
+    ///
+    /// # Errors
+    /// Returns an error describing the first invalid parameter.
+    pub fn check(&self) -> Result<(), String> {
+        let order = self.order();
+        if order < 2 || order > u32::MAX as usize {
+            return Err(format!(
+                "{self}: the number of vertices must be between 2 and {}",
+                u32::MAX
+            ));
+        }
+        match *self {
+            Self::Regular { order, degree } if degree >= order || order * degree % 2 == 1 => Err(
+                format!("{self}: a {degree}-regular graph on {order} vertices does not exist"),
+            ),
+            Self::Regular { degree: 0, .. } => {
+                Err(format!("{self}: the degree must be at least 1"))
+            }
+            Self::Gnp { probability, .. } if !(0.0..=1.0).contains(&probability) => {
+                Err(format!("{self}: the probability must be between 0 and 1"))
+            }
+            Self::Gnp { probability, .. } if probability <= 0.0 => {
+                Err(format!("{self}: the probability must be greater than 0"))
+            }
+            Self::Gnm { order, size } if size > order * (order - 1) / 2 => Err(format!(
+                "{self}: a simple graph on {order} vertices has at most {} edges",
+                order * (order - 1) / 2
+            )),
+            Self::Gnm { order, size } if 2 * size < order => Err(format!(
+                "{self}: {size} edges leave isolated vertices among {order} vertices"
+            )),
+            Self::BarabasiAlbert { order, edges } if edges == 0 || edges >= order => Err(format!(
+                "{self}: the number of edges per vertex must be between 1 and {}",
+                order - 1
+            )),
+            Self::Torus { rows, cols } if rows < 3 || cols < 3 => Err(format!(
+                "{self}: a torus needs at least 3 rows and 3 columns"
+            )),
+            Self::Hypercube { dimension } if dimension > 31 => {
+                Err(format!("{self}: the dimension must be at most 31"))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the number of vertices of the generated graphs.
+    #[must_use]
+    pub fn order(&self) -> usize {
+        match *self {
+            Self::Regular { order, .. }
+            | Self::Gnp { order, .. }
+            | Self::Gnm { order, .. }
+            | Self::BarabasiAlbert { order, .. }
+            | Self::Tree { order } => order,
+            Self::Grid { rows, cols } | Self::Torus { rows, cols } => rows.saturating_mul(cols),
+            Self::Hypercube { dimension } => u32::try_from(dimension)
+                .ok()
+                .and_then(|dimension| 1usize.checked_shl(dimension))
+                .unwrap_or(0),
+        }
+    }
+
+    /// Generates a graph of the family.
+    ///
+    /// The deterministic families (grids, tori, hypercubes) ignore `rng`; the random ones
+    /// give the same graph for the same sequence of random numbers.
+    ///
+    /// # Parameters
+    /// - `rng: &mut dyn RngCore`: Random number generator, typically seeded.
+    ///
+    /// # Returns
+    /// - The generated graph, on the vertices `0..order`.
+    ///
+    /// # Errors
+    /// Returns an error if the parameters are invalid (see [`GraphFamily::check`]), if the
+    /// pairing model rejects [`MAX_PAIRING_ATTEMPTS`] pairings in a row, which happens for
+    /// large degrees, or if [`MAX_ISOLATED_ATTEMPTS`] draws of G(n, p) or G(n, m) in a row
+    /// have isolated vertices, which happens for sparse graphs.
+    pub fn generate(&self, rng: &mut dyn RngCore) -> Result<CsrGraph, String> {
+        self.check()?;
+        let edges = match *self {
+            Self::Regular { order, degree } => regular(order, degree, rng).ok_or_else(|| {
+                format!("{self}: the pairing model failed {MAX_PAIRING_ATTEMPTS} times")
+            })?,
+            Self::Gnp { order, probability } => {
+                without_isolated(order, || gnp(order, probability, rng)).ok_or_else(|| {
+                    format!("{self}: {MAX_ISOLATED_ATTEMPTS} draws had isolated vertices")
+                })?
+            }
+            Self::Gnm { order, size } => without_isolated(order, || gnm(order, size, rng))
+                .ok_or_else(|| {
+                    format!("{self}: {MAX_ISOLATED_ATTEMPTS} draws had isolated vertices")
+                })?,
+            Self::BarabasiAlbert { order, edges } => barabasi_albert(order, edges, rng),
+            Self::Grid { rows, cols } => lattice(rows, cols, false),
+            Self::Torus { rows, cols } => lattice(rows, cols, true),
+            Self::Hypercube { dimension } => hypercube(dimension),
+            Self::Tree { order } => tree(order, rng),
+        };
+        Ok(CsrGraph::from_edges(self.order(), &edges))
+    }
+}
+
+/// Draws edge lists until every one of the `order` vertices has an edge.
+fn without_isolated(
+    order: usize,
+    mut draw: impl FnMut() -> Vec<(u32, u32)>,
+) -> Option<Vec<(u32, u32)>> {
+    (0..MAX_ISOLATED_ATTEMPTS).map(|_| draw()).find(|edges| {
+        let mut covered = vec![false; order];
+        for &(u, v) in edges {
+            covered[u as usize] = true;
+            covered[v as usize] = true;
+        }
+        covered.into_iter().all(|covered| covered)
+    })
+}
+
+/// Pairs `order * degree` points uniformly at random, rejecting pairings with loops or
+/// multiple edges. Degrees above `(order - 1) / 2` pair the complement instead.
+#[allow(clippy::cast_possible_truncation)]
+fn regular(order: usize, degree: usize, rng: &mut dyn RngCore) -> Option<Vec<(u32, u32)>> {
+    let complement = order - 1 - degree;
+    if complement < degree {
+        let absent: HashSet<(u32, u32)> = regular(order, complement, rng)?
+            .into_iter()
+            .map(|(u, v)| (u.min(v), u.max(v)))
+            .collect();
+        return Some(
+            complete_edges(order)
+                .into_iter()
+                .filter(|edge| !absent.contains(edge))
+                .collect(),
+        );
+    }
+
+    let mut points: Vec<u32> = (0..order as u32)
+        .flat_map(|v| std::iter::repeat(v).take(degree))
+        .collect();
+
+    'attempt: for _ in 0..MAX_PAIRING_ATTEMPTS {
+        points.shuffle(rng);
+        let mut seen = HashSet::with_capacity(points.len() / 2);
+        for pair in points.chunks_exact(2) {
+            let edge = (pair[0].min(pair[1]), pair[0].max(pair[1]));
+            if edge.0 == edge.1 || !seen.insert(edge) {
+                continue 'attempt;
+            }
+        }
+        return Some(
+            points
+                .chunks_exact(2)
+                .map(|pair| (pair[0], pair[1]))
+                .collect(),
+        );
+    }
+    None
+}
+
+/// Draws G(n, p) in time linear in the number of edges by skipping geometrically
+/// distributed runs of absent edges (Batagelj and Brandes).
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_possible_wrap,
+    clippy::cast_sign_loss,
+    clippy::cast_precision_loss
+)]
+fn gnp(order: usize, probability: f64, rng: &mut dyn RngCore) -> Vec<(u32, u32)> {
+    let mut edges = Vec::new();
+    if probability <= 0.0 {
+        return edges;
+    }
+    if probability >= 1.0 {
+        return complete_edges(order);
+    }
+
+    let log_q = (1.0 - probability).ln();
+    let (mut v, mut w) = (1usize, -1i64);
+    while v < order {
+        let r: f64 = rng.gen();
+        w += 1 + ((1.0 - r).ln() / log_q).floor() as i64;
+        while w >= v as i64 && v < order {
+            w -= v as i64;
+            v += 1;
+        }
+        if v < order {
+            edges.push((v as u32, w as u32));
+        }
+    }
+    edges
+}
+
+/// Edges of the complete graph on `order` vertices.
+#[allow(clippy::cast_possible_truncation)]
+fn complete_edges(order: usize) -> Vec<(u32, u32)> {
+    (0..order as u32)
+        .flat_map(|u| (u + 1..order as u32).map(move |v| (u, v)))
+        .collect()
+}
+
+/// Samples `size` distinct edges uniformly; dense graphs sample the absent edges instead.
+#[allow(clippy::cast_possible_truncation)]
+fn gnm(order: usize, size: usize, rng: &mut dyn RngCore) -> Vec<(u32, u32)> {
+    let total = order * (order - 1) / 2;
+    let sample = size.min(total - size);
+
+    let mut chosen = BTreeSet::new();
+    while chosen.len() < sample {
+        let u = rng.gen_range(0..order as u32);
+        let v = rng.gen_range(0..order as u32);
+        if u != v {
+            chosen.insert((u.min(v), u.max(v)));
+        }
+    }
+
+    if sample == size {
+        chosen.into_iter().collect()
+    } else {
+        complete_edges(order)
+            .into_iter()
+            .filter(|edge| !chosen.contains(edge))
+            .collect()
+    }
+}
+
+/// Preferential attachment from a star `K_{1,edges}`.
+#[allow(clippy::cast_possible_truncation)]
+fn barabasi_albert(order: usize, edges: usize, rng: &mut dyn RngCore) -> Vec<(u32, u32)> {
+    let mut list: Vec<(u32, u32)> = (1..=edges as u32).map(|v| (0, v)).collect();
+    // Cada vértice aparece uma vez por aresta incidente: sortear desta lista é sortear
+    // proporcionalmente ao grau.
+    let mut endpoints: Vec<u32> = list.iter().flat_map(|&(u, v)| [u, v]).collect();
+
+    for v in edges as u32 + 1..order as u32 {
+        let mut targets = BTreeSet::new();
+        while targets.len() < edges {
+            targets.insert(endpoints[rng.gen_range(0..endpoints.len())]);
+        }
+        for target in targets {
+            list.push((target, v));
+            endpoints.extend([target, v]);
+        }
+    }
+    list
+}
+
+/// Grid `P_rows □ P_cols`, or torus `C_rows □ C_cols` if `wrap`.
+#[allow(clippy::cast_possible_truncation)]
+fn lattice(rows: usize, cols: usize, wrap: bool) -> Vec<(u32, u32)> {
+    let index = |row: usize, col: usize| (row * cols + col) as u32;
+    let mut edges = Vec::with_capacity(2 * rows * cols);
+    for row in 0..rows {
+        for col in 0..cols {
+            if col + 1 < cols || wrap {
+                edges.push((index(row, col), index(row, (col + 1) % cols)));
+            }
+            if row + 1 < rows || wrap {
+                edges.push((index(row, col), index((row + 1) % rows, col)));
+            }
+        }
+    }
+    edges
+}
+
+/// Hypercube: vertices are bit strings, adjacent when they differ in one bit.
+fn hypercube(dimension: usize) -> Vec<(u32, u32)> {
+    (0..1u32 << dimension)
+        .flat_map(|v| {
+            (0..dimension)
+                .map(move |bit| (v, v ^ (1 << bit)))
+                .filter(|&(v, u)| v < u)
+        })
+        .collect()
+}
+
+/// Decodes a uniformly random Prüfer sequence.
+#[allow(clippy::cast_possible_truncation)]
+fn tree(order: usize, rng: &mut dyn RngCore) -> Vec<(u32, u32)> {
+    if order < 2 {
+        return Vec::new();
+    }
+
+    let sequence: Vec<u32> = (0..order - 2)
+        .map(|_| rng.gen_range(0..order as u32))
+        .collect();
+    let mut degree = vec![1usize; order];
+    for &v in &sequence {
+        degree[v as usize] += 1;
+    }
+    let mut leaves: BinaryHeap<Reverse<u32>> = (0..order as u32)
+        .filter(|&v| degree[v as usize] == 1)
+        .map(Reverse)
+        .collect();
+
+    let mut edges = Vec::with_capacity(order - 1);
+    for &v in &sequence {
+        let Some(Reverse(leaf)) = leaves.pop() else {
+            break;
+        };
+        edges.push((leaf, v));
+        degree[v as usize] -= 1;
+        if degree[v as usize] == 1 {
+            leaves.push(Reverse(v));
+        }
+    }
+    let last: Vec<u32> = leaves.into_iter().map(|Reverse(v)| v).collect();
+    edges.push((last[0], last[1]));
+    edges
+}
+
+/// Writes `graph` as an edge list readable by [`crate::utils::build_graph`].
+///
+/// The `comment` lines come first, prefixed with `#`, followed by one `u v` line per edge
+/// with `u < v`.
+///
+/// # Errors
+/// Returns an error of kind [`io::ErrorKind::InvalidInput`] if `graph` has an isolated
+/// vertex, which an edge list cannot represent, and any error of the writer.
+pub fn write_edge_list(
+    graph: &CsrGraph,
+    comment: &[String],
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    if let Some(v) = graph.vertices().find(|&v| graph.degree(v) == 0) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("vertex {v} is isolated and cannot be written to an edge list"),
+        ));
+    }
+
+    for line in comment {
+        writeln!(out, "# {line}")?;
+    }
+    for u in graph.vertices() {
+        for &v in graph.neighbors(u).iter().filter(|&&v| u < v) {
+            writeln!(out, "{u} {v}")?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::utils::build_weighted_graph;
+
+    const FAMILIES: [&str; 9] = [
+        "regular:50:3",
+        "regular:12:9",
+        "gnp:40:0.1",
+        "gnm:30:40",
+        "ba:50:2",
+        "grid:4:6",
+        "torus:4:6",
+        "hypercube:5",
+        "tree:40",
+    ];
+
+    fn generate(family: &str, seed: u64) -> CsrGraph {
+        let family: GraphFamily = family.parse().unwrap();
+        family.generate(&mut StdRng::seed_from_u64(seed)).unwrap()
+    }
+
+    fn edge_list(graph: &CsrGraph) -> String {
+        let mut out = Vec::new();
+        write_edge_list(graph, &["test".to_string()], &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn families_round_trip_through_their_names() {
+        for name in FAMILIES {
+            let family: GraphFamily = name.parse().unwrap();
+            assert_eq!(family.to_string(), name);
+        }
+    }
+
+    #[test]
+    fn the_same_seed_gives_the_same_graph() {
+        for name in FAMILIES {
+            for seed in 0..5 {
+                assert_eq!(
+                    edge_list(&generate(name, seed)),
+                    edge_list(&generate(name, seed))
+                );
+            }
+        }
+        assert_ne!(
+            edge_list(&generate("regular:50:3", 1)),
+            edge_list(&generate("regular:50:3", 2))
+        );
+        assert_ne!(
+            edge_list(&generate("gnp:40:0.1", 1)),
+            edge_list(&generate("gnp:40:0.1", 2))
+        );
+    }
+
+    #[test]
+    fn graphs_have_the_expected_structure() {
+        for seed in 0..20 {
+            for (order, degree) in [(50, 3), (10, 4), (12, 9), (7, 6)] {
+                let graph = generate(&format!("regular:{order}:{degree}"), seed);
+                assert_eq!(graph.order(), order);
+                assert!(graph.vertices().all(|v| graph.degree(v) == degree));
+            }
+            assert_eq!(generate("gnm:30:40", seed).edge_count(), 40);
+            assert_eq!(generate("gnm:10:40", seed).edge_count(), 40);
+            assert_eq!(generate("ba:50:2", seed).edge_count(), 2 + 47 * 2);
+            assert_eq!(generate("tree:40", seed).edge_count(), 39);
+            assert_eq!(generate("tree:40", seed).components().len(), 1);
+        }
+        assert_eq!(generate("grid:4:6", 0).edge_count(), 4 * 5 + 3 * 6);
+        assert_eq!(generate("torus:4:6", 0).edge_count(), 2 * 24);
+        assert_eq!(generate("hypercube:5", 0).edge_count(), 5 * 16);
+        assert_eq!(generate("gnp:10:1", 0).edge_count(), 45);
+    }
+
+    #[test]
+    fn generated_graphs_have_no_isolated_vertices() {
+        for name in FAMILIES
+            .iter()
+            .chain(&["gnp:60:0.1", "gnm:40:60", "tree:2"])
+        {
+            for seed in 0..20 {
+                let graph = generate(name, seed);
+                assert!(graph.vertices().all(|v| graph.degree(v) > 0), "{name}");
+            }
+        }
+
+        let sparse: GraphFamily = "gnp:100:0.001".parse().unwrap();
+        assert!(sparse.generate(&mut StdRng::seed_from_u64(0)).is_err());
+        let isolated = CsrGraph::from_edges(3, &[(0, 1)]);
+        assert!(write_edge_list(&isolated, &[], &mut Vec::new()).is_err());
+    }
+
+    #[test]
+    fn invalid_parameters_are_rejected() {
+        for name in [
+            "regular:5:3",
+            "regular:3:5",
+            "regular:6:0",
+            "gnp:10:1.5",
+            "gnp:10:0",
+            "gnm:5:11",
+            "gnm:10:4",
+            "ba:5:5",
+            "ba:5:0",
+            "grid:1:1",
+            "torus:2:5",
+            "hypercube:0",
+            "hypercube:40",
+            "tree:1",
+        ] {
+            assert!(name.parse::<GraphFamily>().is_err(), "{name}");
+        }
+        for name in ["foo:3", "tree", "grid:3", "gnp:10:x"] {
+            assert!(name.parse::<GraphFamily>().is_err(), "{name}");
+        }
+    }
+
+    #[test]
+    fn edge_lists_are_read_back_by_the_loader() {
+        let path = std::env::temp_dir().join(format!("generators-{}.txt", std::process::id()));
+        for name in FAMILIES {
+            let graph = generate(name, 7);
+            std::fs::write(&path, edge_list(&graph)).unwrap();
+            let loaded = build_weighted_graph(path.to_str().unwrap(), None).unwrap();
+
+            assert_eq!(loaded.order(), graph.order(), "{name}");
+            assert_eq!(loaded.edge_count(), graph.edge_count(), "{name}");
+            let degrees = |graph: &CsrGraph| {
+                let mut degrees: Vec<usize> = graph.vertices().map(|v| graph.degree(v)).collect();
+                degrees.sort_unstable();
+                degrees
+            };
+            assert_eq!(degrees(&loaded), degrees(&graph), "{name}");
+        }
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -1,58 +1,80 @@
-use std::collections::HashMap;
-
-use kambo_graph::{graphs::simple::UndirectedGraph, Graph};
+use crate::csr::CsrGraph;
 
 /// Structure representing a chromosome in the CL-Total-RDGA.
 ///
-/// Each chromosome stores a configuration of labels \{0, 1, 2\} for the vertices of a graph,
-/// with the goal of satisfying the conditions of total Roman domination.
+/// Each chromosome stores a configuration of labels \{0, 1, 2\} (\{0, 1, 2, 3\} for the double
+/// Roman variants) for the vertices of a graph, with the goal of satisfying the conditions of
+/// a Roman domination [`Problem`](super::Problem).
 ///
 /// # Fields
 /// - `genes: Vec<u8>`: A vector that stores the labels for each vertex in the graph.
-///   - `0`: Must have a vertex labeled with the value `2` in its neighborhood.
-///   - `1 | 2`: Must have a vertex labeled with `f > 0` in its neighborhood.
-/// - `neighbors_cache`: cache
+/// - `weight: usize`: The cost of the labelling, the sum of the vertex costs of the genes
+///   (see [`CsrGraph::cost`]), kept up to date on every label change.
+/// - `neighbor_counts`: Per-vertex number of neighbours with each label. Built on the first
+///   call to [`Problem::repair`](super::Problem::repair) or [`Chromosome::track_neighbors`]
+///   and then updated in `O(deg)` by [`Chromosome::set_label`].
 #[derive(Clone, Debug)]
 pub struct Chromosome {
     genes: Vec<u8>,
-    neighbors_cache: Option<NeighborsCache>,
+    weight: usize,
+    neighbor_counts: Option<NeighborCounts>,
 }
 
-#[derive(Clone, Debug)]
-struct NeighborsCache {
-    has_one_neighbor: Vec<bool>,
-    has_two_neighbor: Vec<bool>,
-    vertex_neighbors: HashMap<u32, Vec<u32>>,
-}
+/// Number of distinct labels, `0` to [`Chromosome::MAX_LABEL`].
+const LABELS: usize = Chromosome::MAX_LABEL as usize + 1;
+
+/// Per-vertex histogram of the labels of its neighbours.
+type NeighborCounts = Vec<[u32; LABELS]>;
 
 impl Chromosome {
+    /// Largest label a gene can hold, used by the double Roman variants.
+    pub const MAX_LABEL: u8 = 3;
+
     /// Creates a new chromosome from a vector of genes.
     ///
     /// # Parameters
     /// - `genes: Vec<u8>`: The vector containing the initial labels for the vertices.
+    /// - `graph: &CsrGraph`: The graph labelled by `genes`, whose vertex costs give the
+    ///   weight.
     ///
     /// # Returns
     /// - Returns a new instance of `Chromosome`.
+    ///
+    /// # Panics
+    /// - If `genes` does not have one label per vertex of `graph`.
+    /// - If `graph` carries vertex costs and a gene is greater than `2`.
     #[inline]
     #[must_use]
-    pub fn new(genes: Vec<u8>) -> Self {
+    pub fn new(genes: Vec<u8>, graph: &CsrGraph) -> Self {
+        assert_eq!(
+            genes.len(),
+            graph.order(),
+            "The chromosome must have one gene per vertex"
+        );
+        let weight = graph
+            .vertices()
+            .map(|v| graph.cost(v, genes[v as usize]))
+            .sum();
         Self {
             genes,
-            neighbors_cache: None,
+            weight,
+            neighbor_counts: None,
         }
     }
 
-    /// Calculates the "fitness" value of the chromosome.
+    /// Returns the "fitness" value of the chromosome.
     ///
-    /// The fitness is defined as the total weight of the total Roman domination function,
-    /// which is the sum of all values in the gene vector.
+    /// The fitness is defined as the total weight of the Roman domination function: the
+    /// sum of the costs of the labels, which on graphs without vertex costs is the sum of
+    /// all values in the gene vector. The weight is maintained incrementally, so this is
+    /// `O(1)`.
     ///
     /// # Returns
-    /// - A `u32` value corresponding to the sum of the genes.
+    /// - A `usize` value corresponding to the cost of the labelling.
     #[inline]
     #[must_use]
     pub fn fitness(&self) -> usize {
-        self.genes.iter().map(|&x| usize::from(x)).sum()
+        self.weight
     }
 
     /// Returns a slice containing the genes of the chromosome.
@@ -65,132 +87,167 @@ impl Chromosome {
         &self.genes
     }
 
-    fn initialize_cache(&mut self, graph: &UndirectedGraph<u32>) {
-        let vertex_count = self.genes.len();
-        let mut cache = NeighborsCache {
-            has_one_neighbor: vec![false; vertex_count],
-            has_two_neighbor: vec![false; vertex_count],
-            vertex_neighbors: HashMap::with_capacity(vertex_count),
-        };
-
-        for vertex in graph.vertices() {
-            let neighbors: Vec<u32> = graph
-                .neighbors(vertex)
-                .map(|n| n.copied().collect())
-                .unwrap_or_default();
-
-            cache.vertex_neighbors.insert(*vertex, neighbors);
+    /// Builds the per-vertex neighbour counts if they are not available yet.
+    ///
+    /// This costs `O(n + m)` once; afterwards every [`Chromosome::set_label`] keeps the
+    /// counts up to date in `O(deg)`.
+    ///
+    /// # Panics
+    /// Panics if a gene is greater than [`Chromosome::MAX_LABEL`].
+    pub fn track_neighbors(&mut self, graph: &CsrGraph) {
+        if self.neighbor_counts.is_some() {
+            return;
         }
 
-        self.update_cache(&mut cache);
-        self.neighbors_cache = Some(cache);
-    }
-
-    fn update_cache(&self, cache: &mut NeighborsCache) {
-        for (v, neighbors) in &cache.vertex_neighbors {
-            let v_index = *v as usize;
-            cache.has_one_neighbor[v_index] = neighbors.iter().any(|&n| self.genes[n as usize] > 0);
-
-            cache.has_two_neighbor[v_index] =
-                neighbors.iter().any(|&n| self.genes[n as usize] == 2);
+        let mut counts = vec![[0; LABELS]; self.genes.len()];
+        for v in graph.vertices() {
+            let histogram = &mut counts[v as usize];
+            for &n in graph.neighbors(v) {
+                histogram[usize::from(self.genes[n as usize])] += 1;
+            }
         }
+
+        self.neighbor_counts = Some(counts);
     }
 
-    /// Corrects the chromosome's gene configuration to satisfy the conditions of total Roman domination.
-    ///
-    /// This method ensures that each vertex in the graph satisfies the following conditions:
-    /// - Vertices labeled `0` must have at least one neighbor labeled `2`.
-    /// - Vertices labeled `1` or `2` must have at least one neighbor with a label greater than `0`.
-    ///
-    /// The method iteratively adjusts the labels of vertices in the chromosome until these conditions are met.
+    /// Returns the number of neighbours of `vertex` labelled exactly `label`.
     ///
-    /// # Parameters
-    /// - `graph: &UndirectedGraph<u32>`: The graph representing the structure and relationships
-    ///   between vertices. The graph is used to determine the neighbors of each vertex.
-    ///
-    /// # Details
-    /// - If the `neighbors_cache` is not initialized, this method initializes it before proceeding.
-    /// - The method uses a cache to track which vertices have neighbors with specific labels (`1` or `2`)
-    ///   to optimize label correction.
-    /// - It modifies the gene vector in-place, ensuring all conditions of total Roman domination
-    ///   are satisfied.
+    /// # Panics
+    /// Panics if the neighbour counts have not been built; see [`Chromosome::track_neighbors`].
+    #[inline]
+    #[must_use]
+    pub fn labelled_neighbors(&self, vertex: u32, label: u8) -> u32 {
+        self.counts(vertex)[usize::from(label)]
+    }
+
+    /// Returns the number of neighbours of `vertex` labelled `label` or higher.
     ///
     /// # Panics
-    /// - The method will `panic` if a vertex has an invalid label. Valid labels are:
-    ///   - `0`: Requires at least one neighbor labeled `2`.
-    ///   - `1`: Requires at least one neighbor labeled with a value greater than `0`.
-    ///   - `2`: Requires at least one neighbor labeled with a value greater than `0`.
+    /// Panics if the neighbour counts have not been built; see [`Chromosome::track_neighbors`].
+    #[inline]
+    #[must_use]
+    pub fn neighbors_at_least(&self, vertex: u32, label: u8) -> u32 {
+        self.counts(vertex)[usize::from(label)..].iter().sum()
+    }
+
+    fn counts(&self, vertex: u32) -> &[u32; LABELS] {
+        &self
+            .neighbor_counts
+            .as_ref()
+            .expect("Neighbor counts are not initialized; call track_neighbors first")
+            [vertex as usize]
+    }
+
+    /// Changes the label of `vertex`, updating the weight and, if tracked, the
+    /// neighbour counts of its neighbours in `O(deg(vertex))`.
     ///
-    ///   The panic message includes the vertex index and the invalid label value, providing context for debugging:
-    ///   ```text
-    ///   Vertex with invalid label found! Index: {vertex_idx}, Value: {invalid}.
-    ///   Valid labels are: 0, 1, or 2.
-    pub fn fix(&mut self, graph: &UndirectedGraph<u32>) {
-        if self.neighbors_cache.is_none() {
-            self.initialize_cache(graph);
+    /// # Parameters
+    /// - `vertex: u32`: The vertex to relabel.
+    /// - `label: u8`: The new label, at most [`Chromosome::MAX_LABEL`].
+    /// - `graph: &CsrGraph`: The graph the chromosome refers to.
+    pub fn set_label(&mut self, vertex: u32, label: u8, graph: &CsrGraph) {
+        let v_index = vertex as usize;
+        let old = self.genes[v_index];
+        if old == label {
+            return;
         }
 
-        let mut cache = self.neighbors_cache.take().unwrap();
-        let mut modified = true;
-        let mut visited = vec![false; self.genes.len()];
+        self.genes[v_index] = label;
+        self.weight = self.weight - graph.cost(vertex, old) + graph.cost(vertex, label);
 
-        while modified {
-            modified = false;
+        if let Some(counts) = &mut self.neighbor_counts {
+            for &n in graph.neighbors(vertex) {
+                let histogram = &mut counts[n as usize];
+                histogram[usize::from(old)] -= 1;
+                histogram[usize::from(label)] += 1;
+            }
+        }
+    }
+}
 
-            for vertex in graph.vertices() {
-                let vertex_idx = { *vertex as usize };
-                if visited[vertex_idx] {
-                    continue;
-                }
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
 
-                visited[vertex_idx] = true;
-                let neighbors = cache.vertex_neighbors.get(vertex).unwrap();
-                match self.genes.get(vertex_idx) {
-                    Some(&0) => {
-                        if !cache.has_two_neighbor[vertex_idx] {
-                            if let Some(&neighbor_idx) =
-                                neighbors.iter().find(|&&n| self.genes[n as usize] == 0)
-                            {
-                                let idx = neighbor_idx as usize;
-                                self.genes[idx] = 2;
-                                visited[idx] = false;
-                                modified = true;
-                            }
-                        }
-                    }
-                    Some(&1 | &2) => {
-                        if !cache.has_one_neighbor[vertex_idx] {
-                            // Encontra o primeiro vizinho com valor 0 para atualizar para 1
-                            if let Some(&neighbor_idx) =
-                                neighbors.iter().find(|&&n| self.genes[n as usize] == 0)
-                            {
-                                let idx = neighbor_idx as usize;
-                                self.genes[idx] = 1;
-                                visited[idx] = false;
-                                modified = true;
-                            }
-                        }
-                    }
+    use super::*;
 
-                    Some(&invalid) => {
-                        panic!(
-                        "Vértice com rótulo inválido encontrado! Índice: {vertex}, Valor: {invalid}. \
-                            Os rótulos válidos são: 0, 1, ou 2."
-                    );
-                    }
-                    None => {
-                        panic!(
-                            "Tentativa de acessar índice fora dos limites! Índice: {vertex}. \
-                        Verifique se o vetor de genes está consistente com o grafo.",
-                        );
+    #[test]
+    fn fitness_is_the_weighted_cost_from_construction() {
+        let graph =
+            CsrGraph::from_edges(3, &[(0, 1), (1, 2)]).with_costs(vec![[5, 7], [1, 4], [2, 3]]);
+        let mut chromosome = Chromosome::new(vec![1, 2, 0], &graph);
+        assert_eq!(chromosome.fitness(), 5 + 4);
+
+        chromosome.set_label(2, 1, &graph);
+        assert_eq!(chromosome.fitness(), 5 + 4 + 2);
+
+        chromosome.track_neighbors(&graph);
+        assert_eq!(chromosome.fitness(), 5 + 4 + 2);
+        chromosome.set_label(0, 0, &graph);
+        assert_eq!(chromosome.fitness(), 4 + 2);
+        assert_eq!(chromosome.labelled_neighbors(1, 0), 1);
+    }
+
+    #[test]
+    fn fitness_is_the_label_sum_without_costs() {
+        let graph = CsrGraph::from_edges(4, &[(0, 1), (1, 2), (2, 3)]);
+        let mut chromosome = Chromosome::new(vec![3, 0, 2, 1], &graph);
+        assert_eq!(chromosome.fitness(), 6);
+        chromosome.set_label(1, 1, &graph);
+        chromosome.track_neighbors(&graph);
+        assert_eq!(chromosome.fitness(), 7);
+    }
+
+    #[test]
+    fn incremental_bookkeeping_matches_recomputation() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for weighted in [false, true] {
+            for _ in 0..50 {
+                let order = rng.gen_range(1..15);
+                let edges: Vec<(u32, u32)> = (0..rng.gen_range(0..3 * order))
+                    .map(|_| (rng.gen_range(0..order), rng.gen_range(0..order)))
+                    .collect();
+                let mut graph = CsrGraph::from_edges(order as usize, &edges);
+                if weighted {
+                    let costs = (0..order)
+                        .map(|_| [rng.gen_range(0..5), rng.gen_range(0..9)])
+                        .collect();
+                    graph = graph.with_costs(costs);
+                }
+                let max_label = if weighted { 2 } else { Chromosome::MAX_LABEL };
+
+                let genes = (0..order).map(|_| rng.gen_range(0..=max_label)).collect();
+                let mut chromosome = Chromosome::new(genes, &graph);
+                chromosome.track_neighbors(&graph);
+
+                for _ in 0..100 {
+                    let vertex = rng.gen_range(0..order);
+                    chromosome.set_label(vertex, rng.gen_range(0..=max_label), &graph);
+
+                    let mut fresh = Chromosome::new(chromosome.genes().to_vec(), &graph);
+                    fresh.track_neighbors(&graph);
+                    assert_eq!(chromosome.fitness(), fresh.fitness());
+                    for v in graph.vertices() {
+                        for label in 0..=Chromosome::MAX_LABEL {
+                            assert_eq!(
+                                chromosome.labelled_neighbors(v, label),
+                                fresh.labelled_neighbors(v, label)
+                            );
+                            assert_eq!(
+                                chromosome.neighbors_at_least(v, label),
+                                fresh.neighbors_at_least(v, label)
+                            );
+                        }
                     }
                 }
-            }
 
-            if modified {
-                self.update_cache(&mut cache);
+                // Sem contagens, `set_label` ainda mantém o peso.
+                let mut untracked = Chromosome::new(vec![0; order as usize], &graph);
+                for (v, &label) in (0..).zip(chromosome.genes()) {
+                    untracked.set_label(v, label, &graph);
+                }
+                assert_eq!(untracked.fitness(), chromosome.fitness());
             }
         }
-        self.neighbors_cache = Some(cache);
     }
 }
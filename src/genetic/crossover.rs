@@ -1,20 +1,136 @@
-use kambo_graph::graphs::simple::UndirectedGraph;
+use std::{collections::VecDeque, fmt, str::FromStr};
+
 use rand::prelude::*;
 
+use crate::csr::CsrGraph;
+
 use super::chromosome::Chromosome;
 
 /// Trait defining crossover operations
+///
+/// Children are not repaired: callers repair them with [`Problem::repair`](super::Problem::repair).
 pub trait Crossover {
-    /// Performs crossover between two parent chromosomes
+    /// Performs crossover between two parent chromosomes, drawing randomness from `rng`
     fn crossover(
         &self,
         parent1: &Chromosome,
         parent2: &Chromosome,
-        graph: &UndirectedGraph<u32>,
+        graph: &CsrGraph,
+        rng: &mut dyn RngCore,
     ) -> (Chromosome, Chromosome);
 }
 
-/// Single-point crossover implementation optimized for performance#[derive(Clone)]
+impl<C: Crossover + ?Sized> Crossover for Box<C> {
+    fn crossover(
+        &self,
+        parent1: &Chromosome,
+        parent2: &Chromosome,
+        graph: &CsrGraph,
+        rng: &mut dyn RngCore,
+    ) -> (Chromosome, Chromosome) {
+        (**self).crossover(parent1, parent2, graph, rng)
+    }
+}
+
+/// Crossover operators that can be chosen at run time.
+///
+/// Parsed from and formatted as `single`, `two`, `k:<k>`, `uniform[:<bias>]`, `bfs` and
+/// `region[:<regions>]`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CrossoverOperator {
+    /// [`SinglePoint`].
+    SinglePoint,
+    /// [`TwoPoint`].
+    TwoPoint,
+    /// [`KPoint`] with the given number of cut points.
+    KPoint(usize),
+    /// [`Uniform`] with the given bias towards the first parent.
+    Uniform(f64),
+    /// [`BfsBall`].
+    BfsBall,
+    /// [`RegionPartition`] with the given number of regions.
+    RegionPartition(usize),
+}
+
+impl CrossoverOperator {
+    /// Builds the operator with the given crossover rate.
+    ///
+    /// # Panics
+    /// Panics if a parameter is out of range; see the constructor of each operator.
+    #[must_use]
+    pub fn build(self, crossover_rate: f64) -> Box<dyn Crossover + Send + Sync> {
+        match self {
+            Self::SinglePoint => Box::new(SinglePoint::new(crossover_rate)),
+            Self::TwoPoint => Box::new(TwoPoint::new(crossover_rate)),
+            Self::KPoint(k) => Box::new(KPoint::new(crossover_rate, k)),
+            Self::Uniform(bias) => Box::new(Uniform::new(crossover_rate, bias)),
+            Self::BfsBall => Box::new(BfsBall::new(crossover_rate)),
+            Self::RegionPartition(regions) => {
+                Box::new(RegionPartition::new(crossover_rate, regions))
+            }
+        }
+    }
+}
+
+impl FromStr for CrossoverOperator {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid crossover operator: {s}");
+        let (name, param) = match s.split_once(':') {
+            Some((name, param)) => (name, Some(param)),
+            None => (s, None),
+        };
+
+        let operator = match (name, param) {
+            ("single", None) => Self::SinglePoint,
+            ("two", None) => Self::TwoPoint,
+            ("k", Some(k)) => Self::KPoint(k.parse().map_err(|_| invalid())?),
+            ("uniform", None) => Self::Uniform(0.5),
+            ("uniform", Some(bias)) => Self::Uniform(bias.parse().map_err(|_| invalid())?),
+            ("bfs", None) => Self::BfsBall,
+            ("region", None) => Self::RegionPartition(4),
+            ("region", Some(regions)) => {
+                Self::RegionPartition(regions.parse().map_err(|_| invalid())?)
+            }
+            _ => {
+                return Err(format!(
+                    "{} (expected single, two, k:<k>, uniform[:<bias>], bfs or region[:<regions>])",
+                    invalid()
+                ))
+            }
+        };
+
+        match operator {
+            Self::KPoint(0) => Err("k-point crossover needs at least one cut point".to_string()),
+            Self::Uniform(bias) if !(0.0..=1.0).contains(&bias) => {
+                Err("Uniform crossover bias must be between 0 and 1".to_string())
+            }
+            Self::RegionPartition(regions) if regions < 2 => {
+                Err("Region crossover needs at least two regions".to_string())
+            }
+            operator => Ok(operator),
+        }
+    }
+}
+
+impl fmt::Display for CrossoverOperator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SinglePoint => write!(f, "single"),
+            Self::TwoPoint => write!(f, "two"),
+            Self::KPoint(k) => write!(f, "k:{k}"),
+            Self::Uniform(bias) => write!(f, "uniform:{bias}"),
+            Self::BfsBall => write!(f, "bfs"),
+            Self::RegionPartition(regions) => write!(f, "region:{regions}"),
+        }
+    }
+}
+
+/// Single-point crossover implementation.
+///
+/// Cuts both parents at a random index and exchanges the tails.
+#[derive(Clone, Debug)]
 pub struct SinglePoint {
     crossover_rate: f64,
 }
@@ -29,8 +145,8 @@ impl SinglePoint {
     /// - `1.0` means crossover is always applied.
     ///
     /// # Parameters
-    /// - `crossover_rate: f64`  
-    ///   A floating-point value representing the probability of crossover.  
+    /// - `crossover_rate: f64`:
+    ///   A floating-point value representing the probability of crossover.
     ///   The value must satisfy `0.0 <= crossover_rate <= 1.0`.
     ///
     /// # Returns
@@ -38,12 +154,11 @@ impl SinglePoint {
     ///
     /// # Panics
     /// This method will panic if:
-    /// - `crossover_rate` is outside the range `[0.0, 1.0]`.  
+    /// - `crossover_rate` is outside the range `[0.0, 1.0]`.
     ///   The panic message will be:
     ///   ```text
     ///   Crossover probability must be between 0 and 1
     ///   ```
-    ///
     #[inline]
     #[must_use]
     pub fn new(crossover_rate: f64) -> Self {
@@ -61,53 +176,659 @@ impl Crossover for SinglePoint {
         &self,
         parent1: &Chromosome,
         parent2: &Chromosome,
-        graph: &UndirectedGraph<u32>,
+        graph: &CsrGraph,
+        rng: &mut dyn RngCore,
     ) -> (Chromosome, Chromosome) {
-        let mut rng = thread_rng();
+        let len = parent1.genes().len();
 
         // Se não ocorrer crossover, retorna cópias dos pais
-        if !rng.gen_bool(self.crossover_rate) {
-            return (
-                Chromosome::new(parent1.genes().to_vec()),
-                Chromosome::new(parent2.genes().to_vec()),
-            );
+        if len < 2 || !rng.gen_bool(self.crossover_rate) {
+            return (parent1.clone(), parent2.clone());
         }
 
-        let genes1 = parent1.genes();
-        let genes2 = parent2.genes();
-        let len = genes1.len();
         let point = rng.gen_range(1..len);
+        segment_children(parent1, parent2, &[point], graph)
+    }
+}
 
-        let mut child1_genes = Vec::with_capacity(len);
-        let mut child2_genes = Vec::with_capacity(len);
+/// Two-point crossover: exchanges the segment between two random cut points.
+#[derive(Clone, Debug)]
+pub struct TwoPoint {
+    crossover_rate: f64,
+}
 
-        unsafe {
-            child1_genes.set_len(len);
-            child2_genes.set_len(len);
+impl TwoPoint {
+    /// Creates a new instance with a specified crossover rate.
+    ///
+    /// # Panics
+    /// Panics if `crossover_rate` is outside the range `[0.0, 1.0]`.
+    #[inline]
+    #[must_use]
+    pub fn new(crossover_rate: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&crossover_rate),
+            "Crossover probability must be between 0 and 1"
+        );
+        Self { crossover_rate }
+    }
+}
 
-            // First child
-            std::ptr::copy_nonoverlapping(genes1.as_ptr(), child1_genes.as_mut_ptr(), point);
-            std::ptr::copy_nonoverlapping(
-                genes2.as_ptr().add(point),
-                child1_genes.as_mut_ptr().add(point),
-                len - point,
-            );
+impl Crossover for TwoPoint {
+    fn crossover(
+        &self,
+        parent1: &Chromosome,
+        parent2: &Chromosome,
+        graph: &CsrGraph,
+        rng: &mut dyn RngCore,
+    ) -> (Chromosome, Chromosome) {
+        k_point(self.crossover_rate, 2, parent1, parent2, graph, rng)
+    }
+}
 
-            // Second child
-            std::ptr::copy_nonoverlapping(genes2.as_ptr(), child2_genes.as_mut_ptr(), point);
-            std::ptr::copy_nonoverlapping(
-                genes1.as_ptr().add(point),
-                child2_genes.as_mut_ptr().add(point),
-                len - point,
-            );
+/// K-point crossover: cuts the parents at `k` distinct random points and exchanges
+/// every other segment.
+///
+/// Chromosomes with fewer than `k + 1` genes are cut at every position.
+#[derive(Clone, Debug)]
+pub struct KPoint {
+    crossover_rate: f64,
+    k: usize,
+}
+
+impl KPoint {
+    /// Creates a new instance with a specified crossover rate and number of cut points.
+    ///
+    /// # Panics
+    /// - If `crossover_rate` is outside the range `[0.0, 1.0]`.
+    /// - If `k` is zero.
+    #[inline]
+    #[must_use]
+    pub fn new(crossover_rate: f64, k: usize) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&crossover_rate),
+            "Crossover probability must be between 0 and 1"
+        );
+        assert!(k > 0, "At least one cut point is required");
+        Self { crossover_rate, k }
+    }
+}
+
+impl Crossover for KPoint {
+    fn crossover(
+        &self,
+        parent1: &Chromosome,
+        parent2: &Chromosome,
+        graph: &CsrGraph,
+        rng: &mut dyn RngCore,
+    ) -> (Chromosome, Chromosome) {
+        k_point(self.crossover_rate, self.k, parent1, parent2, graph, rng)
+    }
+}
+
+/// Uniform crossover: every gene of the first child comes from `parent1` with
+/// probability `bias` and from `parent2` otherwise; the second child gets the other gene.
+#[derive(Clone, Debug)]
+pub struct Uniform {
+    crossover_rate: f64,
+    bias: f64,
+}
+
+impl Uniform {
+    /// Creates a new instance with a specified crossover rate and bias towards `parent1`.
+    ///
+    /// # Panics
+    /// Panics if `crossover_rate` or `bias` is outside the range `[0.0, 1.0]`.
+    #[inline]
+    #[must_use]
+    pub fn new(crossover_rate: f64, bias: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&crossover_rate),
+            "Crossover probability must be between 0 and 1"
+        );
+        assert!((0.0..=1.0).contains(&bias), "Bias must be between 0 and 1");
+        Self {
+            crossover_rate,
+            bias,
         }
+    }
+}
+
+impl Crossover for Uniform {
+    fn crossover(
+        &self,
+        parent1: &Chromosome,
+        parent2: &Chromosome,
+        graph: &CsrGraph,
+        rng: &mut dyn RngCore,
+    ) -> (Chromosome, Chromosome) {
+        if !rng.gen_bool(self.crossover_rate) {
+            return (parent1.clone(), parent2.clone());
+        }
+
+        let mask: Vec<bool> = (0..parent1.genes().len())
+            .map(|_| rng.gen_bool(self.bias))
+            .collect();
+        masked_children(parent1, parent2, &mask, graph)
+    }
+}
 
-        let mut child1 = Chromosome::new(child1_genes);
-        let mut child2 = Chromosome::new(child2_genes);
+/// Crossover that exchanges a BFS ball of the graph between the parents.
+///
+/// A ball is grown by breadth-first search from a random vertex until it holds a random
+/// number of vertices between `1` and `n - 1`; if the component of the start vertex is
+/// exhausted first, the search continues from a vertex outside the ball. The first child
+/// takes the labels inside the ball from `parent1` and the rest from `parent2`; the
+/// second child does the opposite. Since the ball is connected, the cut follows the
+/// structure of the graph and the repair has less damage to undo than with
+/// [`SinglePoint`].
+#[derive(Clone, Debug)]
+pub struct BfsBall {
+    crossover_rate: f64,
+}
 
-        child1.fix(graph);
-        child2.fix(graph);
+impl BfsBall {
+    /// Creates a new instance with a specified crossover rate.
+    ///
+    /// # Panics
+    /// Panics if `crossover_rate` is outside the range `[0.0, 1.0]`.
+    #[inline]
+    #[must_use]
+    pub fn new(crossover_rate: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&crossover_rate),
+            "Crossover probability must be between 0 and 1"
+        );
+        Self { crossover_rate }
+    }
+}
 
-        (child1, child2)
+impl Crossover for BfsBall {
+    fn crossover(
+        &self,
+        parent1: &Chromosome,
+        parent2: &Chromosome,
+        graph: &CsrGraph,
+        rng: &mut dyn RngCore,
+    ) -> (Chromosome, Chromosome) {
+        let len = parent1.genes().len();
+        if len < 2 || !rng.gen_bool(self.crossover_rate) {
+            return (parent1.clone(), parent2.clone());
+        }
+
+        let target = rng.gen_range(1..len);
+        let mut inside = vec![false; len];
+        let mut queue = VecDeque::new();
+        let mut size = 0;
+
+        // Vértice inicial aleatório; se a componente acabar, a busca recomeça no próximo
+        // vértice fora da bola a partir dele.
+        let mut next_start = rng.gen_range(0..len);
+        while size < target {
+            if queue.is_empty() {
+                while inside[next_start] {
+                    next_start = (next_start + 1) % len;
+                }
+                inside[next_start] = true;
+                size += 1;
+                #[allow(clippy::cast_possible_truncation)]
+                queue.push_back(next_start as u32);
+            }
+
+            while size < target {
+                let Some(v) = queue.pop_front() else { break };
+                for &n in graph.neighbors(v) {
+                    if size < target && !inside[n as usize] {
+                        inside[n as usize] = true;
+                        size += 1;
+                        queue.push_back(n);
+                    }
+                }
+            }
+        }
+
+        masked_children(parent1, parent2, &inside, graph)
+    }
+}
+
+/// Crossover that exchanges whole connected regions of the graph between the parents.
+///
+/// `regions` seed vertices are drawn at random and a multi-source BFS assigns every
+/// vertex to the region of the closest seed, so each region is connected. Vertices not
+/// reached (in components without a seed) start new regions. Every region is then
+/// inherited from `parent1` or `parent2` with equal probability by the first child and
+/// from the other parent by the second child.
+#[derive(Clone, Debug)]
+pub struct RegionPartition {
+    crossover_rate: f64,
+    regions: usize,
+}
+
+impl RegionPartition {
+    /// Creates a new instance with a specified crossover rate and number of regions.
+    ///
+    /// # Panics
+    /// - If `crossover_rate` is outside the range `[0.0, 1.0]`.
+    /// - If `regions` is smaller than `2`.
+    #[inline]
+    #[must_use]
+    pub fn new(crossover_rate: f64, regions: usize) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&crossover_rate),
+            "Crossover probability must be between 0 and 1"
+        );
+        assert!(regions >= 2, "At least two regions are required");
+        Self {
+            crossover_rate,
+            regions,
+        }
+    }
+}
+
+impl Crossover for RegionPartition {
+    fn crossover(
+        &self,
+        parent1: &Chromosome,
+        parent2: &Chromosome,
+        graph: &CsrGraph,
+        rng: &mut dyn RngCore,
+    ) -> (Chromosome, Chromosome) {
+        let len = parent1.genes().len();
+        if len < 2 || !rng.gen_bool(self.crossover_rate) {
+            return (parent1.clone(), parent2.clone());
+        }
+
+        let (region, regions) = partition(graph, self.regions, rng);
+        let from_parent1: Vec<bool> = (0..regions).map(|_| rng.gen_bool(0.5)).collect();
+        let mask: Vec<bool> = region.iter().map(|&r| from_parent1[r]).collect();
+
+        masked_children(parent1, parent2, &mask, graph)
+    }
+}
+
+/// Splits the vertices into connected regions grown by a multi-source BFS from `seeds`
+/// random vertices; vertices not reached start new regions.
+///
+/// Returns the region of every vertex and the number of regions.
+fn partition(graph: &CsrGraph, seeds: usize, rng: &mut dyn RngCore) -> (Vec<usize>, usize) {
+    let len = graph.order();
+
+    // Região de cada vértice; `usize::MAX` marca vértices ainda não alcançados.
+    let mut region = vec![usize::MAX; len];
+    let mut queue = VecDeque::new();
+    let mut regions = 0;
+
+    let sample = rand::seq::index::sample(rng, len, seeds.min(len));
+    for seed in sample.iter() {
+        region[seed] = regions;
+        regions += 1;
+        #[allow(clippy::cast_possible_truncation)]
+        queue.push_back(seed as u32);
+    }
+
+    for start in 0..len {
+        if region[start] == usize::MAX {
+            region[start] = regions;
+            regions += 1;
+            #[allow(clippy::cast_possible_truncation)]
+            queue.push_back(start as u32);
+        }
+
+        // BFS a partir de todas as sementes ao mesmo tempo.
+        while let Some(v) = queue.pop_front() {
+            for &n in graph.neighbors(v) {
+                if region[n as usize] == usize::MAX {
+                    region[n as usize] = region[v as usize];
+                    queue.push_back(n);
+                }
+            }
+        }
+    }
+
+    (region, regions)
+}
+
+/// Applies a k-point crossover with `k` distinct cut points drawn from `1..len`.
+fn k_point(
+    crossover_rate: f64,
+    k: usize,
+    parent1: &Chromosome,
+    parent2: &Chromosome,
+    graph: &CsrGraph,
+    rng: &mut dyn RngCore,
+) -> (Chromosome, Chromosome) {
+    let len = parent1.genes().len();
+    if len < 2 || !rng.gen_bool(crossover_rate) {
+        return (parent1.clone(), parent2.clone());
+    }
+
+    let points = cut_points(len, k, rng);
+    segment_children(parent1, parent2, &points, graph)
+}
+
+/// Draws `min(k, len - 1)` distinct cut points from `1..len`, in increasing order.
+fn cut_points(len: usize, k: usize, rng: &mut dyn RngCore) -> Vec<usize> {
+    let mut points: Vec<usize> = rand::seq::index::sample(rng, len - 1, k.min(len - 1))
+        .iter()
+        .map(|idx| idx + 1)
+        .collect();
+    points.sort_unstable();
+    points
+}
+
+/// Builds the children of a cut-point crossover.
+///
+/// `points` must be sorted. The segments between consecutive cut points alternate
+/// between the parents: the first child takes the first segment from `parent1`, the
+/// second from `parent2`, and so on; the second child does the opposite.
+fn segment_children(
+    parent1: &Chromosome,
+    parent2: &Chromosome,
+    points: &[usize],
+    graph: &CsrGraph,
+) -> (Chromosome, Chromosome) {
+    let genes1 = parent1.genes();
+    let genes2 = parent2.genes();
+    let mut child1_genes = genes1.to_vec();
+    let mut child2_genes = genes2.to_vec();
+
+    // Segmentos de índice ímpar são trocados entre os filhos.
+    let mut start = 0;
+    for (idx, &end) in points.iter().chain([&genes1.len()]).enumerate() {
+        if idx % 2 == 1 {
+            child1_genes[start..end].copy_from_slice(&genes2[start..end]);
+            child2_genes[start..end].copy_from_slice(&genes1[start..end]);
+        }
+        start = end;
+    }
+
+    (
+        Chromosome::new(child1_genes, graph),
+        Chromosome::new(child2_genes, graph),
+    )
+}
+
+/// Builds the children of a mask-based crossover.
+///
+/// The first child takes the label of vertex `v` from `parent1` if `mask[v]` is `true`
+/// and from `parent2` otherwise; the second child does the opposite.
+fn masked_children(
+    parent1: &Chromosome,
+    parent2: &Chromosome,
+    mask: &[bool],
+    graph: &CsrGraph,
+) -> (Chromosome, Chromosome) {
+    let (child1_genes, child2_genes): (Vec<u8>, Vec<u8>) = parent1
+        .genes()
+        .iter()
+        .zip(parent2.genes())
+        .zip(mask)
+        .map(|((&g1, &g2), &first)| if first { (g1, g2) } else { (g2, g1) })
+        .unzip();
+
+    (
+        Chromosome::new(child1_genes, graph),
+        Chromosome::new(child2_genes, graph),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+
+    use super::*;
+
+    /// Path `0..5`, cycle `5..9`, edge `9-10` and the isolated vertex `11`.
+    fn disconnected_graph() -> CsrGraph {
+        CsrGraph::from_edges(
+            12,
+            &[
+                (0, 1),
+                (1, 2),
+                (2, 3),
+                (3, 4),
+                (5, 6),
+                (6, 7),
+                (7, 8),
+                (8, 5),
+                (9, 10),
+            ],
+        )
+    }
+
+    fn is_connected(graph: &CsrGraph, vertices: &[u32]) -> bool {
+        graph.induced_subgraph(vertices).components().len() == 1
+    }
+
+    fn random_parents(graph: &CsrGraph, rng: &mut StdRng) -> (Chromosome, Chromosome) {
+        let mut parent = || {
+            let genes = (0..graph.order()).map(|_| rng.gen_range(0..=2)).collect();
+            Chromosome::new(genes, graph)
+        };
+        (parent(), parent())
+    }
+
+    /// Asserts that every child gene is the pair of parent genes, in some order.
+    fn assert_genes_exchanged(
+        parents: (&Chromosome, &Chromosome),
+        children: &(Chromosome, Chromosome),
+    ) {
+        for (((&p1, &p2), &c1), &c2) in parents
+            .0
+            .genes()
+            .iter()
+            .zip(parents.1.genes())
+            .zip(children.0.genes())
+            .zip(children.1.genes())
+        {
+            assert!((c1, c2) == (p1, p2) || (c1, c2) == (p2, p1));
+        }
+    }
+
+    /// Vertices the first child takes from a parent labelled `1` everywhere, when the other
+    /// parent is labelled `0` everywhere.
+    fn from_first_parent(
+        crossover: &impl Crossover,
+        graph: &CsrGraph,
+        rng: &mut dyn RngCore,
+    ) -> Vec<u32> {
+        let ones = Chromosome::new(vec![1; graph.order()], graph);
+        let zeros = Chromosome::new(vec![0; graph.order()], graph);
+        let (child, _) = crossover.crossover(&ones, &zeros, graph, rng);
+        graph
+            .vertices()
+            .filter(|&v| child.genes()[v as usize] == 1)
+            .collect()
+    }
+
+    #[test]
+    fn bfs_ball_is_a_proper_connected_subset() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let crossover = BfsBall::new(1.0);
+
+        let connected = CsrGraph::circulant(20, &[1, 6]);
+        for _ in 0..200 {
+            let ball = from_first_parent(&crossover, &connected, &mut rng);
+            assert!((1..connected.order()).contains(&ball.len()));
+            assert!(is_connected(&connected, &ball));
+        }
+
+        // A bola esgota componentes inteiras antes de recomeçar na próxima, então no
+        // máximo uma componente fica dividida e sua parte na bola é conexa.
+        let graph = disconnected_graph();
+        for _ in 0..200 {
+            let ball = from_first_parent(&crossover, &graph, &mut rng);
+            assert!((1..graph.order()).contains(&ball.len()));
+
+            let mut split = 0;
+            for component in graph.components() {
+                let inside: Vec<u32> = component
+                    .iter()
+                    .copied()
+                    .filter(|v| ball.contains(v))
+                    .collect();
+                if !inside.is_empty() && inside.len() < component.len() {
+                    split += 1;
+                    assert!(is_connected(&graph, &inside));
+                }
+            }
+            assert!(split <= 1);
+        }
+    }
+
+    #[test]
+    fn regions_are_connected_and_inherited_whole() {
+        let graph = disconnected_graph();
+        let crossover = RegionPartition::new(1.0, 2);
+        let components = graph.components().len();
+
+        for seed in 0..200 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            // Repete os sorteios do operador para obter as regiões que ele usa.
+            let mut replay = rng.clone();
+            assert!(replay.gen_bool(1.0));
+            let (region, regions) = partition(&graph, 2, &mut replay);
+
+            // Componentes sem semente viram novas regiões.
+            assert!(regions >= components);
+            for r in 0..regions {
+                let vertices: Vec<u32> = graph
+                    .vertices()
+                    .filter(|&v| region[v as usize] == r)
+                    .collect();
+                assert!(is_connected(&graph, &vertices));
+            }
+
+            let inherited = from_first_parent(&crossover, &graph, &mut rng);
+            for v in graph.vertices() {
+                for &n in graph.neighbors(v) {
+                    if region[v as usize] == region[n as usize] {
+                        assert_eq!(inherited.contains(&v), inherited.contains(&n));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn graph_aware_children_exchange_parent_genes() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let graph = disconnected_graph();
+        let operators: [Box<dyn Crossover>; 2] = [
+            Box::new(BfsBall::new(1.0)),
+            Box::new(RegionPartition::new(1.0, 3)),
+        ];
+
+        for operator in &operators {
+            for _ in 0..100 {
+                let (p1, p2) = random_parents(&graph, &mut rng);
+                let children = operator.crossover(&p1, &p2, &graph, &mut rng);
+                assert_genes_exchanged((&p1, &p2), &children);
+            }
+        }
+    }
+
+    #[test]
+    fn cut_points_are_distinct_sorted_and_clamped() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..200 {
+            let len = rng.gen_range(2..20);
+            let k = rng.gen_range(1..25);
+            let points = cut_points(len, k, &mut rng);
+
+            assert_eq!(points.len(), k.min(len - 1));
+            assert!(points.windows(2).all(|pair| pair[0] < pair[1]));
+            assert!(points.iter().all(|point| (1..len).contains(point)));
+        }
+
+        // Com k >= len, todas as posições são cortadas.
+        assert_eq!(cut_points(5, 9, &mut rng), [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn segments_alternate_between_the_parents() {
+        let graph = CsrGraph::from_edges(8, &[]);
+        let ones = Chromosome::new(vec![1; 8], &graph);
+        let zeros = Chromosome::new(vec![0; 8], &graph);
+
+        for (points, expected) in [
+            (&[3][..], [1, 1, 1, 0, 0, 0, 0, 0]),
+            (&[2, 5][..], [1, 1, 0, 0, 0, 1, 1, 1]),
+            (&[1, 4, 6][..], [1, 0, 0, 0, 1, 1, 0, 0]),
+        ] {
+            let (child1, child2) = segment_children(&ones, &zeros, points, &graph);
+            assert_eq!(child1.genes(), expected);
+            let complement: Vec<u8> = expected.iter().map(|&gene| 1 - gene).collect();
+            assert_eq!(child2.genes(), complement);
+        }
+    }
+
+    #[test]
+    fn uniform_bias_one_keeps_the_parents_and_zero_swaps_them() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let graph = disconnected_graph();
+        for _ in 0..20 {
+            let (p1, p2) = random_parents(&graph, &mut rng);
+
+            let (c1, c2) = Uniform::new(1.0, 1.0).crossover(&p1, &p2, &graph, &mut rng);
+            assert_eq!((c1.genes(), c2.genes()), (p1.genes(), p2.genes()));
+
+            let (c1, c2) = Uniform::new(1.0, 0.0).crossover(&p1, &p2, &graph, &mut rng);
+            assert_eq!((c1.genes(), c2.genes()), (p2.genes(), p1.genes()));
+        }
+    }
+
+    #[test]
+    fn cut_point_children_exchange_parent_genes() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let graph = disconnected_graph();
+        let operators: [Box<dyn Crossover>; 4] = [
+            Box::new(SinglePoint::new(1.0)),
+            Box::new(TwoPoint::new(1.0)),
+            Box::new(KPoint::new(1.0, 5)),
+            Box::new(Uniform::new(1.0, 0.5)),
+        ];
+
+        for operator in &operators {
+            for _ in 0..100 {
+                let (p1, p2) = random_parents(&graph, &mut rng);
+                let children = operator.crossover(&p1, &p2, &graph, &mut rng);
+                assert_genes_exchanged((&p1, &p2), &children);
+            }
+        }
+    }
+
+    #[test]
+    fn operators_round_trip_through_display() {
+        for (spec, operator) in [
+            ("single", CrossoverOperator::SinglePoint),
+            ("two", CrossoverOperator::TwoPoint),
+            ("k:3", CrossoverOperator::KPoint(3)),
+            ("uniform", CrossoverOperator::Uniform(0.5)),
+            ("uniform:0.25", CrossoverOperator::Uniform(0.25)),
+            ("bfs", CrossoverOperator::BfsBall),
+            ("region", CrossoverOperator::RegionPartition(4)),
+            ("region:6", CrossoverOperator::RegionPartition(6)),
+        ] {
+            assert_eq!(spec.parse::<CrossoverOperator>(), Ok(operator));
+            assert_eq!(
+                operator.to_string().parse::<CrossoverOperator>(),
+                Ok(operator)
+            );
+        }
+
+        for spec in [
+            "uniform:1.5",
+            "uniform:-0.1",
+            "k:0",
+            "k",
+            "k:x",
+            "region:1",
+            "single:2",
+            "bfs:3",
+            "three",
+            "",
+        ] {
+            assert!(spec.parse::<CrossoverOperator>().is_err(), "{spec:?}");
+        }
     }
 }
@@ -0,0 +1,242 @@
+/// Bucket priority queue of vertices keyed by degree.
+///
+/// Vertex `v` lives in `buckets[degree[v]]`. Removing a vertex and decrementing its
+/// degree are `O(1)` swap-removes; finding a vertex of maximum degree is amortized `O(1)`
+/// because the maximum only moves down as degrees are decremented.
+#[derive(Clone, Debug)]
+pub(crate) struct DegreeBuckets {
+    buckets: Vec<Vec<u32>>,
+    degree: Vec<usize>,
+    position: Vec<usize>,
+    present: Vec<bool>,
+    max_degree: usize,
+    min_degree: usize,
+    len: usize,
+}
+
+impl DegreeBuckets {
+    /// Creates a queue holding every vertex `v` with degree `degrees[v]`.
+    pub(crate) fn new(degrees: Vec<usize>) -> Self {
+        let max_degree = degrees.iter().copied().max().unwrap_or(0);
+        let min_degree = degrees.iter().copied().min().unwrap_or(0);
+        let mut buckets = vec![Vec::new(); max_degree + 1];
+        let mut position = vec![0; degrees.len()];
+
+        for (v, &d) in degrees.iter().enumerate() {
+            position[v] = buckets[d].len();
+            #[allow(clippy::cast_possible_truncation)]
+            buckets[d].push(v as u32);
+        }
+
+        Self {
+            buckets,
+            position,
+            present: vec![true; degrees.len()],
+            len: degrees.len(),
+            degree: degrees,
+            max_degree,
+            min_degree,
+        }
+    }
+
+    /// Returns `true` if `vertex` is still in the queue.
+    #[inline]
+    pub(crate) fn contains(&self, vertex: u32) -> bool {
+        self.present[vertex as usize]
+    }
+
+    /// Returns the current degree of `vertex`.
+    #[inline]
+    pub(crate) fn degree(&self, vertex: u32) -> usize {
+        self.degree[vertex as usize]
+    }
+
+    /// Returns a vertex of maximum degree, if the queue is not empty.
+    pub(crate) fn max(&mut self) -> Option<u32> {
+        if self.len == 0 {
+            return None;
+        }
+        while self.buckets[self.max_degree].is_empty() {
+            self.max_degree -= 1;
+        }
+        self.buckets[self.max_degree].last().copied()
+    }
+
+    /// Returns the minimum degree in the queue, if it is not empty.
+    pub(crate) fn min_degree(&mut self) -> Option<usize> {
+        if self.len == 0 {
+            return None;
+        }
+        while self.buckets[self.min_degree].is_empty() {
+            self.min_degree += 1;
+        }
+        Some(self.min_degree)
+    }
+
+    /// Returns the number of vertices with degree at least `threshold`.
+    pub(crate) fn count_at_least(&self, threshold: usize) -> usize {
+        self.buckets
+            .get(threshold..=self.max_degree)
+            .map_or(0, |buckets| buckets.iter().map(Vec::len).sum())
+    }
+
+    /// Returns the `index`-th vertex with degree at least `threshold`, counting from the
+    /// highest degree down.
+    pub(crate) fn nth_at_least(&self, threshold: usize, mut index: usize) -> Option<u32> {
+        for bucket in self.buckets.get(threshold..=self.max_degree)?.iter().rev() {
+            if index < bucket.len() {
+                return Some(bucket[index]);
+            }
+            index -= bucket.len();
+        }
+        None
+    }
+
+    /// Removes `vertex` from the queue. Does nothing if it was already removed.
+    pub(crate) fn remove(&mut self, vertex: u32) {
+        let v = vertex as usize;
+        if !self.present[v] {
+            return;
+        }
+        self.detach(vertex);
+        self.present[v] = false;
+        self.len -= 1;
+    }
+
+    /// Decrements the degree of `vertex` by one and returns the new degree.
+    ///
+    /// # Panics
+    /// Panics if `vertex` is not in the queue or already has degree `0`.
+    pub(crate) fn decrement(&mut self, vertex: u32) -> usize {
+        let v = vertex as usize;
+        assert!(self.present[v], "Vertex {vertex} is not in the queue");
+        assert!(self.degree[v] > 0, "Vertex {vertex} already has degree 0");
+
+        self.detach(vertex);
+        self.degree[v] -= 1;
+        self.min_degree = self.min_degree.min(self.degree[v]);
+        let bucket = &mut self.buckets[self.degree[v]];
+        self.position[v] = bucket.len();
+        bucket.push(vertex);
+        self.degree[v]
+    }
+
+    /// Takes `vertex` out of its bucket without touching the other bookkeeping.
+    fn detach(&mut self, vertex: u32) {
+        let v = vertex as usize;
+        let bucket = &mut self.buckets[self.degree[v]];
+        let idx = self.position[v];
+        bucket.swap_remove(idx);
+        if let Some(&moved) = bucket.get(idx) {
+            self.position[moved as usize] = idx;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn max_follows_decrements_and_removals() {
+        let mut queue = DegreeBuckets::new(vec![3, 1, 4, 1, 0]);
+        assert_eq!(queue.max(), Some(2));
+
+        // O balde de grau 4 esvazia e o máximo desce para o vértice 0.
+        assert_eq!(queue.decrement(2), 3);
+        assert_eq!(queue.decrement(2), 2);
+        assert_eq!(queue.max(), Some(0));
+        assert_eq!(queue.degree(2), 2);
+
+        queue.remove(0);
+        queue.remove(0);
+        assert!(!queue.contains(0));
+        assert_eq!(queue.max(), Some(2));
+
+        for vertex in [1, 2, 3, 4] {
+            queue.remove(vertex);
+        }
+        assert_eq!(queue.max(), None);
+    }
+
+    #[test]
+    fn min_and_threshold_lookups_skip_emptied_buckets() {
+        let mut queue = DegreeBuckets::new(vec![0, 2, 2, 5, 3]);
+        assert_eq!(queue.min_degree(), Some(0));
+        assert_eq!(queue.count_at_least(2), 4);
+        assert_eq!(queue.nth_at_least(2, 0), Some(3));
+        assert_eq!(queue.nth_at_least(2, 1), Some(4));
+        assert_eq!(queue.nth_at_least(2, 4), None);
+
+        // Os baldes de grau 0 e 5 esvaziam.
+        queue.remove(0);
+        queue.remove(3);
+        assert_eq!(queue.min_degree(), Some(2));
+        assert_eq!(queue.max(), Some(4));
+        assert_eq!(queue.count_at_least(3), 1);
+        assert_eq!(queue.nth_at_least(3, 0), Some(4));
+
+        // Decrementar abaixo do mínimo o faz descer de novo.
+        assert_eq!(queue.decrement(1), 1);
+        assert_eq!(queue.min_degree(), Some(1));
+        assert_eq!(queue.count_at_least(2), 2);
+        assert_eq!(queue.count_at_least(9), 0);
+        assert_eq!(queue.nth_at_least(9, 0), None);
+
+        for vertex in [1, 2, 4] {
+            queue.remove(vertex);
+        }
+        assert_eq!(queue.min_degree(), None);
+        assert_eq!(queue.count_at_least(0), 0);
+    }
+
+    #[test]
+    fn matches_a_naive_queue() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..100 {
+            let degrees: Vec<usize> = (0..30).map(|_| rng.gen_range(0..8)).collect();
+            let mut naive: Vec<Option<usize>> = degrees.iter().copied().map(Some).collect();
+            let mut queue = DegreeBuckets::new(degrees);
+
+            while naive.iter().any(Option::is_some) {
+                let vertex = rng.gen_range(0..30u32);
+                match naive[vertex as usize] {
+                    Some(degree) if degree > 0 && rng.gen_bool(0.7) => {
+                        naive[vertex as usize] = Some(degree - 1);
+                        assert_eq!(queue.decrement(vertex), degree - 1);
+                    }
+                    _ => {
+                        naive[vertex as usize] = None;
+                        queue.remove(vertex);
+                    }
+                }
+
+                let max = naive.iter().flatten().max().copied();
+                assert_eq!(queue.max().map(|v| queue.degree(v)), max);
+                assert_eq!(queue.min_degree(), naive.iter().flatten().min().copied());
+
+                let threshold = rng.gen_range(0..8);
+                let at_least: Vec<u32> = (0..)
+                    .zip(&naive)
+                    .filter(|(_, degree)| degree.is_some_and(|d| d >= threshold))
+                    .map(|(v, _)| v)
+                    .collect();
+                assert_eq!(queue.count_at_least(threshold), at_least.len());
+                let mut listed: Vec<u32> = (0..at_least.len())
+                    .map(|idx| queue.nth_at_least(threshold, idx).unwrap())
+                    .collect();
+                assert!(listed
+                    .windows(2)
+                    .all(|pair| queue.degree(pair[0]) >= queue.degree(pair[1])));
+                listed.sort_unstable();
+                assert_eq!(listed, at_least);
+                for (v, &degree) in (0..).zip(&naive) {
+                    assert_eq!(queue.contains(v), degree.is_some());
+                    assert!(degree.map_or(true, |degree| queue.degree(v) == degree));
+                }
+            }
+        }
+    }
+}
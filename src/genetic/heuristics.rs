@@ -1,99 +1,197 @@
-use kambo_graph::{graphs::simple::UndirectedGraph, Graph, GraphMut};
-use rand::seq::IteratorRandom;
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::BinaryHeap,
+};
 
-use super::chromosome::Chromosome;
+use rand::{seq::SliceRandom, thread_rng, Rng, RngCore};
 
-/// Aliases to representation of a Heuristic
-pub type Heuristic = fn(&UndirectedGraph<u32>) -> Chromosome;
+use crate::csr::CsrGraph;
 
-/// A heuristic function to generate a `Chromosome` using a randomized approach.
-///
-/// # Arguments
-/// - `graph`: A reference to the undirected graph (`UnGraph`) for which the chromosome is generated.
+use super::{chromosome::Chromosome, degree_buckets::DegreeBuckets};
+
+/// A constructive heuristic that builds a chromosome for a graph.
 ///
-/// # Returns
-/// - A `Chromosome` where genes are assigned based on the following procedure:
-///   - Randomly select a vertex and assign it label `2`.
-///   - Assign one of its neighbors label `1`.
-///   - Remaining neighbors are labeled `0`.
-///   - Isolated vertices are handled separately and assigned labels to satisfy constraints.
-#[must_use]
-pub fn h1(graph: &UndirectedGraph<u32>) -> Chromosome {
-    // Inicializa um vetor de genes com valores 0.
-    // O tamanho do vetor é igual ao número de vértices no grafo.
-    let mut genes = vec![0u8; graph.order()];
+/// Heuristics are looked up by [`Heuristic::name`] in a
+/// [`HeuristicRegistry`](super::HeuristicRegistry). Randomized heuristics draw from the
+/// given random number generator, or from the thread-local one when `rng` is `None`;
+/// deterministic heuristics ignore it.
+pub trait Heuristic: Send + Sync {
+    /// Returns the name of the heuristic, e.g. `"h1"`.
+    fn name(&self) -> &str;
 
-    // Faz uma cópia do grafo original para ser manipulado sem alterar o original.
-    let mut h = graph.clone();
+    /// Builds a chromosome for `graph`.
+    ///
+    /// # Parameters
+    /// - `graph: &CsrGraph`: The graph for which the chromosome is generated.
+    /// - `rng: Option<&mut dyn RngCore>`: The random number generator used by randomized
+    ///   heuristics. If `None`, the thread-local generator is used.
+    fn generate(&self, graph: &CsrGraph, rng: Option<&mut dyn RngCore>) -> Chromosome;
 
-    // Cria um gerador de números aleatórios para escolher vértices aleatoriamente.
-    let mut rng = rand::thread_rng();
+    /// Returns `true` if [`Heuristic::generate`] draws from the random number generator.
+    ///
+    /// Deterministic heuristics always build the same chromosome for a graph, so callers
+    /// may build it once and clone it.
+    fn is_randomized(&self) -> bool {
+        true
+    }
+}
 
-    // Enquanto o grafo h ainda tiver vértices...
-    while let Some(v) = h.vertices().choose(&mut rng).copied() {
-        // Passo 4: Define f(v) = 2, marcando o vértice v com a cor 2.
-        genes[v as usize] = 2;
+/// The function a [`FnHeuristic`] runs.
+#[derive(Clone, Copy, Debug)]
+enum Construct {
+    /// A heuristic that draws from a random number generator.
+    Randomized(fn(&CsrGraph, &mut dyn RngCore) -> Chromosome),
+    /// A heuristic that always builds the same chromosome for a graph.
+    Deterministic(fn(&CsrGraph) -> Chromosome),
+}
 
-        // Obtém os vizinhos de v no grafo `h`.
-        let neighbors: Vec<u32> = h
-            .neighbors(&v)
-            .map(|n| n.copied().collect())
-            .unwrap_or_default();
+/// [`Heuristic`] running a free heuristic function under a fixed name.
+///
+/// The constants [`H1`] to [`WH`] wrap the heuristics of this module; heuristics with
+/// parameters, such as [`Grasp`], implement [`Heuristic`] themselves.
+#[derive(Clone, Copy, Debug)]
+pub struct FnHeuristic {
+    name: &'static str,
+    construct: Construct,
+}
 
-        // Passo 5: Se v tem vizinhos, escolha um (o primeiro da lista) e defina f(u) = 1.
-        if let Some(first_neighbor) = neighbors.first() {
-            genes[*first_neighbor as usize] = 1;
+impl FnHeuristic {
+    /// Wraps a heuristic that draws from a random number generator.
+    #[must_use]
+    pub const fn randomized(
+        name: &'static str,
+        construct: fn(&CsrGraph, &mut dyn RngCore) -> Chromosome,
+    ) -> Self {
+        Self {
+            name,
+            construct: Construct::Randomized(construct),
+        }
+    }
 
-            // Passo 6: Para os demais vizinhos de v, define f(w) = 0.
-            for w in neighbors.iter().skip(1) {
-                genes[*w as usize] = 0;
-            }
+    /// Wraps a heuristic that ignores the random number generator.
+    #[must_use]
+    pub const fn deterministic(name: &'static str, construct: fn(&CsrGraph) -> Chromosome) -> Self {
+        Self {
+            name,
+            construct: Construct::Deterministic(construct),
         }
+    }
+}
 
-        // Passo 7: Remove o vértice `v` e seus vizinhos do grafo `h`.
-        let _ = h.remove_vertex(&v);
-        for neighbor in neighbors {
-            let _ = h.remove_vertex(&neighbor);
+impl Heuristic for FnHeuristic {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn generate(&self, graph: &CsrGraph, rng: Option<&mut dyn RngCore>) -> Chromosome {
+        match (self.construct, rng) {
+            (Construct::Randomized(construct), Some(rng)) => construct(graph, rng),
+            (Construct::Randomized(construct), None) => construct(graph, &mut thread_rng()),
+            (Construct::Deterministic(construct), _) => construct(graph),
         }
+    }
 
-        // Passo 8: Enquanto houver vértices isolados em h...
-        let isolated_vertices = h.get_isolated_vertices();
-        for z in isolated_vertices {
-            // Caso contrário, define f(z) = 1.
-            genes[z as usize] = 1;
-            let has_neighbor_with_1 = graph
-                .neighbors(&z)
-                .is_some_and(|mut neighbors| neighbors.any(|n| genes[*n as usize] == 1));
+    fn is_randomized(&self) -> bool {
+        matches!(self.construct, Construct::Randomized(_))
+    }
+}
 
-            // Verifica se `z` tem vizinhos no grafo original com f = 1.
-            if !has_neighbor_with_1 {
-                // Se não há vizinhos com f = 1, escolhe um vizinho com f = 0 e define f = 1.
-                if let Some(mut neighbors) = graph.neighbors(&z) {
-                    if let Some(first) = neighbors.find(|&n| genes[*n as usize] == 0) {
-                        genes[*first as usize] = 1;
-                    }
-                }
-            }
+/// [`Heuristic`] running [`h1`].
+pub const H1: FnHeuristic = FnHeuristic::randomized("h1", h1);
+/// [`Heuristic`] running [`h2`].
+pub const H2: FnHeuristic = FnHeuristic::deterministic("h2", h2);
+/// [`Heuristic`] running [`h3`].
+pub const H3: FnHeuristic = FnHeuristic::deterministic("h3", h3);
+/// [`Heuristic`] running [`h4`].
+pub const H4: FnHeuristic = FnHeuristic::deterministic("h4", h4);
+/// [`Heuristic`] running [`h5`].
+pub const H5: FnHeuristic = FnHeuristic::deterministic("h5", h5);
+/// [`Heuristic`] running [`ih1`].
+pub const IH1: FnHeuristic = FnHeuristic::randomized("ih1", ih1);
+/// [`Heuristic`] running [`ih2`].
+pub const IH2: FnHeuristic = FnHeuristic::deterministic("ih2", ih2);
+/// [`Heuristic`] running [`ih3`].
+pub const IH3: FnHeuristic = FnHeuristic::deterministic("ih3", ih3);
+/// [`Heuristic`] running [`ih4`].
+pub const IH4: FnHeuristic = FnHeuristic::deterministic("ih4", ih4);
+/// [`Heuristic`] running [`ir1`].
+pub const IR1: FnHeuristic = FnHeuristic::deterministic("ir1", ir1);
+/// [`Heuristic`] running [`pr1`].
+pub const PR1: FnHeuristic = FnHeuristic::deterministic("pr1", pr1);
+/// [`Heuristic`] running [`wh`].
+pub const WH: FnHeuristic = FnHeuristic::deterministic("wh", wh);
 
-            // Passo 12: Remove o vértice `z` do grafo `h`.
-            let _ = h.remove_vertex(&z);
+/// [`Heuristic`] running [`grasp`] with a fixed `alpha`.
+///
+/// # Fields
+/// - `alpha: f64`: Greediness of the restricted candidate list, in `[0, 1]`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Grasp {
+    /// Greediness of the restricted candidate list.
+    pub alpha: f64,
+}
+
+impl Grasp {
+    /// Creates the heuristic with the given `alpha`.
+    ///
+    /// # Panics
+    /// Panics if `alpha` is not in `[0, 1]`.
+    #[must_use]
+    pub fn new(alpha: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&alpha),
+            "Alpha must be between 0 and 1"
+        );
+        Self { alpha }
+    }
+}
+
+impl Default for Grasp {
+    fn default() -> Self {
+        Self::new(0.2)
+    }
+}
+
+impl Heuristic for Grasp {
+    fn name(&self) -> &'static str {
+        "grasp"
+    }
+
+    fn generate(&self, graph: &CsrGraph, rng: Option<&mut dyn RngCore>) -> Chromosome {
+        match rng {
+            Some(rng) => grasp(graph, self.alpha, rng),
+            None => grasp(graph, self.alpha, &mut thread_rng()),
         }
     }
+}
 
-    // Retorna a solução como um Chromosome, encapsulando o vetor de genes.
-    Chromosome::new(genes)
+/// A heuristic function to generate a `Chromosome` using a randomized approach.
+///
+/// # Arguments
+/// - `graph`: A reference to the undirected graph (`CsrGraph`) for which the chromosome is generated.
+/// - `rng`: The random number generator used to pick vertices.
+///
+/// # Returns
+/// - A `Chromosome` where genes are assigned based on the following procedure:
+///   - Randomly select a vertex and assign it label `2`.
+///   - Assign one of its neighbors label `1`.
+///   - Remaining neighbors are labeled `0`.
+///   - Isolated vertices are handled separately and assigned labels to satisfy constraints.
+#[must_use]
+pub fn h1(graph: &CsrGraph, rng: &mut dyn RngCore) -> Chromosome {
+    roman(graph, |h| h.random_vertex(rng), |_, _| {})
 }
 
 /// A heuristic function to generate a `Chromosome` using a vertex degree-based approach.
 ///
 /// # Overview
-/// This heuristic assigns labels to vertices in an undirected graph (`UnGraph`) by prioritizing
+/// This heuristic assigns labels to vertices in an undirected graph (`CsrGraph`) by prioritizing
 /// vertices with the highest degree (number of neighbors). This strategy aims to maximize the
 /// impact of the labels on highly connected vertices, which are likely to influence the overall
 /// graph structure.
 ///
 /// # Arguments
-/// - `graph`: A reference to the undirected graph (`UnGraph`) for which the chromosome is generated.
+/// - `graph`: A reference to the undirected graph (`CsrGraph`) for which the chromosome is generated.
 ///
 /// # Returns
 /// - A `Chromosome` where genes are assigned based on the following procedure:
@@ -114,77 +212,19 @@ pub fn h1(graph: &UndirectedGraph<u32>) -> Chromosome {
 /// This heuristic is similar to `h1`, but it prioritizes vertices with the highest degree
 /// during the selection process, aiming to optimize the influence of the assigned labels.
 #[must_use]
-pub fn h2(graph: &UndirectedGraph<u32>) -> Chromosome {
-    // Inicializa um vetor de genes com valores 0.
-    // O tamanho do vetor é igual ao número de vértices no grafo.
-    let mut genes = vec![0u8; graph.order()];
-
-    // Faz uma cópia do grafo original para ser manipulado sem alterar o original.
-    let mut h = graph.clone();
-
-    // Enquanto o grafo h ainda tiver vértices... (Já captura o v = vértice de maior grau do grafo)
-    while let Some(v) = h.vertices().max_by_key(|&vertex| h.degree(vertex)).copied() {
-        // Passo 4: Define f(v) = 2, marcando o vértice v com a cor 2.
-        genes[v as usize] = 2;
-
-        // Obtém os vizinhos de v no grafo `h`.
-        let neighbors: Vec<u32> = h
-            .neighbors(&v)
-            .map(|n| n.copied().collect())
-            .unwrap_or_default();
-
-        // Passo 5: Se v tem vizinhos, escolha um (o primeiro da lista) e defina f(u) = 1.
-        if let Some(first_neighbor) = neighbors.first() {
-            genes[*first_neighbor as usize] = 1;
-
-            // Passo 6: Para os demais vizinhos de v, define f(w) = 0.
-            for w in neighbors.iter().skip(1) {
-                genes[*w as usize] = 0;
-            }
-        }
-
-        // Passo 7: Remove o vértice `v` e seus vizinhos do grafo `h`.
-        let _ = h.remove_vertex(&v);
-        for neighbor in neighbors {
-            let _ = h.remove_vertex(&neighbor);
-        }
-
-        // Passo 8: Enquanto houver vértices isolados em h...
-        let isolated_vertices = h.get_isolated_vertices();
-        for z in isolated_vertices {
-            genes[z as usize] = 1;
-            let has_neighbor_with_1 = graph
-                .neighbors(&z)
-                .is_some_and(|mut neighbors| neighbors.any(|n| genes[*n as usize] == 1));
-
-            // Verifica se `z` tem vizinhos no grafo original com f = 1.
-            if !has_neighbor_with_1 {
-                // Se não há vizinhos com f = 1, escolhe um vizinho com f = 0 e define f = 1.
-                if let Some(mut neighbors) = graph.neighbors(&z) {
-                    if let Some(first) = neighbors.find(|&n| genes[*n as usize] == 0) {
-                        genes[*first as usize] = 1;
-                    }
-                }
-            }
-
-            // Passo 12: Remove o vértice `z` do grafo `h`.
-            let _ = h.remove_vertex(&z);
-        }
-    }
-
-    // Retorna a solução como um Chromosome, encapsulando o vetor de genes.
-    Chromosome::new(genes)
+pub fn h2(graph: &CsrGraph) -> Chromosome {
+    roman(graph, Residual::max_degree_vertex, |_, _| {})
 }
 
 /// A heuristic function to generate a `Chromosome` using a degree-based and neighbor-priority approach.
 ///
 /// # Overview
-/// This heuristic assigns labels to vertices in an undirected graph (`UnGraph`) by prioritizing vertices with
+/// This heuristic assigns labels to vertices in an undirected graph (`CsrGraph`) by prioritizing vertices with
 /// the highest degree and further refining the selection of neighbors based on their degrees. The goal is to
 /// maximize the influence of labels while ensuring constraints are satisfied.
 ///
 /// # Arguments
-/// - `graph`: A reference to the undirected graph (`UnGraph`) for which the chromosome is generated.
+/// - `graph`: A reference to the undirected graph (`CsrGraph`) for which the chromosome is generated.
 ///
 /// # Returns
 /// - A `Chromosome` where genes are assigned based on the following procedure:
@@ -205,80 +245,21 @@ pub fn h2(graph: &UndirectedGraph<u32>) -> Chromosome {
 /// - This heuristic refines the approach of `h2` by introducing a sorting step to prioritize neighbors with higher degrees.
 /// - It is particularly useful in graphs where the connectivity of neighbors significantly influences the solution.
 #[must_use]
-pub fn h3(graph: &UndirectedGraph<u32>) -> Chromosome {
-    // Inicializa um vetor de genes com valores 0.
-    // O tamanho do vetor é igual ao número de vértices no grafo.
-    let mut genes = vec![0u8; graph.order()];
-
-    // Faz uma cópia do grafo original para ser manipulado sem alterar o original.
-    let mut h = graph.clone();
-
-    // Enquanto o grafo h ainda tiver vértices... (Já captura o v = vértice de maior grau do grafo)
-    while let Some(v) = h.vertices().max_by_key(|&vertex| h.degree(vertex)).copied() {
-        // Passo 4: Define f(v) = 2, marcando o vértice v com a cor 2.
-        genes[v as usize] = 2;
-
-        // Obtém os vizinhos de v no grafo `h`.
-        let mut neighbors: Vec<u32> = h
-            .neighbors(&v)
-            .map(|n| n.copied().collect())
-            .unwrap_or_default();
-
-        // Ordena os vizinhos de forma decrescente pelo grau
-        neighbors.sort_by(|&a, &b| h.degree(&b).cmp(&h.degree(&a)));
-
-        // Passo 5: Se v tem vizinhos, escolha um (o primeiro da lista, ou seja, o com maior grau) e defina f(u) = 1.
-        if let Some(first_neighbor) = neighbors.first() {
-            genes[*first_neighbor as usize] = 1;
-
-            // Passo 6: Para os demais vizinhos de v, define f(w) = 0.
-            for w in neighbors.iter().skip(1) {
-                genes[*w as usize] = 0;
-            }
-        }
-
-        // Passo 7: Remove o vértice `v` e seus vizinhos do grafo `h`.
-        let _ = h.remove_vertex(&v);
-        for neighbor in neighbors {
-            let _ = h.remove_vertex(&neighbor);
-        }
-
-        // Passo 8: Enquanto houver vértices isolados em h...
-        let isolated_vertices = h.get_isolated_vertices();
-        for z in isolated_vertices {
-            // Caso contrário, define f(z) = 1.
-            genes[z as usize] = 1;
-            let has_neighbor_with_1 = graph
-                .neighbors(&z)
-                .is_some_and(|mut neighbors| neighbors.any(|n| genes[*n as usize] == 1));
-
-            // Verifica se `z` tem vizinhos no grafo original com f = 1.
-            if !has_neighbor_with_1 {
-                // Se não há vizinhos com f = 1, escolhe um vizinho com f = 0 e define f = 1.
-                if let Some(mut neighbors) = graph.neighbors(&z) {
-                    if let Some(first) = neighbors.find(|&n| genes[*n as usize] == 0) {
-                        genes[*first as usize] = 1;
-                    }
-                }
-            }
-
-            // Passo 12: Remove o vértice `z` do grafo `h`.
-            let _ = h.remove_vertex(&z);
-        }
-    }
-
-    // Retorna a solução como um Chromosome, encapsulando o vetor de genes.
-    Chromosome::new(genes)
+pub fn h3(graph: &CsrGraph) -> Chromosome {
+    // Ordena os vizinhos de forma decrescente pelo grau.
+    roman(graph, Residual::max_degree_vertex, |h, neighbors| {
+        neighbors.sort_by_key(|&w| Reverse(h.degree(w)));
+    })
 }
 
 /// A heuristic function to generate a `Chromosome` using a degree-based and isolated vertex clustering approach.
 ///
 /// # Overview
-/// This heuristic assigns labels to vertices in an undirected graph (`UnGraph`) by prioritizing high-degree vertices
+/// This heuristic assigns labels to vertices in an undirected graph (`CsrGraph`) by prioritizing high-degree vertices
 /// and clustering isolated vertices with common neighbors. It ensures all constraints are met while minimizing label violations.
 ///
 /// # Arguments
-/// - `graph`: A reference to the undirected graph (`UnGraph`) for which the chromosome is generated.
+/// - `graph`: A reference to the undirected graph (`CsrGraph`) for which the chromosome is generated.
 ///
 /// # Returns
 /// - A `Chromosome` where genes are assigned based on the following procedure:
@@ -299,27 +280,24 @@ pub fn h3(graph: &UndirectedGraph<u32>) -> Chromosome {
 ///   into clusters based on their connections to common neighbors.
 /// - It is particularly useful for graphs with sparse regions or large numbers of isolated vertices.
 #[must_use]
-pub fn h4(graph: &UndirectedGraph<u32>) -> Chromosome {
+pub fn h4(graph: &CsrGraph) -> Chromosome {
     // Inicializa um vetor de genes com valores 0.
     // O tamanho do vetor é igual ao número de vértices no grafo.
     let mut genes = vec![0u8; graph.order()];
 
-    // Faz uma cópia do grafo original para ser manipulado sem alterar o original.
-    let mut h = graph.clone();
+    // Grafo residual `h`: começa igual ao original e perde vértices a cada passo.
+    let mut h = Residual::new(graph);
 
     // Enquanto o grafo h ainda tiver vértices... (Já captura o v = vértice de maior grau do grafo)
-    while let Some(v) = h.vertices().max_by_key(|&vertex| h.degree(vertex)).copied() {
+    while let Some(v) = h.max_degree_vertex() {
         // Passo 4: Define f(v) = 2, marcando o vértice v com a cor 2.
         genes[v as usize] = 2;
 
         // Obtém os vizinhos de v no grafo `h`.
-        let mut neighbors: Vec<u32> = h
-            .neighbors(&v)
-            .map(|n| n.copied().collect())
-            .unwrap_or_default();
+        let mut neighbors: Vec<u32> = h.neighbors(v).collect();
 
         // Ordena os vizinhos de forma decrescente pelo grau
-        neighbors.sort_by(|&a, &b| h.degree(&b).cmp(&h.degree(&a)));
+        neighbors.sort_by_key(|&w| Reverse(h.degree(w)));
 
         // Passo 5: Se v tem vizinhos, escolha um (o primeiro da lista, ou seja, o com maior grau) e defina f(u) = 1.
         if let Some(first_neighbor) = neighbors.first() {
@@ -332,19 +310,15 @@ pub fn h4(graph: &UndirectedGraph<u32>) -> Chromosome {
         }
 
         // Passo 7: Remove o vértice `v` e seus vizinhos do grafo `h`.
-        let _ = h.remove_vertex(&v);
+        h.remove_vertex(v);
         for neighbor in neighbors {
-            let _ = h.remove_vertex(&neighbor);
+            h.remove_vertex(neighbor);
         }
 
         // Passo 8-14: Processa vértices isolados
         loop {
             // Encontra vértices isolados em H
-            let isolated: Vec<u32> = h
-                .vertices()
-                .filter(|&v| h.degree(v).unwrap_or(0) == 0)
-                .copied()
-                .collect();
+            let isolated: Vec<u32> = h.take_isolated_vertices();
 
             if isolated.is_empty() {
                 break;
@@ -353,12 +327,7 @@ pub fn h4(graph: &UndirectedGraph<u32>) -> Chromosome {
             // Encontra os vizinhos dos vértices isolados no grafo original
             let mut ns: Vec<u32> = Vec::new();
             for &s in &isolated {
-                ns.extend(
-                    graph
-                        .neighbors(&s)
-                        .map(|n| n.copied().collect::<Vec<_>>())
-                        .unwrap_or_default(),
-                );
+                ns.extend_from_slice(graph.neighbors(s));
             }
             ns.sort_unstable();
             ns.dedup();
@@ -368,40 +337,116 @@ pub fn h4(graph: &UndirectedGraph<u32>) -> Chromosome {
                 // Conta quantos vizinhos z tem em S
                 let isolated_neighbors = isolated
                     .iter()
-                    .filter(|&&s| graph.contains_edge(&z, &s))
+                    .filter(|&&s| graph.contains_edge(z, s))
                     .count();
 
+                // Todo z recebe f(z) = 2
+                genes[z as usize] = 2;
+
                 if isolated_neighbors >= 2 {
-                    // Se z tem 2 ou mais vizinhos em S, define f(z) = 2
-                    genes[z as usize] = 2;
-                    // E seus vizinhos em S recebem 0
+                    // Se z tem 2 ou mais vizinhos em S, seus vizinhos em S recebem 0
                     for &s in &isolated {
-                        if graph.contains_edge(&z, &s) {
+                        if graph.contains_edge(z, s) {
                             genes[s as usize] = 0;
                         }
                     }
-                } else {
-                    // Caso contrário, também recebe 2
-                    genes[z as usize] = 2;
-                }
-            }
-
-            // Pinta com 0 os vértices restantes em S
-            for &s in &isolated {
-                if genes[s as usize] == 0 {
-                    genes[s as usize] = 0;
                 }
             }
 
             // Remove todos os vértices de S do grafo H
             for s in isolated {
-                let _ = h.remove_vertex(&s);
+                h.remove_vertex(s);
             }
         }
     }
 
     // Retorna a solução como um Chromosome, encapsulando o vetor de genes.
-    Chromosome::new(genes)
+    Chromosome::new(genes, graph)
+}
+
+/// Greedy randomized adaptive construction (GRASP).
+///
+/// # Overview
+/// Works like [`h2`], but instead of always labelling a vertex of maximum residual degree
+/// with `2`, picks it uniformly from a restricted candidate list (RCL): the vertices whose
+/// residual degree `d` satisfies `d >= d_max - alpha * (d_max - d_min)`. With `alpha = 0`
+/// the choice is purely greedy; with `alpha = 1` every remaining vertex is a candidate,
+/// as in [`h1`].
+///
+/// # Arguments
+/// - `graph`: A reference to the undirected graph (`CsrGraph`) for which the chromosome is generated.
+/// - `alpha`: Greediness of the RCL, in `[0, 1]`.
+/// - `rng`: The random number generator used to pick candidates.
+///
+/// # Returns
+/// - A `Chromosome` built with the same labelling steps as [`h2`].
+#[must_use]
+pub fn grasp(graph: &CsrGraph, alpha: f64, rng: &mut dyn RngCore) -> Chromosome {
+    roman(graph, |h| h.rcl_vertex(alpha, rng), |_, _| {})
+}
+
+/// Construction shared by [`h1`], [`h2`], [`h3`] and [`grasp`]; `select` picks the next
+/// vertex labelled `2` from the residual graph and `order` arranges its residual
+/// neighbours, the first of which is labelled `1`.
+fn roman<'a>(
+    graph: &'a CsrGraph,
+    mut select: impl FnMut(&mut Residual<'a>) -> Option<u32>,
+    order: impl Fn(&Residual<'a>, &mut [u32]),
+) -> Chromosome {
+    // Inicializa um vetor de genes com valores 0.
+    // O tamanho do vetor é igual ao número de vértices no grafo.
+    let mut genes = vec![0u8; graph.order()];
+
+    // Grafo residual `h`: começa igual ao original e perde vértices a cada passo.
+    let mut h = Residual::new(graph);
+
+    // Enquanto o grafo h ainda tiver vértices...
+    while let Some(v) = select(&mut h) {
+        // Passo 4: Define f(v) = 2, marcando o vértice v com a cor 2.
+        genes[v as usize] = 2;
+
+        // Obtém os vizinhos de v no grafo `h`.
+        let mut neighbors: Vec<u32> = h.neighbors(v).collect();
+        order(&h, &mut neighbors);
+
+        // Passo 5: Se v tem vizinhos, escolha um (o primeiro da lista) e defina f(u) = 1.
+        if let Some(first_neighbor) = neighbors.first() {
+            genes[*first_neighbor as usize] = 1;
+
+            // Passo 6: Para os demais vizinhos de v, define f(w) = 0.
+            for w in neighbors.iter().skip(1) {
+                genes[*w as usize] = 0;
+            }
+        }
+
+        // Passo 7: Remove o vértice `v` e seus vizinhos do grafo `h`.
+        h.remove_vertex(v);
+        for neighbor in neighbors {
+            h.remove_vertex(neighbor);
+        }
+
+        // Passo 8: Enquanto houver vértices isolados em h...
+        let isolated_vertices = h.take_isolated_vertices();
+        for z in isolated_vertices {
+            // Caso contrário, define f(z) = 1.
+            genes[z as usize] = 1;
+            let has_neighbor_with_1 = graph.neighbors(z).iter().any(|&n| genes[n as usize] == 1);
+
+            // Verifica se `z` tem vizinhos no grafo original com f = 1.
+            if !has_neighbor_with_1 {
+                // Se não há vizinhos com f = 1, escolhe um vizinho com f = 0 e define f = 1.
+                if let Some(&first) = graph.neighbors(z).iter().find(|&&n| genes[n as usize] == 0) {
+                    genes[first as usize] = 1;
+                }
+            }
+
+            // Passo 12: Remove o vértice `z` do grafo `h`.
+            h.remove_vertex(z);
+        }
+    }
+
+    // Retorna a solução como um Chromosome, encapsulando o vetor de genes.
+    Chromosome::new(genes, graph)
 }
 
 /// A heuristic function to generate a `Chromosome` by assigning a default label to all vertices.
@@ -411,13 +456,621 @@ pub fn h4(graph: &UndirectedGraph<u32>) -> Chromosome {
 /// It serves as a baseline or trivial solution, ensuring all vertices satisfy a minimum labeling constraint.
 ///
 /// # Arguments
-/// - `graph`: A reference to the undirected graph (`UnGraph`) for which the chromosome is generated.
+/// - `graph`: A reference to the undirected graph (`CsrGraph`) for which the chromosome is generated.
 ///
 /// # Returns
 /// - A `Chromosome` where all genes are assigned the label `1`.
 #[must_use]
-pub fn h5(graph: &UndirectedGraph<u32>) -> Chromosome {
+pub fn h5(graph: &CsrGraph) -> Chromosome {
     // Cria um vetor de genes com todos os vértices rotulados com valor 1;
     let genes: Vec<u8> = vec![1; graph.order()];
-    Chromosome::new(genes)
+    Chromosome::new(genes, graph)
+}
+
+/// Italian version of [`h1`].
+///
+/// # Overview
+/// Italian domination only asks the labels of the neighbours of a `0`-vertex to sum to at
+/// least `2`, so a vertex chosen at random gets label `2` and all its neighbours get `0`;
+/// no neighbour needs label `1`. A vertex left isolated in the residual graph stays `0`
+/// if its neighbours already sum to `2`. If they sum to `1`, its first neighbour labelled
+/// `0` gets label `1`; otherwise the vertex itself gets label `1`.
+///
+/// # Arguments
+/// - `graph`: A reference to the undirected graph (`CsrGraph`) for which the chromosome is generated.
+/// - `rng`: The random number generator used to pick vertices.
+///
+/// # Returns
+/// - An Italian dominating function of `graph` with labels `0`, `1` and `2`.
+#[must_use]
+pub fn ih1(graph: &CsrGraph, rng: &mut dyn RngCore) -> Chromosome {
+    italian(
+        graph,
+        |h| h.random_vertex(rng),
+        Italian {
+            by_degree: false,
+            cluster: false,
+        },
+    )
+}
+
+/// Italian version of [`h2`]: like [`ih1`], but always picks a vertex of maximum
+/// residual degree.
+///
+/// # Arguments
+/// - `graph`: A reference to the undirected graph (`CsrGraph`) for which the chromosome is generated.
+///
+/// # Returns
+/// - An Italian dominating function of `graph` with labels `0`, `1` and `2`.
+#[must_use]
+pub fn ih2(graph: &CsrGraph) -> Chromosome {
+    italian(
+        graph,
+        Residual::max_degree_vertex,
+        Italian {
+            by_degree: false,
+            cluster: false,
+        },
+    )
+}
+
+/// Italian version of [`h3`]: like [`ih2`], but an isolated vertex whose neighbours sum
+/// to `1` raises its `0`-neighbour of highest degree to `1`, as [`h3`] gives label `1`
+/// to the neighbour of highest degree.
+///
+/// # Arguments
+/// - `graph`: A reference to the undirected graph (`CsrGraph`) for which the chromosome is generated.
+///
+/// # Returns
+/// - An Italian dominating function of `graph` with labels `0`, `1` and `2`.
+#[must_use]
+pub fn ih3(graph: &CsrGraph) -> Chromosome {
+    italian(
+        graph,
+        Residual::max_degree_vertex,
+        Italian {
+            by_degree: true,
+            cluster: false,
+        },
+    )
+}
+
+/// Italian version of [`h4`]: like [`ih3`], but first clusters the vertices left isolated
+/// in the residual graph.
+///
+/// A vertex adjacent to at least two isolated vertices whose neighbours do not sum to `2`
+/// yet gets label `2`, covering all of them at once. Such vertices are taken in
+/// decreasing order of the number of isolated vertices they cover; the isolated vertices
+/// still uncovered are then handled as in [`ih3`].
+///
+/// # Arguments
+/// - `graph`: A reference to the undirected graph (`CsrGraph`) for which the chromosome is generated.
+///
+/// # Returns
+/// - An Italian dominating function of `graph` with labels `0`, `1` and `2`.
+#[must_use]
+pub fn ih4(graph: &CsrGraph) -> Chromosome {
+    italian(
+        graph,
+        Residual::max_degree_vertex,
+        Italian {
+            by_degree: true,
+            cluster: true,
+        },
+    )
+}
+
+/// Options of the Italian constructions.
+#[derive(Clone, Copy)]
+struct Italian {
+    /// Raise the `0`-neighbour of highest degree instead of the first one.
+    by_degree: bool,
+    /// Cover isolated vertices in clusters before handling them one by one.
+    cluster: bool,
+}
+
+/// Italian construction shared by [`ih1`] to [`ih4`]; `select` picks the next vertex
+/// labelled `2` from the residual graph.
+fn italian<'a>(
+    graph: &'a CsrGraph,
+    mut select: impl FnMut(&mut Residual<'a>) -> Option<u32>,
+    options: Italian,
+) -> Chromosome {
+    let mut genes = vec![0u8; graph.order()];
+    let mut h = Residual::new(graph);
+
+    while let Some(v) = select(&mut h) {
+        // v recebe 2 e todos os seus vizinhos ficam com 0.
+        genes[v as usize] = 2;
+        let neighbors: Vec<u32> = h.neighbors(v).collect();
+        h.remove_vertex(v);
+        for neighbor in neighbors {
+            h.remove_vertex(neighbor);
+        }
+
+        // Vértices isolados não têm vizinhos em h, então removê-los não isola outros.
+        let isolated = h.take_isolated_vertices();
+        if options.cluster {
+            cover_clusters(graph, &mut genes, &isolated);
+        }
+        for z in isolated {
+            cover_italian(graph, &mut genes, z, options.by_degree);
+            h.remove_vertex(z);
+        }
+    }
+
+    Chromosome::new(genes, graph)
+}
+
+/// Sum of the labels of the neighbours of `vertex`, capped at `2`.
+fn neighbor_sum(graph: &CsrGraph, genes: &[u8], vertex: u32) -> u8 {
+    graph
+        .neighbors(vertex)
+        .iter()
+        .map(|&n| genes[n as usize])
+        .fold(0, |sum, label| sum.saturating_add(label).min(2))
+}
+
+/// Makes the `0`-vertex `z` satisfy the Italian condition by raising a `0`-neighbour to
+/// `1` or, if that is not enough, `z` itself to `1`.
+fn cover_italian(graph: &CsrGraph, genes: &mut [u8], z: u32, by_degree: bool) {
+    if genes[z as usize] > 0 {
+        return;
+    }
+
+    match neighbor_sum(graph, genes, z) {
+        2 => {}
+        1 => {
+            let mut zeros = graph
+                .neighbors(z)
+                .iter()
+                .copied()
+                .filter(|&n| genes[n as usize] == 0);
+            let target = if by_degree {
+                zeros.max_by_key(|&n| graph.degree(n))
+            } else {
+                zeros.next()
+            };
+            genes[target.unwrap_or(z) as usize] = 1;
+        }
+        _ => genes[z as usize] = 1,
+    }
+}
+
+/// Gives label `2` to the vertices adjacent to at least two vertices of `isolated` whose
+/// neighbours do not sum to `2`, in decreasing order of how many of them they cover.
+fn cover_clusters(graph: &CsrGraph, genes: &mut [u8], isolated: &[u32]) {
+    let mut candidates: Vec<u32> = isolated
+        .iter()
+        .flat_map(|&s| graph.neighbors(s).iter().copied())
+        .collect();
+    candidates.sort_unstable();
+    candidates.dedup();
+
+    let uncovered = |genes: &[u8], z: u32| {
+        isolated
+            .iter()
+            .filter(|&&s| graph.contains_edge(z, s) && neighbor_sum(graph, genes, s) < 2)
+            .count()
+    };
+    candidates.sort_by_key(|&z| Reverse(uncovered(genes, z)));
+
+    for z in candidates {
+        if genes[z as usize] < 2 && uncovered(genes, z) >= 2 {
+            genes[z as usize] = 2;
+        }
+    }
+}
+
+/// Greedy construction of an independent Roman dominating function.
+///
+/// # Overview
+/// Like [`h2`], repeatedly gives label `2` to a vertex of maximum residual degree, but
+/// labels all its neighbours `0`. A vertex left isolated in the residual graph has only
+/// neighbours labelled `0` that are adjacent to a `2`, so it gets label `1`. No two
+/// positive vertices are adjacent.
+///
+/// # Arguments
+/// - `graph`: A reference to the undirected graph (`CsrGraph`) for which the chromosome is generated.
+///
+/// # Returns
+/// - An independent Roman dominating function of `graph`.
+#[must_use]
+pub fn ir1(graph: &CsrGraph) -> Chromosome {
+    let mut genes = vec![0u8; graph.order()];
+    let mut h = Residual::new(graph);
+
+    loop {
+        // Os vizinhos de um vértice isolado em h já foram removidos com rótulo 0; os
+        // vértices isolados no grafo original também recebem 1.
+        for z in h.take_isolated_vertices() {
+            genes[z as usize] = 1;
+            h.remove_vertex(z);
+        }
+
+        let Some(v) = h.max_degree_vertex() else {
+            break;
+        };
+        genes[v as usize] = 2;
+        let neighbors: Vec<u32> = h.neighbors(v).collect();
+        h.remove_vertex(v);
+        for neighbor in neighbors {
+            h.remove_vertex(neighbor);
+        }
+    }
+
+    Chromosome::new(genes, graph)
+}
+
+/// Greedy construction of a perfect Roman dominating function.
+///
+/// # Overview
+/// Visits the vertices in decreasing order of degree. A vertex that is still unlabelled
+/// and has no neighbour labelled `0` gets label `2`, and its unlabelled neighbours get
+/// label `0`. A vertex labelled `0` is never adjacent to a later `2`, so it has exactly
+/// one `2`-neighbour. Vertices left unlabelled, including isolated ones, get label `1`.
+///
+/// # Arguments
+/// - `graph`: A reference to the undirected graph (`CsrGraph`) for which the chromosome is generated.
+///
+/// # Returns
+/// - A perfect Roman dominating function of `graph`.
+#[must_use]
+pub fn pr1(graph: &CsrGraph) -> Chromosome {
+    const UNLABELLED: u8 = u8::MAX;
+    let mut genes = vec![UNLABELLED; graph.order()];
+
+    let mut order: Vec<u32> = graph.vertices().filter(|&v| graph.degree(v) > 0).collect();
+    order.sort_by_key(|&v| Reverse(graph.degree(v)));
+
+    for v in order {
+        let neighbors = graph.neighbors(v);
+        if genes[v as usize] != UNLABELLED || neighbors.iter().any(|&n| genes[n as usize] == 0) {
+            continue;
+        }
+
+        genes[v as usize] = 2;
+        for &n in neighbors {
+            if genes[n as usize] == UNLABELLED {
+                genes[n as usize] = 0;
+            }
+        }
+    }
+
+    for label in &mut genes {
+        if *label == UNLABELLED {
+            *label = 1;
+        }
+    }
+
+    Chromosome::new(genes, graph)
+}
+
+/// Cost-aware version of [`h2`] for graphs with vertex costs.
+///
+/// # Overview
+/// Instead of a vertex of maximum residual degree, labels `2` the vertex that dominates
+/// the most residual vertices (itself and its residual neighbours) per unit of the cost
+/// of label `2`; see [`CsrGraph::cost`]. The residual neighbour with the cheapest label
+/// `1` gets label `1` and the others `0`. A vertex left isolated in the residual graph gets
+/// label `1` and, if it has no neighbour labelled `1`, so does its cheapest neighbour
+/// labelled `0`.
+///
+/// Without vertex costs, every label `2` costs the same, so vertices of maximum residual
+/// degree are chosen first, as by [`h2`].
+///
+/// # Arguments
+/// - `graph`: A reference to the undirected graph (`CsrGraph`) for which the chromosome is generated.
+///
+/// # Returns
+/// - A `Chromosome` built with the same labelling steps as [`h2`].
+#[must_use]
+pub fn wh(graph: &CsrGraph) -> Chromosome {
+    let mut genes = vec![0u8; graph.order()];
+    let mut h = Residual::new(graph);
+
+    // Fila de prioridade preguiçosa: entradas com grau desatualizado são reinseridas.
+    let mut queue: BinaryHeap<Ratio> = graph
+        .vertices()
+        .map(|v| Ratio::new(graph, v, graph.degree(v)))
+        .collect();
+    let cheapest_one =
+        |candidates: &mut dyn Iterator<Item = u32>| candidates.min_by_key(|&n| graph.cost(n, 1));
+
+    while let Some(Ratio {
+        vertex: v, degree, ..
+    }) = queue.pop()
+    {
+        if !h.contains(v) {
+            continue;
+        }
+        if h.degree(v) != degree {
+            queue.push(Ratio::new(graph, v, h.degree(v)));
+            continue;
+        }
+
+        genes[v as usize] = 2;
+        let neighbors: Vec<u32> = h.neighbors(v).collect();
+        if let Some(one) = cheapest_one(&mut neighbors.iter().copied()) {
+            genes[one as usize] = 1;
+        }
+
+        h.remove_vertex(v);
+        for neighbor in neighbors {
+            h.remove_vertex(neighbor);
+        }
+
+        for z in h.take_isolated_vertices() {
+            genes[z as usize] = 1;
+            let has_neighbor_with_1 = graph.neighbors(z).iter().any(|&n| genes[n as usize] == 1);
+            if !has_neighbor_with_1 {
+                let mut zeros = graph
+                    .neighbors(z)
+                    .iter()
+                    .copied()
+                    .filter(|&n| genes[n as usize] == 0);
+                if let Some(one) = cheapest_one(&mut zeros) {
+                    genes[one as usize] = 1;
+                }
+            }
+            h.remove_vertex(z);
+        }
+    }
+
+    Chromosome::new(genes, graph)
+}
+
+/// Entry of the priority queue of [`wh`]: a vertex ordered by the number of vertices it
+/// dominates per unit of the cost of label `2`, ties broken by the smaller index.
+#[derive(Clone, Copy, Debug)]
+struct Ratio {
+    vertex: u32,
+    degree: usize,
+    cost: usize,
+}
+
+impl Ratio {
+    fn new(graph: &CsrGraph, vertex: u32, degree: usize) -> Self {
+        Self {
+            vertex,
+            degree,
+            cost: graph.cost(vertex, 2),
+        }
+    }
+}
+
+impl Ord for Ratio {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // (d₁ + 1) / c₁ comparado com (d₂ + 1) / c₂ sem divisão; custo 0 vence.
+        let lhs = (self.degree as u128 + 1) * other.cost as u128;
+        let rhs = (other.degree as u128 + 1) * self.cost as u128;
+        lhs.cmp(&rhs).then_with(|| other.vertex.cmp(&self.vertex))
+    }
+}
+
+impl PartialOrd for Ratio {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Ratio {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Ratio {}
+
+/// Residual graph used by the constructive heuristics.
+///
+/// Starts as the whole graph and loses vertices as the heuristics label them. Residual
+/// degrees live in a [`DegreeBuckets`] queue, so removing a vertex costs `O(deg)` and a
+/// vertex of maximum residual degree is found in amortized `O(1)`. The vertices still
+/// present are also kept in a dense list for uniform random picks. The original graph is
+/// never cloned.
+struct Residual<'a> {
+    graph: &'a CsrGraph,
+    queue: DegreeBuckets,
+    alive: Vec<u32>,
+    alive_position: Vec<usize>,
+    isolated: Vec<u32>,
+}
+
+impl<'a> Residual<'a> {
+    fn new(graph: &'a CsrGraph) -> Self {
+        let isolated = graph.vertices().filter(|&v| graph.degree(v) == 0).collect();
+
+        Self {
+            graph,
+            queue: DegreeBuckets::new(graph.vertices().map(|v| graph.degree(v)).collect()),
+            alive: graph.vertices().collect(),
+            alive_position: (0..graph.order()).collect(),
+            isolated,
+        }
+    }
+
+    /// A vertex chosen uniformly among those still present.
+    fn random_vertex(&self, rng: &mut dyn RngCore) -> Option<u32> {
+        self.alive.choose(rng).copied()
+    }
+
+    /// A vertex of maximum residual degree.
+    fn max_degree_vertex(&mut self) -> Option<u32> {
+        self.queue.max()
+    }
+
+    /// A vertex chosen uniformly among those whose residual degree is at least
+    /// `d_max - alpha * (d_max - d_min)`.
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    fn rcl_vertex(&mut self, alpha: f64, rng: &mut dyn RngCore) -> Option<u32> {
+        let top = self.queue.max()?;
+        let max = self.queue.degree(top);
+        let min = self.queue.min_degree()?;
+        let threshold = max - (alpha * (max - min) as f64).floor() as usize;
+
+        let candidates = self.queue.count_at_least(threshold);
+        self.queue
+            .nth_at_least(threshold, rng.gen_range(0..candidates))
+    }
+
+    /// Neighbours of `vertex` still present in the residual graph.
+    fn neighbors(&self, vertex: u32) -> impl Iterator<Item = u32> + '_ {
+        self.graph
+            .neighbors(vertex)
+            .iter()
+            .copied()
+            .filter(|&n| self.queue.contains(n))
+    }
+
+    fn degree(&self, vertex: u32) -> usize {
+        self.queue.degree(vertex)
+    }
+
+    /// Returns `true` if `vertex` is still present.
+    fn contains(&self, vertex: u32) -> bool {
+        self.queue.contains(vertex)
+    }
+
+    /// Removes `vertex`, if present, and records neighbours left without edges.
+    fn remove_vertex(&mut self, vertex: u32) {
+        if !self.queue.contains(vertex) {
+            return;
+        }
+        self.queue.remove(vertex);
+
+        let idx = self.alive_position[vertex as usize];
+        self.alive.swap_remove(idx);
+        if let Some(&moved) = self.alive.get(idx) {
+            self.alive_position[moved as usize] = idx;
+        }
+
+        for &n in self.graph.neighbors(vertex) {
+            if self.queue.contains(n) && self.queue.decrement(n) == 0 {
+                self.isolated.push(n);
+            }
+        }
+    }
+
+    /// Returns the vertices isolated in the residual graph that were not returned before.
+    fn take_isolated_vertices(&mut self) -> Vec<u32> {
+        let mut isolated = std::mem::take(&mut self.isolated);
+        isolated.retain(|&v| self.queue.contains(v));
+        isolated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::genetic::{
+        IndependentRomanDomination, ItalianDomination, PerfectRomanDomination, Problem,
+        RomanDomination, TotalItalianDomination,
+    };
+
+    /// A G(n, p) graph with random `n` and `p`, possibly with isolated vertices.
+    fn random_graph(rng: &mut StdRng) -> CsrGraph {
+        let order = rng.gen_range(2..30);
+        let probability = rng.gen_range(0.05..0.5);
+        let edges: Vec<(u32, u32)> = (0..order)
+            .flat_map(|u| (u + 1..order).map(move |v| (u, v)))
+            .filter(|_| rng.gen_bool(probability))
+            .collect();
+        CsrGraph::from_edges(order as usize, &edges)
+    }
+
+    #[test]
+    fn greedy_rcl_picks_a_vertex_of_maximum_residual_degree() {
+        let mut rng = StdRng::seed_from_u64(34);
+        for _ in 0..40 {
+            let graph = random_graph(&mut rng);
+
+            // Percorre o grafo residual como `grasp` faz, removendo v e seus vizinhos.
+            let mut h = Residual::new(&graph);
+            while let Some(v) = h.rcl_vertex(0.0, &mut rng) {
+                let top = h.max_degree_vertex().unwrap();
+                assert_eq!(h.degree(v), h.degree(top));
+
+                let neighbors: Vec<u32> = h.neighbors(v).collect();
+                h.remove_vertex(v);
+                for n in neighbors {
+                    h.remove_vertex(n);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn greedy_grasp_matches_h2_on_a_star() {
+        let edges: Vec<(u32, u32)> = (1..8).map(|leaf| (0, leaf)).collect();
+        let graph = CsrGraph::from_edges(8, &edges);
+        let mut rng = StdRng::seed_from_u64(34);
+
+        let greedy = grasp(&graph, 0.0, &mut rng);
+        assert_eq!(greedy.genes(), h2(&graph).genes());
+        assert_eq!(greedy.genes()[0], 2);
+    }
+
+    #[test]
+    fn italian_heuristics_build_italian_dominating_functions() {
+        let mut rng = StdRng::seed_from_u64(43);
+        for _ in 0..40 {
+            let graph = random_graph(&mut rng);
+            let total = graph.vertices().all(|v| graph.degree(v) > 0);
+            for heuristic in [IH1, IH2, IH3, IH4] {
+                let chromosome = heuristic.generate(&graph, Some(&mut rng));
+                assert!(
+                    ItalianDomination.is_feasible(&chromosome, &graph),
+                    "{}",
+                    heuristic.name()
+                );
+
+                // A versão total só é viável sem vértices isolados.
+                if total {
+                    let mut chromosome = chromosome;
+                    TotalItalianDomination.prepare(&mut chromosome, &graph);
+                    assert!(TotalItalianDomination.is_feasible(&chromosome, &graph));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn ir1_and_pr1_build_feasible_labellings() {
+        let mut rng = StdRng::seed_from_u64(44);
+        for _ in 0..40 {
+            let graph = random_graph(&mut rng);
+            assert!(IndependentRomanDomination.is_feasible(&ir1(&graph), &graph));
+            assert!(PerfectRomanDomination.is_feasible(&pr1(&graph), &graph));
+        }
+
+        // Em caminhos, ambas atingem i_R(P_n) = γ_R^p(P_n) = ⌈2n / 3⌉.
+        for n in 1..=12u32 {
+            let edges: Vec<(u32, u32)> = (1..n).map(|v| (v - 1, v)).collect();
+            let path = CsrGraph::from_edges(n as usize, &edges);
+            let expected = (2 * n as usize).div_ceil(3);
+            assert_eq!(ir1(&path).fitness(), expected, "ir1 P_{n}");
+            assert_eq!(pr1(&path).fitness(), expected, "pr1 P_{n}");
+        }
+    }
+
+    #[test]
+    fn wh_avoids_an_expensive_hub() {
+        // Estrela K_{1,6} cujo centro custa 10 com rótulo 1 e 20 com rótulo 2.
+        let edges: Vec<(u32, u32)> = (1..7).map(|leaf| (0, leaf)).collect();
+        let mut costs = vec![[1, 2]; 7];
+        costs[0] = [10, 20];
+        let graph = CsrGraph::from_edges(7, &edges).with_costs(costs);
+
+        let (greedy, weighted) = (h2(&graph), wh(&graph));
+        assert!(RomanDomination.is_feasible(&weighted, &graph));
+        assert_eq!(greedy.fitness(), 21);
+        assert_eq!(weighted.fitness(), 17);
+    }
 }
@@ -0,0 +1,368 @@
+use std::{fmt, str::FromStr};
+
+use rayon::prelude::*;
+
+use crate::csr::CsrGraph;
+
+use super::{Chromosome, Crossover, Population, Problem, Selection};
+
+/// Migration topology connecting the islands of an [`IslandModel`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Topology {
+    /// Island `i` sends its migrants to island `i + 1` (the last one sends to the first).
+    Ring,
+    /// Every island sends its migrants to every other island.
+    FullyConnected,
+}
+
+impl Topology {
+    /// Returns the indices of the islands that receive migrants from island `source`.
+    #[must_use]
+    pub fn targets(self, source: usize, islands: usize) -> Vec<usize> {
+        if islands < 2 {
+            return Vec::new();
+        }
+
+        match self {
+            Self::Ring => vec![(source + 1) % islands],
+            Self::FullyConnected => (0..islands).filter(|&idx| idx != source).collect(),
+        }
+    }
+}
+
+impl FromStr for Topology {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ring" => Ok(Self::Ring),
+            "full" | "fully-connected" => Ok(Self::FullyConnected),
+            _ => Err(format!(
+                "Invalid topology: {s} (expected \"ring\" or \"full\")"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Topology {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ring => write!(f, "ring"),
+            Self::FullyConnected => write!(f, "full"),
+        }
+    }
+}
+
+/// A population together with the operators used to evolve it.
+///
+/// # Fields
+/// - `population: Population`: The chromosomes living on the island.
+/// - `selector: S`: The selection strategy of the island.
+/// - `crossover: C`: The crossover operator of the island.
+pub struct Island<S, C> {
+    population: Population,
+    selector: S,
+    crossover: C,
+}
+
+impl<S: Selection, C: Crossover> Island<S, C> {
+    /// Creates a new island from a population and its operators.
+    #[inline]
+    #[must_use]
+    pub fn new(population: Population, selector: S, crossover: C) -> Self {
+        Self {
+            population,
+            selector,
+            crossover,
+        }
+    }
+
+    /// Returns a reference to the population of the island.
+    #[inline]
+    #[must_use]
+    pub fn population(&self) -> &Population {
+        &self.population
+    }
+
+    /// Returns a mutable reference to the population of the island.
+    #[inline]
+    #[must_use]
+    pub fn population_mut(&mut self) -> &mut Population {
+        &mut self.population
+    }
+
+    /// Returns a mutable reference to the crossover operator of the island.
+    #[inline]
+    #[must_use]
+    pub fn crossover_mut(&mut self) -> &mut C {
+        &mut self.crossover
+    }
+}
+
+/// Island-model genetic algorithm.
+///
+/// Several islands evolve in parallel and, every `migration_interval` generations,
+/// each island sends copies of its `migration_size` best chromosomes to the islands
+/// given by the migration [`Topology`]. Migrants replace the worst chromosomes of
+/// the receiving island.
+pub struct IslandModel<S, C> {
+    islands: Vec<Island<S, C>>,
+    topology: Topology,
+    migration_interval: usize,
+    migration_size: usize,
+    generation: usize,
+}
+
+impl<S, C> IslandModel<S, C>
+where
+    S: Selection + Send + Sync,
+    C: Crossover + Send + Sync,
+{
+    /// Creates a new island model.
+    ///
+    /// # Parameters
+    /// - `islands: Vec<Island<S, C>>`: The islands to evolve.
+    /// - `topology: Topology`: Which islands exchange migrants.
+    /// - `migration_interval: usize`: Number of generations between migrations.
+    /// - `migration_size: usize`: Number of chromosomes each island sends per migration.
+    ///
+    /// # Panics
+    /// - If `islands` is empty.
+    /// - If `migration_interval` is zero.
+    #[must_use]
+    pub fn new(
+        islands: Vec<Island<S, C>>,
+        topology: Topology,
+        migration_interval: usize,
+        migration_size: usize,
+    ) -> Self {
+        assert!(!islands.is_empty(), "At least one island must be provided.");
+        assert!(
+            migration_interval > 0,
+            "Migration interval must be greater than 0"
+        );
+
+        Self {
+            islands,
+            topology,
+            migration_interval,
+            migration_size,
+            generation: 0,
+        }
+    }
+
+    /// Returns the islands of the model.
+    #[inline]
+    #[must_use]
+    pub fn islands(&self) -> &[Island<S, C>] {
+        &self.islands
+    }
+
+    /// Returns the islands of the model mutably.
+    #[inline]
+    #[must_use]
+    pub fn islands_mut(&mut self) -> &mut [Island<S, C>] {
+        &mut self.islands
+    }
+
+    /// Evolves every island by one generation in parallel, then migrates if the
+    /// migration interval has elapsed. Offspring are repaired with `problem`.
+    pub fn envolve(&mut self, problem: &dyn Problem, graph: &CsrGraph) {
+        self.step(problem, graph, None);
+    }
+
+    /// Evolves every island like [`IslandModel::envolve`], deterministically.
+    ///
+    /// Island `i` evolves with [`Population::envolve_seeded`] using a seed derived from
+    /// `seed` and `i`, so the result does not depend on the number of threads.
+    pub fn envolve_seeded(&mut self, problem: &dyn Problem, graph: &CsrGraph, seed: u64) {
+        self.step(problem, graph, Some(seed));
+    }
+
+    fn step(&mut self, problem: &dyn Problem, graph: &CsrGraph, seed: Option<u64>) {
+        self.islands
+            .par_iter_mut()
+            .enumerate()
+            .for_each(|(idx, island)| match seed {
+                Some(seed) => island.population.envolve_seeded(
+                    &island.selector,
+                    &island.crossover,
+                    problem,
+                    graph,
+                    seed.wrapping_add(idx as u64),
+                ),
+                None => {
+                    island
+                        .population
+                        .envolve(&island.selector, &island.crossover, problem, graph);
+                }
+            });
+
+        self.generation += 1;
+        if self.generation % self.migration_interval == 0 {
+            self.migrate();
+        }
+    }
+
+    /// Sends the best chromosomes of every island to its neighbours in the topology.
+    pub fn migrate(&mut self) {
+        if self.migration_size == 0 {
+            return;
+        }
+
+        let count = self.islands.len();
+        let emigrants: Vec<Vec<Chromosome>> = self
+            .islands
+            .iter()
+            .map(|island| island.population.best_chromosomes(self.migration_size))
+            .collect();
+
+        let mut incoming: Vec<Vec<Chromosome>> = vec![Vec::new(); count];
+        for (source, migrants) in emigrants.into_iter().enumerate() {
+            for target in self.topology.targets(source, count) {
+                incoming[target].extend(migrants.iter().cloned());
+            }
+        }
+
+        for (island, migrants) in self.islands.iter_mut().zip(incoming) {
+            island.population.replace_worst(migrants);
+        }
+    }
+
+    /// Returns the best chromosome over all islands.
+    #[must_use]
+    pub fn best_chromosome(&self) -> Option<&Chromosome> {
+        self.islands
+            .iter()
+            .filter_map(|island| island.population.best_chromosome())
+            .min_by_key(|chromosome| chromosome.fitness())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::genetic::{Heuristic, KTournament, SinglePoint, TotalRomanDomination, H1};
+
+    fn model(graph: &CsrGraph) -> IslandModel<KTournament, SinglePoint> {
+        let heuristics: Vec<Arc<dyn Heuristic>> = vec![Arc::new(H1)];
+        let islands = (0..4)
+            .map(|idx| {
+                let mut rng = StdRng::seed_from_u64(idx);
+                let population =
+                    Population::new(20, &heuristics, &TotalRomanDomination, graph, &mut rng);
+                Island::new(population, KTournament::new(3), SinglePoint::new(0.9))
+            })
+            .collect();
+        IslandModel::new(islands, Topology::Ring, 3, 2)
+    }
+
+    fn genes(population: &Population) -> Vec<Vec<u8>> {
+        population
+            .chromosomes()
+            .iter()
+            .map(|chromosome| chromosome.genes().to_vec())
+            .collect()
+    }
+
+    #[test]
+    fn ring_sends_to_the_next_island_and_full_to_all_others() {
+        for source in 0..4 {
+            assert_eq!(Topology::Ring.targets(source, 4), [(source + 1) % 4]);
+            let others: Vec<usize> = (0..4).filter(|&idx| idx != source).collect();
+            assert_eq!(Topology::FullyConnected.targets(source, 4), others);
+        }
+        assert!(Topology::Ring.targets(0, 1).is_empty());
+        assert!(Topology::FullyConnected.targets(0, 1).is_empty());
+    }
+
+    /// Islands whose chromosomes cost `10 * i + 1` to `10 * i + 4`, in a model that
+    /// migrates the best `migration_size` chromosomes of each island.
+    fn staged_model(
+        graph: &CsrGraph,
+        topology: Topology,
+        migration_size: usize,
+    ) -> IslandModel<KTournament, SinglePoint> {
+        let islands = (0..3)
+            .map(|island| {
+                let chromosomes = (1..=4)
+                    .map(|rank| {
+                        let fitness = 10 * island + rank;
+                        let genes = (0..graph.order()).map(|v| u8::from(v < fitness)).collect();
+                        Chromosome::new(genes, graph)
+                    })
+                    .collect();
+                Island::new(
+                    Population::from_chromosomes(chromosomes),
+                    KTournament::new(3),
+                    SinglePoint::new(0.9),
+                )
+            })
+            .collect();
+        IslandModel::new(islands, topology, 1, migration_size)
+    }
+
+    fn fitness(model: &IslandModel<KTournament, SinglePoint>) -> Vec<Vec<usize>> {
+        model
+            .islands()
+            .iter()
+            .map(|island| {
+                let mut fitness: Vec<usize> = island
+                    .population()
+                    .chromosomes()
+                    .iter()
+                    .map(Chromosome::fitness)
+                    .collect();
+                fitness.sort_unstable();
+                fitness
+            })
+            .collect()
+    }
+
+    #[test]
+    fn migrants_replace_the_worst_chromosomes_of_their_targets() {
+        let graph = CsrGraph::from_edges(40, &[]);
+
+        let mut ring = staged_model(&graph, Topology::Ring, 1);
+        ring.migrate();
+        assert_eq!(
+            fitness(&ring),
+            [[1, 2, 3, 21], [1, 11, 12, 13], [11, 21, 22, 23]]
+        );
+
+        let mut full = staged_model(&graph, Topology::FullyConnected, 1);
+        full.migrate();
+        assert_eq!(
+            fitness(&full),
+            [[1, 2, 11, 21], [1, 11, 12, 21], [1, 11, 21, 22]]
+        );
+    }
+
+    #[test]
+    fn island_seeds_are_offset_by_the_island_index() {
+        let graph = CsrGraph::circulant(60, &[1, 7]);
+        let mut model = model(&graph);
+        let populations: Vec<Population> = model
+            .islands()
+            .iter()
+            .map(|island| island.population().clone())
+            .collect();
+
+        // Uma geração não alcança o intervalo de migração.
+        model.envolve_seeded(&TotalRomanDomination, &graph, 7);
+        for (idx, (island, mut population)) in model.islands().iter().zip(populations).enumerate() {
+            population.envolve_seeded(
+                &island.selector,
+                &island.crossover,
+                &TotalRomanDomination,
+                &graph,
+                7 + idx as u64,
+            );
+            assert_eq!(genes(island.population()), genes(&population));
+        }
+    }
+}
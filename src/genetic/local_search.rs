@@ -0,0 +1,154 @@
+use crate::csr::CsrGraph;
+
+use super::{Chromosome, Problem};
+
+/// Pruning local search: lowers labels that are not needed for feasibility.
+///
+/// Vertices are visited in decreasing order of the cost of their label (see
+/// [`CsrGraph::cost`]; without vertex costs, of their label) and, within a cost, in
+/// increasing order of degree. Each label is lowered to the smallest cheaper label that
+/// keeps the problem satisfied, e.g. a `2` to `0` if possible and otherwise to `1`. A
+/// label is only kept lowered if the vertex and its neighbours stay satisfied, which the
+/// neighbour counts of the chromosome answer in `O(deg)`.
+///
+/// When lowering a label only tightens the conditions of the other vertices, a label that
+/// cannot be lowered now never can later and a single pass reaches a local minimum. This
+/// does not hold for independent and perfect Roman domination, where lowering a label can
+/// also relax the conditions of its neighbours; there the pass stays feasible but may stop
+/// short of a local minimum.
+///
+/// # Parameters
+/// - `chromosome: &mut Chromosome`: A feasible chromosome; it stays feasible.
+/// - `problem: &dyn Problem`: The problem the chromosome satisfies.
+/// - `graph: &CsrGraph`: The graph the chromosome refers to.
+pub fn prune(chromosome: &mut Chromosome, problem: &dyn Problem, graph: &CsrGraph) {
+    chromosome.track_neighbors(graph);
+
+    let mut order: Vec<u32> = graph
+        .vertices()
+        .filter(|&v| chromosome.genes()[v as usize] > 0)
+        .collect();
+    order.sort_by_key(|&v| {
+        (
+            std::cmp::Reverse(graph.cost(v, chromosome.genes()[v as usize])),
+            graph.degree(v),
+        )
+    });
+
+    for v in order {
+        let current = chromosome.genes()[v as usize];
+        let cost = graph.cost(v, current);
+        for label in (0..current).filter(|&label| graph.cost(v, label) < cost) {
+            // Tenta o rótulo menor e desfaz se algum vértice ficar insatisfeito.
+            chromosome.set_label(v, label, graph);
+            if is_satisfied_around(chromosome, v, problem, graph) {
+                break;
+            }
+            chromosome.set_label(v, current, graph);
+        }
+    }
+}
+
+/// Returns `true` if `vertex` and all its neighbours are satisfied.
+fn is_satisfied_around(
+    chromosome: &Chromosome,
+    vertex: u32,
+    problem: &dyn Problem,
+    graph: &CsrGraph,
+) -> bool {
+    problem.is_satisfied(chromosome, vertex)
+        && graph
+            .neighbors(vertex)
+            .iter()
+            .all(|&n| problem.is_satisfied(chromosome, n))
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::*;
+    use crate::{generators::GraphFamily, genetic::ProblemVariant};
+
+    /// Random graphs without isolated vertices, so that every variant is feasible.
+    fn graphs(rng: &mut StdRng) -> Vec<CsrGraph> {
+        (0..40)
+            .map(|_| {
+                let family = GraphFamily::Gnp {
+                    order: rng.gen_range(2..30),
+                    probability: rng.gen_range(0.2..0.5),
+                };
+                family.generate(rng).unwrap()
+            })
+            .collect()
+    }
+
+    /// Feasible chromosomes of `problem` built by its heuristics and by repairing random
+    /// labellings.
+    fn feasible_chromosomes(
+        problem: &dyn Problem,
+        graph: &CsrGraph,
+        rng: &mut StdRng,
+    ) -> Vec<Chromosome> {
+        let mut chromosomes: Vec<Chromosome> = problem
+            .heuristics()
+            .iter()
+            .map(|heuristic| {
+                let mut chromosome = heuristic.generate(graph, Some(&mut *rng));
+                problem.prepare(&mut chromosome, graph);
+                chromosome
+            })
+            .collect();
+        for _ in 0..5 {
+            let genes = graph
+                .vertices()
+                .map(|_| rng.gen_range(0..=problem.max_label()))
+                .collect();
+            let mut chromosome = Chromosome::new(genes, graph);
+            problem.repair(&mut chromosome, graph);
+            chromosomes.push(chromosome);
+        }
+        assert!(chromosomes
+            .iter()
+            .all(|chromosome| problem.is_feasible(chromosome, graph)));
+        chromosomes
+    }
+
+    #[test]
+    fn prune_keeps_every_variant_feasible() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for graph in graphs(&mut rng) {
+            for variant in ProblemVariant::ALL {
+                let problem = variant.build();
+                for mut chromosome in feasible_chromosomes(&*problem, &graph, &mut rng) {
+                    let before = chromosome.fitness();
+                    prune(&mut chromosome, &*problem, &graph);
+                    assert!(problem.is_feasible(&chromosome, &graph), "{variant}");
+                    assert!(chromosome.fitness() <= before);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn prune_reaches_a_local_minimum_for_total_roman_domination() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let problem = ProblemVariant::TotalRoman.build();
+        for graph in graphs(&mut rng) {
+            for mut chromosome in feasible_chromosomes(&*problem, &graph, &mut rng) {
+                prune(&mut chromosome, &*problem, &graph);
+
+                for v in graph.vertices() {
+                    for label in 0..chromosome.genes()[v as usize] {
+                        let mut lowered = chromosome.clone();
+                        lowered.set_label(v, label, &graph);
+                        assert!(
+                            !problem.is_feasible(&lowered, &graph),
+                            "Vertex {v} can still be lowered to {label}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,20 +1,58 @@
 /// Chromosome-related functionalities.
 pub mod chromosome;
 
+/// Roman domination problems: feasibility rules and repair
+pub mod problem;
+
 /// Crossover
 pub mod crossover;
 
 /// Heuristics to generate initial population
 pub mod heuristics;
 
+mod degree_buckets;
+
+/// Named registry of heuristics and weighted initial-population mixes
+pub mod registry;
+
+/// Local search improving feasible chromosomes
+pub mod local_search;
+
+/// Mutation operator
+pub mod mutation;
+
+/// Variation operator combining crossover and mutation with adaptive rates
+pub mod variation;
+
 ///Selection strategy
 pub mod selection;
 
 ///Population
 pub mod population;
 
+/// Island model with migration between populations
+pub mod island;
+
 pub use chromosome::Chromosome;
-pub use crossover::{Crossover, SinglePoint};
-pub use heuristics::{h1, h2, h3, h4, h5, Heuristic};
+pub use crossover::{
+    BfsBall, Crossover, CrossoverOperator, KPoint, RegionPartition, SinglePoint, TwoPoint, Uniform,
+};
+pub use heuristics::{
+    grasp, h1, h2, h3, h4, h5, ih1, ih2, ih3, ih4, ir1, pr1, wh, FnHeuristic, Grasp, Heuristic, H1,
+    H2, H3, H4, H5, IH1, IH2, IH3, IH4, IR1, PR1, WH,
+};
+pub use island::{Island, IslandModel, Topology};
+pub use local_search::prune;
+pub use mutation::Mutation;
 pub use population::Population;
-pub use selection::{KTournament, Selection};
+pub use problem::{
+    DoubleRomanDomination, IndependentRomanDomination, ItalianDomination, PerfectRomanDomination,
+    Problem, ProblemVariant, RomanDomination, TotalDoubleRomanDomination, TotalItalianDomination,
+    TotalRomanDomination,
+};
+pub use registry::{HeuristicMix, HeuristicRegistry};
+pub use selection::{
+    DistinctTournament, KTournament, LinearRank, Roulette, Selection, SelectionOperator,
+    StochasticUniversal,
+};
+pub use variation::Variation;
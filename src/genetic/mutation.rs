@@ -0,0 +1,152 @@
+use rand::prelude::*;
+
+use crate::csr::CsrGraph;
+
+use super::Chromosome;
+
+/// Mutation that relabels random genes.
+///
+/// Every gene is replaced, with probability `rate`, by one of the other labels of the
+/// problem chosen uniformly. Mutated positions are drawn with geometric gaps, so a
+/// mutation costs time proportional to the number of mutated genes rather than to the
+/// number of vertices.
+/// The chromosome is not repaired; see [`Problem::repair`](super::Problem::repair).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Mutation {
+    rate: f64,
+}
+
+impl Mutation {
+    /// Creates a new instance with the given per-gene mutation rate.
+    ///
+    /// # Panics
+    /// Panics if `rate` is outside the range `[0.0, 1.0]`.
+    #[inline]
+    #[must_use]
+    pub fn new(rate: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&rate),
+            "Mutation rate must be between 0 and 1"
+        );
+        Self { rate }
+    }
+
+    /// Returns the per-gene mutation rate.
+    #[inline]
+    #[must_use]
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// Mutates `chromosome` in place, drawing new labels from `0` to `max_label`.
+    ///
+    /// # Returns
+    /// - `true` if at least one gene was relabelled.
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    pub fn mutate(
+        &self,
+        chromosome: &mut Chromosome,
+        max_label: u8,
+        graph: &CsrGraph,
+        rng: &mut dyn RngCore,
+    ) -> bool {
+        let len = chromosome.genes().len();
+        if self.rate <= 0.0 || len == 0 {
+            return false;
+        }
+
+        let mut mutated = false;
+        let mut position = 0;
+        loop {
+            // Salto geométrico até o próximo gene mutado.
+            if self.rate < 1.0 {
+                let u: f64 = rng.gen_range(f64::EPSILON..1.0);
+                let gap = (u.ln() / (1.0 - self.rate).ln()).floor();
+                if gap >= (len - position) as f64 {
+                    break;
+                }
+                position += gap as usize;
+            }
+            if position >= len {
+                break;
+            }
+
+            let vertex = position as u32;
+            let label =
+                (chromosome.genes()[position] + rng.gen_range(1..=max_label)) % (max_label + 1);
+            chromosome.set_label(vertex, label, graph);
+            mutated = true;
+            position += 1;
+        }
+
+        mutated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mutated_genes(before: &Chromosome, after: &Chromosome) -> usize {
+        before
+            .genes()
+            .iter()
+            .zip(after.genes())
+            .filter(|(a, b)| a != b)
+            .count()
+    }
+
+    #[test]
+    fn rate_zero_leaves_the_chromosome_untouched() {
+        let graph = CsrGraph::circulant(20, &[1]);
+        let mut rng = StdRng::seed_from_u64(39);
+        let original = Chromosome::new((0..20).map(|v| v % 3).collect(), &graph);
+
+        let mut chromosome = original.clone();
+        assert!(!Mutation::new(0.0).mutate(&mut chromosome, 2, &graph, &mut rng));
+        assert_eq!(chromosome.genes(), original.genes());
+    }
+
+    #[test]
+    fn rate_one_relabels_every_gene_within_range() {
+        let graph = CsrGraph::circulant(20, &[1]);
+        let mut rng = StdRng::seed_from_u64(39);
+        for max_label in [1u8, 2, 3] {
+            let original = Chromosome::new((0..20).map(|v| v % (max_label + 1)).collect(), &graph);
+            let mut chromosome = original.clone();
+            assert!(Mutation::new(1.0).mutate(&mut chromosome, max_label, &graph, &mut rng));
+            assert_eq!(mutated_genes(&original, &chromosome), 20);
+            assert!(chromosome.genes().iter().all(|&g| g <= max_label));
+        }
+    }
+
+    #[test]
+    #[allow(clippy::cast_precision_loss)]
+    fn mean_number_of_mutated_genes_matches_the_rate() {
+        let graph = CsrGraph::from_edges(1000, &[]);
+        let mut rng = StdRng::seed_from_u64(39);
+        let original = Chromosome::new(vec![0; 1000], &graph);
+
+        for rate in [0.01, 0.1, 0.5] {
+            let mutation = Mutation::new(rate);
+            let trials = 200;
+            let total: usize = (0..trials)
+                .map(|_| {
+                    let mut chromosome = original.clone();
+                    mutation.mutate(&mut chromosome, 3, &graph, &mut rng);
+                    mutated_genes(&original, &chromosome)
+                })
+                .sum();
+            let mean = total as f64 / f64::from(trials);
+            let expected = rate * 1000.0;
+            assert!(
+                (mean - expected).abs() < 0.05 * expected,
+                "rate {rate}: mean {mean}, expected {expected}"
+            );
+        }
+    }
+}
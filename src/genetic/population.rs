@@ -1,6 +1,15 @@
-use kambo_graph::graphs::simple::UndirectedGraph;
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+    sync::Arc,
+};
 
-use super::{Chromosome, Crossover, Heuristic, Selection};
+use rand::{rngs::StdRng, thread_rng, Rng, RngCore, SeedableRng};
+use rayon::prelude::*;
+
+use crate::csr::CsrGraph;
+
+use super::{Chromosome, Crossover, Heuristic, HeuristicMix, Problem, Selection};
 
 /// Represents a population of chromosomes for evolutionary algorithms.
 ///
@@ -22,51 +31,124 @@ impl Population {
     ///
     /// This function generates chromosomes by applying the heuristics in sequence. If the
     /// population size exceeds the number of heuristics, the last heuristic is used to
-    /// generate the remaining chromosomes.
+    /// generate the remaining chromosomes. Every chromosome is prepared with [`Problem::prepare`].
     ///
     /// # Parameters
     /// - `size: usize`: The number of chromosomes to generate for the population.
-    /// - `heuristics: Vec<Heuristic>`:
-    ///   A vector of heuristic functions used to generate chromosomes.
-    ///   Each heuristic is a function of the form `fn(&UnGraph<u32, ()>) -> Chromosome`.
-    /// - `graph: &UnGraph<u32, ()>`:
+    /// - `heuristics: &[Arc<dyn Heuristic>]`:
+    ///   The heuristics used to generate chromosomes, in order.
+    /// - `problem: &dyn Problem`: The problem the chromosomes must satisfy.
+    /// - `graph: &CsrGraph`:
     ///   An undirected graph that represents the problem structure.
+    /// - `rng: &mut dyn RngCore`: The random number generator passed to the heuristics.
     ///
     /// # Panics
-    /// - If the `heuristics` vector is empty.
+    /// - If the `heuristics` slice is empty.
     ///   - Panic message: `"At least one heuristic must be provided."`
     ///
     /// # Returns
     /// - A new instance of `Population` with chromosomes generated by the heuristics.
-    ///
-    /// # Notes
-    /// - Chromosomes are adjusted using their `fix` method to ensure they satisfy
-    ///   problem-specific constraints.
-    #[inline]
     #[must_use]
-    pub fn new(size: usize, heuristics: &[Heuristic], graph: &UndirectedGraph<u32>) -> Self {
+    pub fn new(
+        size: usize,
+        heuristics: &[Arc<dyn Heuristic>],
+        problem: &dyn Problem,
+        graph: &CsrGraph,
+        rng: &mut dyn RngCore,
+    ) -> Self {
         assert!(
             !heuristics.is_empty(),
             "At least one heuristic must be provided."
         );
         let mut chromosomes = Vec::with_capacity(size);
 
-        for heuristic in heuristics {
-            if chromosomes.len() < size {
-                let chromosome = heuristic(graph);
-                chromosomes.push(chromosome);
-            }
+        for heuristic in heuristics.iter().take(size) {
+            let mut chromosome = heuristic.generate(graph, Some(&mut *rng));
+            problem.prepare(&mut chromosome, graph);
+            chromosomes.push(chromosome);
         }
 
-        let last_heuristic = *heuristics.last().unwrap();
-        while chromosomes.len() < size {
-            let chromosome = last_heuristic(graph);
-            chromosomes.push(chromosome);
+        let last_heuristic = heuristics.last().unwrap();
+        let remaining = size - chromosomes.len();
+        Self::generate_into(
+            &mut chromosomes,
+            last_heuristic.as_ref(),
+            remaining,
+            problem,
+            graph,
+            rng,
+        );
+
+        Self { chromosomes, size }
+    }
+
+    /// Creates a new population whose chromosomes are shared among the heuristics of `mix`
+    /// according to their weights. Every chromosome is prepared with [`Problem::prepare`].
+    ///
+    /// # Parameters
+    /// - `size: usize`: The number of chromosomes to generate for the population.
+    /// - `mix: &HeuristicMix`: The heuristics and their weights; see [`HeuristicMix::counts`].
+    /// - `problem: &dyn Problem`: The problem the chromosomes must satisfy.
+    /// - `graph: &CsrGraph`: An undirected graph that represents the problem structure.
+    /// - `rng: &mut dyn RngCore`: The random number generator passed to the heuristics.
+    #[must_use]
+    pub fn from_mix(
+        size: usize,
+        mix: &HeuristicMix,
+        problem: &dyn Problem,
+        graph: &CsrGraph,
+        rng: &mut dyn RngCore,
+    ) -> Self {
+        let mut chromosomes = Vec::with_capacity(size);
+
+        for ((heuristic, _), count) in mix.entries().iter().zip(mix.counts(size)) {
+            Self::generate_into(
+                &mut chromosomes,
+                heuristic.as_ref(),
+                count,
+                problem,
+                graph,
+                rng,
+            );
         }
 
         Self { chromosomes, size }
     }
 
+    /// Creates a population holding exactly `chromosomes`.
+    #[cfg(test)]
+    pub(crate) fn from_chromosomes(chromosomes: Vec<Chromosome>) -> Self {
+        let size = chromosomes.len();
+        Self { chromosomes, size }
+    }
+
+    /// Appends `count` chromosomes built by `heuristic` and prepared with `problem`.
+    /// Deterministic heuristics run once and their chromosome is cloned.
+    fn generate_into(
+        chromosomes: &mut Vec<Chromosome>,
+        heuristic: &dyn Heuristic,
+        count: usize,
+        problem: &dyn Problem,
+        graph: &CsrGraph,
+        rng: &mut dyn RngCore,
+    ) {
+        if count == 0 {
+            return;
+        }
+
+        if heuristic.is_randomized() {
+            chromosomes.extend((0..count).map(|_| {
+                let mut chromosome = heuristic.generate(graph, Some(&mut *rng));
+                problem.prepare(&mut chromosome, graph);
+                chromosome
+            }));
+        } else {
+            let mut chromosome = heuristic.generate(graph, None);
+            problem.prepare(&mut chromosome, graph);
+            chromosomes.resize(chromosomes.len() + count, chromosome);
+        }
+    }
+
     /// Returns the size of the population.
     ///
     /// # Returns
@@ -89,44 +171,108 @@ impl Population {
 
     /// Evolves the population by applying selection and crossover operations.
     ///
-    /// The method iteratively selects parent chromosomes using the provided selection strategy,
-    /// applies the crossover operator to generate offspring, and replaces the population with
-    /// the newly generated chromosomes.
+    /// The method selects pairs of parent chromosomes using the provided selection strategy,
+    /// applies the crossover operator to every pair in parallel to generate offspring,
+    /// repairs the offspring and replaces the population with them.
+    ///
+    /// Each worker thread uses its own thread-local random number generator, so the result
+    /// depends on scheduling. Use [`Population::envolve_seeded`] for reproducible runs.
     ///
     /// # Parameters
     /// - `selector: &S`: A reference to a selection strategy that implements the `Selection` trait.
     ///   The selector is used to choose parent chromosomes from the current population.
     /// - `crossover: &C`: A reference to a crossover strategy that implements the `Crossover` trait.
     ///   The crossover operator generates offspring chromosomes from selected parent chromosomes.
-    /// - `graph: &UnGraph<u32, ()>`: A reference to the underlying graph structure, used to validate
+    /// - `problem: &dyn Problem`: The problem repairing the offspring.
+    /// - `graph: &CsrGraph`: A reference to the underlying graph structure, used to validate
     ///   or influence the crossover operation.
     ///
     /// # Behavior
-    /// 1. A new vector of chromosomes is pre-allocated with the same size as the current population.
-    /// 2. While the number of new chromosomes is less than the population size:
-    ///    - Two parent chromosomes are selected using the provided `selector`.
-    ///    - The `crossover` operator is applied to generate two offspring chromosomes.
-    ///    - The offspring chromosomes are added to the new population.
-    /// 3. Once the new population is complete, the chromosomes are added back to the population.
+    /// 1. Parent chromosomes are selected with [`Selection::select_many`] and paired up in
+    ///    order.
+    /// 2. The `crossover` operator is applied to every pair in parallel, producing two
+    ///    offspring chromosomes per pair, which are repaired with `problem`.
+    /// 3. Once all offspring are generated, they replace the current population.
     #[inline]
-    pub fn envolve<S: Selection, C: Crossover>(
+    pub fn envolve<S, C>(
         &mut self,
         selector: &S,
         crossover: &C,
-        graph: &UndirectedGraph<u32>,
-    ) {
-        let mut new_chromosomes: Vec<Chromosome> = Vec::with_capacity(self.size);
+        problem: &dyn Problem,
+        graph: &CsrGraph,
+    ) where
+        S: Selection + Sync,
+        C: Crossover + Sync,
+    {
+        self.chromosomes = self.offspring(selector, crossover, problem, graph, None);
+    }
 
-        while new_chromosomes.len() < self.size() {
-            let parent1 = selector.select(self);
-            let parent2 = selector.select(self);
+    /// Evolves the population like [`Population::envolve`], deterministically.
+    ///
+    /// Parents are selected with a generator seeded from `seed`, and the crossover of the
+    /// `i`-th pair uses its own generator derived from `seed` and `i`. The resulting
+    /// population therefore depends only on `seed`, not on the number of threads.
+    #[inline]
+    pub fn envolve_seeded<S, C>(
+        &mut self,
+        selector: &S,
+        crossover: &C,
+        problem: &dyn Problem,
+        graph: &CsrGraph,
+        seed: u64,
+    ) where
+        S: Selection + Sync,
+        C: Crossover + Sync,
+    {
+        self.chromosomes = self.offspring(selector, crossover, problem, graph, Some(seed));
+    }
 
-            let (child1, child2) = crossover.crossover(parent1, parent2, graph);
-            new_chromosomes.push(child1);
-            new_chromosomes.push(child2);
-        }
+    fn offspring<S, C>(
+        &self,
+        selector: &S,
+        crossover: &C,
+        problem: &dyn Problem,
+        graph: &CsrGraph,
+        seed: Option<u64>,
+    ) -> Vec<Chromosome>
+    where
+        S: Selection + Sync,
+        C: Crossover + Sync,
+    {
+        let pairs = self.size.div_ceil(2);
+        let mut selection_rng: Box<dyn RngCore> = match seed {
+            Some(seed) => Box::new(StdRng::seed_from_u64(seed)),
+            None => Box::new(thread_rng()),
+        };
+        let pair_seed = selection_rng.gen::<u64>();
 
-        self.chromosomes = new_chromosomes;
+        let selected = selector.select_many(self, 2 * pairs, &mut *selection_rng);
+        let parents: Vec<(&Chromosome, &Chromosome)> = selected
+            .chunks_exact(2)
+            .map(|pair| (pair[0], pair[1]))
+            .collect();
+
+        let children: Vec<(Chromosome, Chromosome)> = parents
+            .into_par_iter()
+            .enumerate()
+            .map(|(idx, (parent1, parent2))| {
+                let (mut child1, mut child2) = match seed {
+                    Some(_) => {
+                        let mut rng = StdRng::seed_from_u64(pair_seed.wrapping_add(idx as u64));
+                        crossover.crossover(parent1, parent2, graph, &mut rng)
+                    }
+                    None => crossover.crossover(parent1, parent2, graph, &mut thread_rng()),
+                };
+                problem.repair(&mut child1, graph);
+                problem.repair(&mut child2, graph);
+                (child1, child2)
+            })
+            .collect();
+
+        children
+            .into_iter()
+            .flat_map(|(child1, child2)| [child1, child2])
+            .collect()
     }
 
     /// Returns a reference to the chromosome with the best fitness (lowest value).
@@ -136,4 +282,237 @@ impl Population {
             .iter()
             .min_by_key(|chromosome| chromosome.fitness())
     }
+
+    /// Returns copies of the `count` best chromosomes, best first.
+    ///
+    /// If `count` exceeds the number of chromosomes, the whole population is returned.
+    #[must_use]
+    pub fn best_chromosomes(&self, count: usize) -> Vec<Chromosome> {
+        let mut ranked: Vec<&Chromosome> = self.chromosomes.iter().collect();
+        ranked.sort_by_key(|chromosome| chromosome.fitness());
+        ranked.into_iter().take(count).cloned().collect()
+    }
+
+    /// Replaces the worst chromosomes of the population with the given ones.
+    ///
+    /// Each incoming chromosome takes the place of one of the worst (highest fitness)
+    /// members. Incoming chromosomes beyond the population size are ignored.
+    pub fn replace_worst(&mut self, incoming: Vec<Chromosome>) {
+        let mut ranked: Vec<usize> = (0..self.chromosomes.len()).collect();
+        ranked.sort_by_key(|&idx| std::cmp::Reverse(self.chromosomes[idx].fitness()));
+
+        for (idx, chromosome) in ranked.into_iter().zip(incoming) {
+            self.chromosomes[idx] = chromosome;
+        }
+    }
+
+    /// Returns the mean normalized Hamming distance between pairs of chromosomes.
+    ///
+    /// The value is in `[0, 1]`: `0` means every chromosome is identical and `1` means
+    /// every pair differs at every gene. It is computed from the number of chromosomes
+    /// with each label at each position, in `O(size * n)` instead of comparing all pairs.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn diversity(&self) -> f64 {
+        let count = self.chromosomes.len();
+        let len = self.chromosomes.first().map_or(0, |c| c.genes().len());
+        if count < 2 || len == 0 {
+            return 0.0;
+        }
+
+        let mut label_counts = vec![[0u64; Chromosome::MAX_LABEL as usize + 1]; len];
+        for chromosome in &self.chromosomes {
+            for (counts, &gene) in label_counts.iter_mut().zip(chromosome.genes()) {
+                counts[usize::from(gene)] += 1;
+            }
+        }
+
+        // Em cada posição, o número de pares com rótulos diferentes é (N² - Σ c²) / 2.
+        let count = count as u64;
+        let differing: u64 = label_counts
+            .iter()
+            .map(|counts| (count * count - counts.iter().map(|c| c * c).sum::<u64>()) / 2)
+            .sum();
+        let pairs = count * (count - 1) / 2;
+
+        differing as f64 / (pairs as f64 * len as f64)
+    }
+
+    /// Returns the indices of chromosomes whose genes equal those of an earlier chromosome.
+    ///
+    /// Genotypes are compared by hash, and chromosomes with equal hashes are compared gene
+    /// by gene.
+    #[must_use]
+    pub fn duplicates(&self) -> Vec<usize> {
+        let mut seen: HashMap<u64, Vec<usize>> = HashMap::with_capacity(self.chromosomes.len());
+        let mut duplicates = Vec::new();
+
+        for (idx, chromosome) in self.chromosomes.iter().enumerate() {
+            let mut hasher = DefaultHasher::new();
+            chromosome.genes().hash(&mut hasher);
+
+            let same_hash = seen.entry(hasher.finish()).or_default();
+            if same_hash
+                .iter()
+                .any(|&other| self.chromosomes[other].genes() == chromosome.genes())
+            {
+                duplicates.push(idx);
+            } else {
+                same_hash.push(idx);
+            }
+        }
+
+        duplicates
+    }
+
+    /// Replaces every duplicate genotype (see [`Population::duplicates`]) with a
+    /// chromosome built by `replacement`, and returns the number of replaced chromosomes.
+    pub fn replace_duplicates<F>(&mut self, mut replacement: F) -> usize
+    where
+        F: FnMut() -> Chromosome,
+    {
+        let duplicates = self.duplicates();
+        for &idx in &duplicates {
+            self.chromosomes[idx] = replacement();
+        }
+        duplicates.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rayon::ThreadPoolBuilder;
+
+    use super::*;
+    use crate::genetic::{KTournament, SinglePoint, TotalRomanDomination, H1};
+
+    /// Genes of a population evolved for ten seeded generations.
+    fn evolve(population: &Population, graph: &CsrGraph, seed: u64) -> Vec<Vec<u8>> {
+        let mut population = population.clone();
+        for generation in 0..10 {
+            population.envolve_seeded(
+                &KTournament::new(3),
+                &SinglePoint::new(0.9),
+                &TotalRomanDomination,
+                graph,
+                seed + generation,
+            );
+        }
+        population
+            .chromosomes()
+            .iter()
+            .map(|chromosome| chromosome.genes().to_vec())
+            .collect()
+    }
+
+    fn initial_population(graph: &CsrGraph) -> Population {
+        let heuristics: Vec<Arc<dyn Heuristic>> = vec![Arc::new(H1)];
+        let mut rng = StdRng::seed_from_u64(0);
+        Population::new(40, &heuristics, &TotalRomanDomination, graph, &mut rng)
+    }
+
+    #[test]
+    fn replace_worst_replaces_the_highest_fitness() {
+        let graph = CsrGraph::from_edges(10, &[]);
+        let chromosome = |fitness: usize| {
+            let genes = (0..10).map(|v| u8::from(v < fitness)).collect();
+            Chromosome::new(genes, &graph)
+        };
+        let fitness = |population: &Population| {
+            let mut fitness: Vec<usize> = population
+                .chromosomes()
+                .iter()
+                .map(Chromosome::fitness)
+                .collect();
+            fitness.sort_unstable();
+            fitness
+        };
+
+        let mut population = Population::from_chromosomes([5, 1, 9, 3, 7].map(chromosome).to_vec());
+        population.replace_worst(vec![chromosome(0), chromosome(2)]);
+        assert_eq!(fitness(&population), [0, 1, 2, 3, 5]);
+
+        // Os excedentes são ignorados.
+        population.replace_worst((0..8).map(|_| chromosome(4)).collect());
+        assert_eq!(fitness(&population), [4; 5]);
+    }
+
+    #[test]
+    #[allow(clippy::cast_precision_loss)]
+    fn diversity_is_the_mean_pairwise_hamming_distance() {
+        let graph = CsrGraph::from_edges(30, &[]);
+        let mut rng = StdRng::seed_from_u64(0);
+        for size in [2usize, 3, 10, 25] {
+            let chromosomes: Vec<Chromosome> = (0..size)
+                .map(|_| {
+                    let genes = (0..30)
+                        .map(|_| rng.gen_range(0..=Chromosome::MAX_LABEL))
+                        .collect();
+                    Chromosome::new(genes, &graph)
+                })
+                .collect();
+
+            let mut distance = 0;
+            for (idx, a) in chromosomes.iter().enumerate() {
+                for b in &chromosomes[idx + 1..] {
+                    distance += a
+                        .genes()
+                        .iter()
+                        .zip(b.genes())
+                        .filter(|(x, y)| x != y)
+                        .count();
+                }
+            }
+            let naive = distance as f64 / (size * (size - 1) / 2 * 30) as f64;
+
+            let diversity = Population::from_chromosomes(chromosomes).diversity();
+            assert!((diversity - naive).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn identical_chromosomes_have_no_diversity_and_are_duplicates() {
+        let graph = CsrGraph::from_edges(6, &[]);
+        let chromosome = Chromosome::new(vec![0, 1, 2, 0, 1, 2], &graph);
+        let population = Population::from_chromosomes(vec![chromosome; 5]);
+        assert!(population.diversity().abs() < f64::EPSILON);
+        assert_eq!(population.duplicates(), [1, 2, 3, 4]);
+
+        let other = Chromosome::new(vec![2, 1, 0, 0, 1, 2], &graph);
+        let mut chromosomes = population.chromosomes().to_vec();
+        chromosomes[2] = other.clone();
+        chromosomes.push(other);
+        assert_eq!(
+            Population::from_chromosomes(chromosomes).duplicates(),
+            [1, 3, 4, 5]
+        );
+    }
+
+    #[test]
+    fn seeded_evolution_repeats() {
+        let graph = CsrGraph::circulant(60, &[1, 7]);
+        let population = initial_population(&graph);
+        assert_eq!(
+            evolve(&population, &graph, 7),
+            evolve(&population, &graph, 7)
+        );
+        assert_ne!(
+            evolve(&population, &graph, 7),
+            evolve(&population, &graph, 8)
+        );
+    }
+
+    #[test]
+    fn seeded_evolution_does_not_depend_on_the_thread_count() {
+        let graph = CsrGraph::circulant(60, &[1, 7]);
+        let population = initial_population(&graph);
+        let [one, four] = [1, 4].map(|threads| {
+            let pool = ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap();
+            pool.install(|| evolve(&population, &graph, 7))
+        });
+        assert_eq!(one, four);
+    }
 }
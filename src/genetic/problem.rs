@@ -0,0 +1,1048 @@
+use std::{fmt, str::FromStr, sync::Arc};
+
+use crate::csr::CsrGraph;
+
+use super::{Chromosome, Heuristic, H1, H2, H3, H4, H5, IH1, IH2, IH3, IH4, IR1, PR1};
+
+/// A Roman domination problem: which labellings are feasible and how to repair the
+/// others.
+///
+/// Feasibility is checked vertex by vertex from the neighbour counts of the chromosome
+/// (see [`Chromosome::track_neighbors`]), so checking or repairing a vertex costs
+/// `O(deg)`. Every variant minimises the weight of the labelling,
+/// [`Chromosome::fitness`].
+pub trait Problem: Send + Sync {
+    /// Returns the name of the problem, as accepted by [`ProblemVariant::from_str`].
+    fn name(&self) -> &str;
+
+    /// Returns the largest label of the problem.
+    fn max_label(&self) -> u8 {
+        2
+    }
+
+    /// Returns `true` if `vertex` satisfies the conditions of the problem under its
+    /// current label.
+    ///
+    /// # Panics
+    /// Panics if the neighbour counts of `chromosome` have not been built.
+    fn is_satisfied(&self, chromosome: &Chromosome, vertex: u32) -> bool;
+
+    /// Returns the relabelling `(vertex, label)` that repairs `vertex`, or `None` if
+    /// `vertex` is satisfied or cannot be repaired. Relabellings only raise labels and
+    /// never leave a satisfied vertex unsatisfied.
+    ///
+    /// # Panics
+    /// - If the neighbour counts of `chromosome` have not been built.
+    /// - If `vertex` has a label greater than [`Problem::max_label`].
+    fn repair_move(
+        &self,
+        chromosome: &Chromosome,
+        vertex: u32,
+        graph: &CsrGraph,
+    ) -> Option<(u32, u8)>;
+
+    /// Returns `true` if every label is at most [`Problem::max_label`] and every vertex of
+    /// `chromosome` satisfies the problem.
+    fn is_feasible(&self, chromosome: &Chromosome, graph: &CsrGraph) -> bool {
+        if chromosome
+            .genes()
+            .iter()
+            .any(|&label| label > self.max_label())
+        {
+            return false;
+        }
+
+        let mut chromosome = chromosome.clone();
+        chromosome.track_neighbors(graph);
+        graph.vertices().all(|v| self.is_satisfied(&chromosome, v))
+    }
+
+    /// Repairs `chromosome` in place so that every vertex satisfies the problem.
+    ///
+    /// Every vertex is checked once; when a vertex is relabelled by
+    /// [`Problem::repair_move`], it is checked again, since its own condition may now be
+    /// violated. Relabellings never leave a satisfied vertex unsatisfied, so the vertices
+    /// already checked stay satisfied.
+    ///
+    /// Problems in which raising a label can break the conditions of other vertices
+    /// override this to lower labels first.
+    fn repair(&self, chromosome: &mut Chromosome, graph: &CsrGraph) {
+        apply_repair_moves(self, chromosome, graph);
+    }
+
+    /// Turns a chromosome built by a [`Heuristic`](super::Heuristic), whose labels are
+    /// `0`, `1` and `2`, into a feasible chromosome of the problem.
+    ///
+    /// The default repairs it unchanged.
+    fn prepare(&self, chromosome: &mut Chromosome, graph: &CsrGraph) {
+        self.repair(chromosome, graph);
+    }
+
+    /// Returns the heuristics building the initial population when no mix is configured,
+    /// in order; the first one also builds immigrants.
+    ///
+    /// The default is `h1` to `h5` followed by `h1` again, which then builds the rest of
+    /// the population.
+    fn heuristics(&self) -> Vec<Arc<dyn Heuristic>> {
+        vec![
+            Arc::new(H1),
+            Arc::new(H2),
+            Arc::new(H3),
+            Arc::new(H4),
+            Arc::new(H5),
+            Arc::new(H1),
+        ]
+    }
+}
+
+impl<P: Problem + ?Sized> Problem for Box<P> {
+    fn name(&self) -> &str {
+        (**self).name()
+    }
+
+    fn max_label(&self) -> u8 {
+        (**self).max_label()
+    }
+
+    fn is_satisfied(&self, chromosome: &Chromosome, vertex: u32) -> bool {
+        (**self).is_satisfied(chromosome, vertex)
+    }
+
+    fn repair_move(
+        &self,
+        chromosome: &Chromosome,
+        vertex: u32,
+        graph: &CsrGraph,
+    ) -> Option<(u32, u8)> {
+        (**self).repair_move(chromosome, vertex, graph)
+    }
+
+    fn is_feasible(&self, chromosome: &Chromosome, graph: &CsrGraph) -> bool {
+        (**self).is_feasible(chromosome, graph)
+    }
+
+    fn repair(&self, chromosome: &mut Chromosome, graph: &CsrGraph) {
+        (**self).repair(chromosome, graph);
+    }
+
+    fn prepare(&self, chromosome: &mut Chromosome, graph: &CsrGraph) {
+        (**self).prepare(chromosome, graph);
+    }
+
+    fn heuristics(&self) -> Vec<Arc<dyn Heuristic>> {
+        (**self).heuristics()
+    }
+}
+
+/// Problems that can be chosen at run time.
+///
+/// Parsed from and formatted as `total-roman`, `roman`, `double-roman`,
+/// `total-double-roman`, `italian`, `total-italian`, `independent-roman` and
+/// `perfect-roman`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ProblemVariant {
+    /// [`TotalRomanDomination`].
+    #[default]
+    TotalRoman,
+    /// [`RomanDomination`].
+    Roman,
+    /// [`DoubleRomanDomination`].
+    DoubleRoman,
+    /// [`TotalDoubleRomanDomination`].
+    TotalDoubleRoman,
+    /// [`ItalianDomination`].
+    Italian,
+    /// [`TotalItalianDomination`].
+    TotalItalian,
+    /// [`IndependentRomanDomination`].
+    IndependentRoman,
+    /// [`PerfectRomanDomination`].
+    PerfectRoman,
+}
+
+impl ProblemVariant {
+    /// Every variant, in declaration order.
+    #[cfg(test)]
+    pub(crate) const ALL: [Self; 8] = [
+        Self::TotalRoman,
+        Self::Roman,
+        Self::DoubleRoman,
+        Self::TotalDoubleRoman,
+        Self::Italian,
+        Self::TotalItalian,
+        Self::IndependentRoman,
+        Self::PerfectRoman,
+    ];
+
+    /// Builds the problem.
+    #[must_use]
+    pub fn build(self) -> Arc<dyn Problem> {
+        match self {
+            Self::TotalRoman => Arc::new(TotalRomanDomination),
+            Self::Roman => Arc::new(RomanDomination),
+            Self::DoubleRoman => Arc::new(DoubleRomanDomination),
+            Self::TotalDoubleRoman => Arc::new(TotalDoubleRomanDomination),
+            Self::Italian => Arc::new(ItalianDomination),
+            Self::TotalItalian => Arc::new(TotalItalianDomination),
+            Self::IndependentRoman => Arc::new(IndependentRomanDomination),
+            Self::PerfectRoman => Arc::new(PerfectRomanDomination),
+        }
+    }
+}
+
+impl FromStr for ProblemVariant {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "total-roman" => Ok(Self::TotalRoman),
+            "roman" => Ok(Self::Roman),
+            "double-roman" => Ok(Self::DoubleRoman),
+            "total-double-roman" => Ok(Self::TotalDoubleRoman),
+            "italian" => Ok(Self::Italian),
+            "total-italian" => Ok(Self::TotalItalian),
+            "independent-roman" => Ok(Self::IndependentRoman),
+            "perfect-roman" => Ok(Self::PerfectRoman),
+            _ => Err(format!(
+                "Invalid problem: {s} (expected total-roman, roman, double-roman, \
+                 total-double-roman, italian, total-italian, independent-roman or \
+                 perfect-roman)"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ProblemVariant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TotalRoman => write!(f, "total-roman"),
+            Self::Roman => write!(f, "roman"),
+            Self::DoubleRoman => write!(f, "double-roman"),
+            Self::TotalDoubleRoman => write!(f, "total-double-roman"),
+            Self::Italian => write!(f, "italian"),
+            Self::TotalItalian => write!(f, "total-italian"),
+            Self::IndependentRoman => write!(f, "independent-roman"),
+            Self::PerfectRoman => write!(f, "perfect-roman"),
+        }
+    }
+}
+
+/// Total Roman domination (`γ_tR`).
+///
+/// - Vertices labelled `0` must have a neighbour labelled `2`.
+/// - Vertices labelled `1` or `2` must have a neighbour with a label greater than `0`.
+///
+/// A vertex labelled `0` without a `2`-neighbour is repaired by raising its first
+/// `0`-neighbour to `2`, or its first neighbour if all neighbours are positive. A
+/// positive vertex without a positive neighbour gets its first neighbour raised to `1`.
+/// Isolated vertices cannot be repaired, since no labelling is feasible.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TotalRomanDomination;
+
+impl Problem for TotalRomanDomination {
+    fn name(&self) -> &'static str {
+        "total-roman"
+    }
+
+    fn is_satisfied(&self, chromosome: &Chromosome, vertex: u32) -> bool {
+        match chromosome.genes()[vertex as usize] {
+            0 => chromosome.labelled_neighbors(vertex, 2) > 0,
+            _ => chromosome.neighbors_at_least(vertex, 1) > 0,
+        }
+    }
+
+    fn repair_move(
+        &self,
+        chromosome: &Chromosome,
+        vertex: u32,
+        graph: &CsrGraph,
+    ) -> Option<(u32, u8)> {
+        let neighbors = graph.neighbors(vertex);
+
+        match label(chromosome, vertex, self.max_label()) {
+            0 if chromosome.labelled_neighbors(vertex, 2) == 0 => {
+                first_zero_or_any(chromosome, neighbors).map(|n| (n, 2))
+            }
+            // Sem vizinhos positivos, todos os vizinhos têm rótulo 0.
+            1 | 2 if chromosome.neighbors_at_least(vertex, 1) == 0 => {
+                neighbors.first().map(|&n| (n, 1))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Roman domination (`γ_R`).
+///
+/// - Vertices labelled `0` must have a neighbour labelled `2`.
+/// - Vertices labelled `1` or `2` have no condition.
+///
+/// A vertex labelled `0` without a `2`-neighbour is repaired by raising its first
+/// `0`-neighbour to `2`, or its first neighbour if all neighbours are positive. An
+/// isolated vertex labelled `0` is raised to `1`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RomanDomination;
+
+impl Problem for RomanDomination {
+    fn name(&self) -> &'static str {
+        "roman"
+    }
+
+    fn is_satisfied(&self, chromosome: &Chromosome, vertex: u32) -> bool {
+        chromosome.genes()[vertex as usize] > 0 || chromosome.labelled_neighbors(vertex, 2) > 0
+    }
+
+    fn repair_move(
+        &self,
+        chromosome: &Chromosome,
+        vertex: u32,
+        graph: &CsrGraph,
+    ) -> Option<(u32, u8)> {
+        match label(chromosome, vertex, self.max_label()) {
+            0 if chromosome.labelled_neighbors(vertex, 2) == 0 => Some(
+                first_zero_or_any(chromosome, graph.neighbors(vertex))
+                    .map_or((vertex, 1), |n| (n, 2)),
+            ),
+            _ => None,
+        }
+    }
+}
+
+/// Double Roman domination (`γ_dR`), with labels `0` to `3`.
+///
+/// - Vertices labelled `0` must have a neighbour labelled `3` or two neighbours labelled `2`.
+/// - Vertices labelled `1` must have a neighbour labelled `2` or `3`.
+/// - Vertices labelled `2` or `3` have no condition.
+///
+/// A vertex labelled `0` with a single `2`-neighbour gets it raised to `3`; without
+/// neighbours labelled `2` or `3`, its first `0`-neighbour is raised to `3`, or its first
+/// neighbour if all neighbours are positive, and an isolated vertex is raised to `2`. A
+/// vertex labelled `1` without a neighbour labelled `2` or `3` is raised to `2`.
+///
+/// Heuristic chromosomes are prepared by relabelling every `2` as `3` and every `1` as
+/// `2`, which turns a Roman dominating function into a double Roman dominating function.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DoubleRomanDomination;
+
+impl Problem for DoubleRomanDomination {
+    fn name(&self) -> &'static str {
+        "double-roman"
+    }
+
+    fn max_label(&self) -> u8 {
+        3
+    }
+
+    fn is_satisfied(&self, chromosome: &Chromosome, vertex: u32) -> bool {
+        match chromosome.genes()[vertex as usize] {
+            0 => {
+                chromosome.labelled_neighbors(vertex, 3) > 0
+                    || chromosome.labelled_neighbors(vertex, 2) > 1
+            }
+            1 => chromosome.neighbors_at_least(vertex, 2) > 0,
+            _ => true,
+        }
+    }
+
+    fn repair_move(
+        &self,
+        chromosome: &Chromosome,
+        vertex: u32,
+        graph: &CsrGraph,
+    ) -> Option<(u32, u8)> {
+        match label(chromosome, vertex, self.max_label()) {
+            0 if !self.is_satisfied(chromosome, vertex) => {
+                let neighbors = graph.neighbors(vertex);
+                // Um único vizinho 2 passa a 3; sem vizinhos 2 ou 3, um vizinho recebe 3.
+                let target = neighbors
+                    .iter()
+                    .find(|&&n| chromosome.genes()[n as usize] == 2)
+                    .copied()
+                    .or_else(|| first_zero_or_any(chromosome, neighbors));
+                Some(target.map_or((vertex, 2), |n| (n, 3)))
+            }
+            1 if !self.is_satisfied(chromosome, vertex) => Some((vertex, 2)),
+            _ => None,
+        }
+    }
+
+    fn prepare(&self, chromosome: &mut Chromosome, graph: &CsrGraph) {
+        double(chromosome, graph);
+        self.repair(chromosome, graph);
+    }
+}
+
+/// Total double Roman domination (`γ_tdR`), with labels `0` to `3`.
+///
+/// The conditions of [`DoubleRomanDomination`], and every vertex with a positive label
+/// must have a neighbour with a positive label.
+///
+/// Violations of the double Roman conditions are repaired as in
+/// [`DoubleRomanDomination`]; a positive vertex without a positive neighbour then gets
+/// its first neighbour raised to `1`. Isolated vertices cannot be repaired, since no
+/// labelling is feasible. Heuristic chromosomes are prepared as in
+/// [`DoubleRomanDomination`], which keeps total Roman dominating functions total.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TotalDoubleRomanDomination;
+
+impl Problem for TotalDoubleRomanDomination {
+    fn name(&self) -> &'static str {
+        "total-double-roman"
+    }
+
+    fn max_label(&self) -> u8 {
+        3
+    }
+
+    fn is_satisfied(&self, chromosome: &Chromosome, vertex: u32) -> bool {
+        DoubleRomanDomination.is_satisfied(chromosome, vertex)
+            && (chromosome.genes()[vertex as usize] == 0
+                || chromosome.neighbors_at_least(vertex, 1) > 0)
+    }
+
+    fn repair_move(
+        &self,
+        chromosome: &Chromosome,
+        vertex: u32,
+        graph: &CsrGraph,
+    ) -> Option<(u32, u8)> {
+        DoubleRomanDomination
+            .repair_move(chromosome, vertex, graph)
+            .or_else(|| {
+                // Um vértice 1 já foi elevado a 2 acima, então o vizinho que recebe 1
+                // tem um vizinho com rótulo pelo menos 2.
+                let isolated = chromosome.genes()[vertex as usize] > 0
+                    && chromosome.neighbors_at_least(vertex, 1) == 0;
+                graph
+                    .neighbors(vertex)
+                    .first()
+                    .filter(|_| isolated)
+                    .map(|&n| (n, 1))
+            })
+    }
+
+    fn prepare(&self, chromosome: &mut Chromosome, graph: &CsrGraph) {
+        double(chromosome, graph);
+        self.repair(chromosome, graph);
+    }
+}
+
+/// Italian domination, also called Roman \{2\}-domination (`γ_I`).
+///
+/// - Vertices labelled `0` must have neighbours whose labels sum to at least `2`.
+/// - Vertices labelled `1` or `2` have no condition.
+///
+/// A vertex labelled `0` whose neighbours sum to `1` gets its `1`-neighbour raised to
+/// `2`. If they sum to `0`, its first neighbour is raised to `2`, and an isolated vertex is
+/// raised to `1`. The initial population is built by the Italian heuristics
+/// [`ih1`](super::ih1) to [`ih4`](super::ih4).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ItalianDomination;
+
+impl Problem for ItalianDomination {
+    fn name(&self) -> &'static str {
+        "italian"
+    }
+
+    fn is_satisfied(&self, chromosome: &Chromosome, vertex: u32) -> bool {
+        chromosome.genes()[vertex as usize] > 0 || italian_sum(chromosome, vertex) >= 2
+    }
+
+    fn repair_move(
+        &self,
+        chromosome: &Chromosome,
+        vertex: u32,
+        graph: &CsrGraph,
+    ) -> Option<(u32, u8)> {
+        match label(chromosome, vertex, self.max_label()) {
+            0 if italian_sum(chromosome, vertex) < 2 => {
+                // Com soma 1 há exatamente um vizinho 1; com soma 0 todos os vizinhos têm 0.
+                let neighbors = graph.neighbors(vertex);
+                let target = neighbors
+                    .iter()
+                    .find(|&&n| chromosome.genes()[n as usize] == 1)
+                    .or_else(|| neighbors.first());
+                Some(target.map_or((vertex, 1), |&n| (n, 2)))
+            }
+            _ => None,
+        }
+    }
+
+    fn heuristics(&self) -> Vec<Arc<dyn Heuristic>> {
+        italian_heuristics()
+    }
+}
+
+/// Total Italian domination (`γ_tI`).
+///
+/// The conditions of [`ItalianDomination`], and every vertex with a positive label must
+/// have a neighbour with a positive label.
+///
+/// Violations of the Italian condition are repaired as in [`ItalianDomination`]; a
+/// positive vertex without a positive neighbour then gets its first neighbour raised to
+/// `1`. Isolated vertices cannot be repaired, since no labelling is feasible. The initial
+/// population is built by the Italian heuristics, whose chromosomes the repair makes
+/// total.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TotalItalianDomination;
+
+impl Problem for TotalItalianDomination {
+    fn name(&self) -> &'static str {
+        "total-italian"
+    }
+
+    fn is_satisfied(&self, chromosome: &Chromosome, vertex: u32) -> bool {
+        match chromosome.genes()[vertex as usize] {
+            0 => italian_sum(chromosome, vertex) >= 2,
+            _ => chromosome.neighbors_at_least(vertex, 1) > 0,
+        }
+    }
+
+    fn repair_move(
+        &self,
+        chromosome: &Chromosome,
+        vertex: u32,
+        graph: &CsrGraph,
+    ) -> Option<(u32, u8)> {
+        ItalianDomination
+            .repair_move(chromosome, vertex, graph)
+            .or_else(|| {
+                let isolated = chromosome.genes()[vertex as usize] > 0
+                    && chromosome.neighbors_at_least(vertex, 1) == 0;
+                graph
+                    .neighbors(vertex)
+                    .first()
+                    .filter(|_| isolated)
+                    .map(|&n| (n, 1))
+            })
+    }
+
+    fn heuristics(&self) -> Vec<Arc<dyn Heuristic>> {
+        italian_heuristics()
+    }
+}
+
+/// Independent Roman domination (`i_R`).
+///
+/// - Vertices labelled `0` must have a neighbour labelled `2`.
+/// - Vertices labelled `1` or `2` must have no neighbour with a positive label, i.e. the
+///   positive vertices form an independent set.
+///
+/// Raising a label can break independence, so the repair first lowers labels: positive
+/// vertices are visited in decreasing order of label and then of degree, and a vertex
+/// adjacent to a positive vertex kept before it is lowered to `0`. A vertex labelled `0`
+/// without a `2`-neighbour is then repaired by raising a `1`-neighbour to `2` or, if it
+/// has no positive neighbour, by raising itself to `2` when some neighbour also lacks a
+/// `2`-neighbour and to `1` otherwise. Neither move creates adjacent positive vertices.
+///
+/// The initial population is built by [`ir1`](super::ir1) and by the Roman heuristics,
+/// whose chromosomes the repair makes independent.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct IndependentRomanDomination;
+
+impl Problem for IndependentRomanDomination {
+    fn name(&self) -> &'static str {
+        "independent-roman"
+    }
+
+    fn is_satisfied(&self, chromosome: &Chromosome, vertex: u32) -> bool {
+        match chromosome.genes()[vertex as usize] {
+            0 => chromosome.labelled_neighbors(vertex, 2) > 0,
+            _ => chromosome.neighbors_at_least(vertex, 1) == 0,
+        }
+    }
+
+    fn repair_move(
+        &self,
+        chromosome: &Chromosome,
+        vertex: u32,
+        graph: &CsrGraph,
+    ) -> Option<(u32, u8)> {
+        match label(chromosome, vertex, self.max_label()) {
+            0 if chromosome.labelled_neighbors(vertex, 2) == 0 => {
+                let genes = chromosome.genes();
+                let neighbors = graph.neighbors(vertex);
+                // Sem vizinhos 2, um vizinho positivo tem rótulo 1 e seus vizinhos têm 0.
+                if let Some(&one) = neighbors.iter().find(|&&n| genes[n as usize] == 1) {
+                    return Some((one, 2));
+                }
+                let uncovered = neighbors
+                    .iter()
+                    .any(|&n| genes[n as usize] == 0 && chromosome.labelled_neighbors(n, 2) == 0);
+                Some((vertex, if uncovered { 2 } else { 1 }))
+            }
+            _ => None,
+        }
+    }
+
+    fn repair(&self, chromosome: &mut Chromosome, graph: &CsrGraph) {
+        chromosome.track_neighbors(graph);
+
+        let mut positive: Vec<u32> = graph
+            .vertices()
+            .filter(|&v| chromosome.genes()[v as usize] > 0)
+            .collect();
+        positive.sort_by_key(|&v| {
+            (
+                std::cmp::Reverse(chromosome.genes()[v as usize]),
+                std::cmp::Reverse(graph.degree(v)),
+            )
+        });
+
+        // Mantém um conjunto independente guloso de vértices positivos; os demais vão a 0.
+        let mut kept = vec![false; graph.order()];
+        for v in positive {
+            if graph.neighbors(v).iter().any(|&n| kept[n as usize]) {
+                chromosome.set_label(v, 0, graph);
+            } else {
+                kept[v as usize] = true;
+            }
+        }
+
+        apply_repair_moves(self, chromosome, graph);
+    }
+
+    fn heuristics(&self) -> Vec<Arc<dyn Heuristic>> {
+        vec![
+            Arc::new(IR1),
+            Arc::new(H2),
+            Arc::new(H3),
+            Arc::new(H4),
+            Arc::new(H5),
+            Arc::new(H1),
+        ]
+    }
+}
+
+/// Perfect Roman domination (`γ_R^p`).
+///
+/// - Vertices labelled `0` must have exactly one neighbour labelled `2`.
+/// - Vertices labelled `1` or `2` have no condition.
+///
+/// Raising a label to `2` can give a `0`-neighbour a second `2`-neighbour, so the repair
+/// first lowers labels: a vertex labelled `2` with more `0`-neighbours that have another
+/// `2`-neighbour than `0`-neighbours dominated only by it is lowered to `0` if it has
+/// exactly one `2`-neighbour and to `1` otherwise. A vertex labelled `0` that is still dominated
+/// by two or more `2`-neighbours is then raised to `1`. A vertex labelled `0` without a
+/// `2`-neighbour gets its first neighbour labelled below `2` whose `0`-neighbours have no
+/// `2`-neighbour raised to `2`, or is raised to `1` if there is none.
+///
+/// The initial population is built by [`pr1`](super::pr1) and by the Roman heuristics.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PerfectRomanDomination;
+
+impl Problem for PerfectRomanDomination {
+    fn name(&self) -> &'static str {
+        "perfect-roman"
+    }
+
+    fn is_satisfied(&self, chromosome: &Chromosome, vertex: u32) -> bool {
+        chromosome.genes()[vertex as usize] > 0 || chromosome.labelled_neighbors(vertex, 2) == 1
+    }
+
+    fn repair_move(
+        &self,
+        chromosome: &Chromosome,
+        vertex: u32,
+        graph: &CsrGraph,
+    ) -> Option<(u32, u8)> {
+        match label(chromosome, vertex, self.max_label()) {
+            0 if chromosome.labelled_neighbors(vertex, 2) == 0 => {
+                let genes = chromosome.genes();
+                // Um vizinho só pode passar a 2 se nenhum vizinho 0 dele já tiver um vizinho 2.
+                let target = graph.neighbors(vertex).iter().find(|&&n| {
+                    genes[n as usize] < 2
+                        && graph.neighbors(n).iter().all(|&w| {
+                            genes[w as usize] > 0 || chromosome.labelled_neighbors(w, 2) == 0
+                        })
+                });
+                Some(target.map_or((vertex, 1), |&n| (n, 2)))
+            }
+            0 if chromosome.labelled_neighbors(vertex, 2) > 1 => Some((vertex, 1)),
+            _ => None,
+        }
+    }
+
+    fn repair(&self, chromosome: &mut Chromosome, graph: &CsrGraph) {
+        chromosome.track_neighbors(graph);
+
+        let twos: Vec<u32> = graph
+            .vertices()
+            .filter(|&v| chromosome.genes()[v as usize] == 2)
+            .collect();
+
+        for v in twos {
+            // Vizinhos 0 dominados só por v ficam descobertos; os com outro vizinho 2, não.
+            let (mut sole, mut shared) = (0, 0);
+            for &n in graph.neighbors(v) {
+                match chromosome.genes()[n as usize] {
+                    0 if chromosome.labelled_neighbors(n, 2) == 1 => sole += 1,
+                    0 => shared += 1,
+                    _ => {}
+                }
+            }
+
+            if shared > sole {
+                let lowered = u8::from(chromosome.labelled_neighbors(v, 2) != 1);
+                chromosome.set_label(v, lowered, graph);
+            }
+        }
+
+        apply_repair_moves(self, chromosome, graph);
+    }
+
+    fn heuristics(&self) -> Vec<Arc<dyn Heuristic>> {
+        vec![
+            Arc::new(PR1),
+            Arc::new(H2),
+            Arc::new(H3),
+            Arc::new(H4),
+            Arc::new(H5),
+            Arc::new(H1),
+        ]
+    }
+}
+
+/// Checks every vertex once and applies [`Problem::repair_move`] until no move is left;
+/// a relabelled vertex is checked again, since its own condition may now be violated.
+fn apply_repair_moves<P: Problem + ?Sized>(
+    problem: &P,
+    chromosome: &mut Chromosome,
+    graph: &CsrGraph,
+) {
+    chromosome.track_neighbors(graph);
+
+    // Pilha de vértices a verificar; desempilha em ordem crescente de índice.
+    let mut pending: Vec<u32> = graph.vertices().rev().collect();
+
+    while let Some(vertex) = pending.pop() {
+        if let Some((relabelled, label)) = problem.repair_move(chromosome, vertex, graph) {
+            chromosome.set_label(relabelled, label, graph);
+            pending.push(relabelled);
+        }
+    }
+}
+
+/// Returns the sum of the labels of the neighbours of `vertex`, for labels up to `2`.
+fn italian_sum(chromosome: &Chromosome, vertex: u32) -> u32 {
+    2 * chromosome.labelled_neighbors(vertex, 2) + chromosome.labelled_neighbors(vertex, 1)
+}
+
+/// `ih1` to `ih4` and `h5`, followed by `ih1` again for the rest of the population.
+fn italian_heuristics() -> Vec<Arc<dyn Heuristic>> {
+    vec![
+        Arc::new(IH1),
+        Arc::new(IH2),
+        Arc::new(IH3),
+        Arc::new(IH4),
+        Arc::new(H5),
+        Arc::new(IH1),
+    ]
+}
+
+/// Returns the label of `vertex`.
+///
+/// # Panics
+/// Panics if `vertex` is out of range or has a label greater than `max_label`.
+fn label(chromosome: &Chromosome, vertex: u32, max_label: u8) -> u8 {
+    match chromosome.genes().get(vertex as usize) {
+        Some(&label) if label <= max_label => label,
+        Some(&invalid) => {
+            panic!(
+                "Vértice com rótulo inválido encontrado! Índice: {vertex}, Valor: {invalid}. \
+                    Os rótulos válidos vão de 0 a {max_label}."
+            );
+        }
+        None => {
+            panic!(
+                "Tentativa de acessar índice fora dos limites! Índice: {vertex}. \
+                Verifique se o vetor de genes está consistente com o grafo.",
+            );
+        }
+    }
+}
+
+/// Returns the first neighbour labelled `0`, or the first neighbour if there is none.
+fn first_zero_or_any(chromosome: &Chromosome, neighbors: &[u32]) -> Option<u32> {
+    neighbors
+        .iter()
+        .find(|&&n| chromosome.genes()[n as usize] == 0)
+        .or_else(|| neighbors.first())
+        .copied()
+}
+
+/// Relabels every `2` as `3` and every `1` as `2`.
+fn double(chromosome: &mut Chromosome, graph: &CsrGraph) {
+    let genes = chromosome
+        .genes()
+        .iter()
+        .map(|&label| if label > 0 { label + 1 } else { 0 })
+        .collect();
+    *chromosome = Chromosome::new(genes, graph);
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::*;
+    use crate::{exact::brute_force, genetic::h2};
+
+    fn path(order: u32) -> CsrGraph {
+        let edges: Vec<(u32, u32)> = (1..order).map(|v| (v - 1, v)).collect();
+        CsrGraph::from_edges(order as usize, &edges)
+    }
+
+    /// A random connected graph on `order` vertices, a Hamiltonian path plus random
+    /// chords, followed by `isolated` isolated vertices.
+    fn random_graph(order: u32, isolated: u32, rng: &mut StdRng) -> CsrGraph {
+        let probability = rng.gen_range(0.0..0.4);
+        let mut edges: Vec<(u32, u32)> = (1..order).map(|v| (v - 1, v)).collect();
+        for u in 0..order {
+            for v in u + 2..order {
+                if rng.gen_bool(probability) {
+                    edges.push((u, v));
+                }
+            }
+        }
+        CsrGraph::from_edges((order + isolated) as usize, &edges)
+    }
+
+    fn complete(order: u32) -> CsrGraph {
+        let edges: Vec<(u32, u32)> = (0..order)
+            .flat_map(|u| (u + 1..order).map(move |v| (u, v)))
+            .collect();
+        CsrGraph::from_edges(order as usize, &edges)
+    }
+
+    fn satisfied(problem: &dyn Problem, genes: &[u8], graph: &CsrGraph, vertex: u32) -> bool {
+        let mut chromosome = Chromosome::new(genes.to_vec(), graph);
+        chromosome.track_neighbors(graph);
+        problem.is_satisfied(&chromosome, vertex)
+    }
+
+    /// Random labellings of `graph` with labels up to `max_label`.
+    fn random_genes(graph: &CsrGraph, max_label: u8, rng: &mut StdRng) -> Vec<u8> {
+        graph
+            .vertices()
+            .map(|_| rng.gen_range(0..=max_label))
+            .collect()
+    }
+
+    fn repaired(problem: &dyn Problem, genes: &[u8], graph: &CsrGraph) -> Vec<u8> {
+        let mut chromosome = Chromosome::new(genes.to_vec(), graph);
+        problem.repair(&mut chromosome, graph);
+        assert!(
+            problem.is_feasible(&chromosome, graph),
+            "{}",
+            problem.name()
+        );
+        chromosome.genes().to_vec()
+    }
+
+    #[test]
+    fn a_zero_between_positive_neighbours_gets_a_neighbour_raised_to_two() {
+        let graph = path(3);
+        assert_eq!(repaired(&RomanDomination, &[1, 0, 1], &graph), [2, 0, 1]);
+        assert_eq!(
+            repaired(&TotalRomanDomination, &[0, 1, 1], &graph),
+            [0, 2, 1]
+        );
+    }
+
+    #[test]
+    fn roman_and_total_roman_repairs_reach_feasibility() {
+        let mut rng = StdRng::seed_from_u64(41);
+        for _ in 0..50 {
+            let order = rng.gen_range(2..25);
+            let connected = random_graph(order, 0, &mut rng);
+            let with_isolated = random_graph(order, 3, &mut rng);
+            let zeros = |graph: &CsrGraph| vec![0; graph.order()];
+
+            repaired(&TotalRomanDomination, &zeros(&connected), &connected);
+            repaired(&RomanDomination, &zeros(&with_isolated), &with_isolated);
+            for _ in 0..5 {
+                let genes = random_genes(&connected, 2, &mut rng);
+                repaired(&TotalRomanDomination, &genes, &connected);
+                let genes = random_genes(&with_isolated, 2, &mut rng);
+                repaired(&RomanDomination, &genes, &with_isolated);
+            }
+        }
+    }
+
+    #[test]
+    fn an_isolated_zero_is_raised_to_one() {
+        let graph = CsrGraph::from_edges(3, &[(0, 1)]);
+        assert_eq!(repaired(&RomanDomination, &[2, 0, 0], &graph), [2, 0, 1]);
+    }
+
+    #[test]
+    fn variant_names_round_trip() {
+        for variant in ProblemVariant::ALL {
+            let name = variant.to_string();
+            assert_eq!(name.parse::<ProblemVariant>(), Ok(variant));
+            assert_eq!(variant.build().name(), name);
+        }
+        assert!("roman-domination".parse::<ProblemVariant>().is_err());
+    }
+
+    #[test]
+    fn double_roman_conditions() {
+        // Estrela K_{1,3} com centro 0.
+        let star = CsrGraph::from_edges(4, &[(0, 1), (0, 2), (0, 3)]);
+        let center = |problem: &dyn Problem, genes: &[u8]| satisfied(problem, genes, &star, 0);
+
+        for problem in [
+            &DoubleRomanDomination as &dyn Problem,
+            &TotalDoubleRomanDomination,
+        ] {
+            assert!(center(problem, &[0, 3, 0, 0]));
+            assert!(center(problem, &[0, 2, 2, 0]));
+            assert!(!center(problem, &[0, 2, 1, 1]));
+            assert!(!center(problem, &[0, 1, 1, 1]));
+            assert!(center(problem, &[1, 2, 0, 0]));
+            assert!(center(problem, &[1, 0, 3, 0]));
+            assert!(!center(problem, &[1, 1, 1, 1]));
+        }
+
+        // Rótulos 2 e 3 só exigem, na versão total, um vizinho positivo.
+        assert!(center(&DoubleRomanDomination, &[3, 0, 0, 0]));
+        assert!(!center(&TotalDoubleRomanDomination, &[3, 0, 0, 0]));
+        assert!(!center(&TotalDoubleRomanDomination, &[2, 0, 0, 0]));
+        assert!(center(&TotalDoubleRomanDomination, &[3, 1, 0, 0]));
+        assert!(satisfied(
+            &TotalDoubleRomanDomination,
+            &[3, 1, 0, 0],
+            &star,
+            1
+        ));
+    }
+
+    #[test]
+    fn prepare_doubles_heuristic_labels() {
+        let mut rng = StdRng::seed_from_u64(42);
+        for _ in 0..30 {
+            let order = rng.gen_range(2..25);
+            let graph = random_graph(order, 0, &mut rng);
+            let heuristic = h2(&graph);
+            let doubled: Vec<u8> = heuristic
+                .genes()
+                .iter()
+                .map(|&label| match label {
+                    0 => 0,
+                    _ => label + 1,
+                })
+                .collect();
+
+            // Uma função de dominação romana dobrada já é dupla romana.
+            let mut chromosome = heuristic.clone();
+            DoubleRomanDomination.prepare(&mut chromosome, &graph);
+            assert_eq!(chromosome.genes(), doubled);
+
+            let mut chromosome = heuristic;
+            TotalDoubleRomanDomination.prepare(&mut chromosome, &graph);
+            assert!(TotalDoubleRomanDomination.is_feasible(&chromosome, &graph));
+            assert!(chromosome.genes().iter().zip(&doubled).all(|(a, b)| a >= b));
+        }
+    }
+
+    #[test]
+    fn double_roman_numbers_of_paths_and_complete_graphs() {
+        let optimum = |graph: &CsrGraph| {
+            brute_force(&DoubleRomanDomination, graph)
+                .unwrap()
+                .fitness()
+        };
+        for n in 1..=8 {
+            // γ_dR(P_n) = n se n ≡ 0 (mod 3), n + 1 caso contrário.
+            let expected = if n % 3 == 0 { n } else { n + 1 };
+            assert_eq!(optimum(&path(n)), expected as usize, "P_{n}");
+        }
+        for n in 2..=8 {
+            assert_eq!(optimum(&complete(n)), 3, "K_{n}");
+        }
+    }
+
+    #[test]
+    fn italian_zeros_need_neighbours_summing_to_two() {
+        let star = CsrGraph::from_edges(4, &[(0, 1), (0, 2), (0, 3)]);
+        for problem in [&ItalianDomination as &dyn Problem, &TotalItalianDomination] {
+            let center = |genes: &[u8]| satisfied(problem, genes, &star, 0);
+            assert!(center(&[0, 2, 0, 0]));
+            assert!(center(&[0, 1, 1, 0]));
+            assert!(center(&[0, 1, 0, 2]));
+            assert!(!center(&[0, 1, 0, 0]));
+            assert!(!center(&[0, 0, 0, 0]));
+        }
+
+        // Folhas positivas só precisam, na versão total, de um vizinho positivo.
+        assert!(satisfied(&ItalianDomination, &[0, 1, 1, 0], &star, 1));
+        assert!(!satisfied(&TotalItalianDomination, &[0, 1, 1, 0], &star, 1));
+        assert!(satisfied(&TotalItalianDomination, &[1, 1, 0, 0], &star, 1));
+    }
+
+    #[test]
+    fn italian_numbers_of_paths() {
+        for n in 1..=8 {
+            // γ_I(P_n) = ⌈(n + 1) / 2⌉.
+            let optimum = brute_force(&ItalianDomination, &path(n)).unwrap();
+            assert_eq!(optimum.fitness(), (n as usize + 2) / 2, "P_{n}");
+        }
+    }
+
+    #[test]
+    fn independent_and_perfect_roman_conditions() {
+        let graph = path(3);
+        let feasible = |problem: &dyn Problem, genes: &[u8]| {
+            let mut chromosome = Chromosome::new(genes.to_vec(), &graph);
+            chromosome.track_neighbors(&graph);
+            problem.is_feasible(&chromosome, &graph)
+        };
+
+        for problem in [
+            &IndependentRomanDomination as &dyn Problem,
+            &PerfectRomanDomination,
+        ] {
+            assert!(feasible(problem, &[0, 2, 0]));
+            assert!(!feasible(problem, &[0, 1, 0]));
+        }
+
+        // Vértices positivos adjacentes quebram a independência.
+        assert!(!feasible(&IndependentRomanDomination, &[0, 2, 1]));
+        assert!(!feasible(&IndependentRomanDomination, &[1, 1, 1]));
+        assert!(feasible(&PerfectRomanDomination, &[0, 2, 1]));
+
+        // Um vértice 0 com dois vizinhos 2 não é dominado perfeitamente.
+        assert!(feasible(&IndependentRomanDomination, &[2, 0, 2]));
+        assert!(!feasible(&PerfectRomanDomination, &[2, 0, 2]));
+    }
+
+    #[test]
+    fn independent_and_perfect_repairs_reach_feasibility() {
+        let mut rng = StdRng::seed_from_u64(44);
+        for _ in 0..50 {
+            let order = rng.gen_range(2..25);
+            let graph = random_graph(order, 2, &mut rng);
+            for _ in 0..5 {
+                let genes = random_genes(&graph, 2, &mut rng);
+                repaired(&IndependentRomanDomination, &genes, &graph);
+                repaired(&PerfectRomanDomination, &genes, &graph);
+            }
+        }
+    }
+
+    #[test]
+    fn independent_and_perfect_roman_numbers_of_paths() {
+        for n in 1..=8 {
+            // i_R(P_n) = γ_R^p(P_n) = γ_R(P_n) = ⌈2n / 3⌉.
+            let expected = (2 * n as usize).div_ceil(3);
+            for problem in [
+                &IndependentRomanDomination as &dyn Problem,
+                &PerfectRomanDomination,
+            ] {
+                let optimum = brute_force(problem, &path(n)).unwrap();
+                assert_eq!(optimum.fitness(), expected, "{} P_{n}", problem.name());
+            }
+        }
+    }
+}
@@ -0,0 +1,285 @@
+use std::{fmt, sync::Arc};
+
+use super::{Grasp, Heuristic, H1, H2, H3, H4, H5, IH1, IH2, IH3, IH4, IR1, PR1, WH};
+
+/// Collection of heuristics that can be looked up by name.
+///
+/// The default registry contains the heuristics `h1` to `h5`, their Italian versions
+/// `ih1` to `ih4`, `ir1` and `pr1` for independent and perfect Roman domination, the
+/// cost-aware `wh` and `grasp` with the default `alpha`; the solver replaces the latter
+/// with the configured [`GraspParams`](crate::solver::GraspParams) before building a
+/// population. Heuristics registered later replace earlier ones with the same name.
+#[derive(Clone)]
+pub struct HeuristicRegistry {
+    heuristics: Vec<Arc<dyn Heuristic>>,
+}
+
+impl HeuristicRegistry {
+    /// Creates an empty registry.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            heuristics: Vec::new(),
+        }
+    }
+
+    /// Adds `heuristic` to the registry, replacing any heuristic with the same name.
+    pub fn register(&mut self, heuristic: Arc<dyn Heuristic>) {
+        match self
+            .heuristics
+            .iter_mut()
+            .find(|registered| registered.name() == heuristic.name())
+        {
+            Some(registered) => *registered = heuristic,
+            None => self.heuristics.push(heuristic),
+        }
+    }
+
+    /// Returns the heuristic called `name`, if registered.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<Arc<dyn Heuristic>> {
+        self.heuristics
+            .iter()
+            .find(|heuristic| heuristic.name() == name)
+            .cloned()
+    }
+
+    /// Returns the names of the registered heuristics, in registration order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.heuristics.iter().map(|heuristic| heuristic.name())
+    }
+
+    /// Parses an initial-population mix such as `"h1:0.6,h3:0.2,h4:0.2"`.
+    ///
+    /// Each entry is a heuristic name optionally followed by `:` and a non-negative
+    /// weight; a missing weight counts as `1`. Weights are relative and need not sum
+    /// to `1`.
+    ///
+    /// # Errors
+    /// Returns an error if the mix is empty, names an unknown heuristic, has an invalid
+    /// weight, or if all weights are zero.
+    pub fn parse_mix(&self, spec: &str) -> Result<HeuristicMix, String> {
+        let mut entries = Vec::new();
+
+        for entry in spec.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let (name, weight) = match entry.split_once(':') {
+                Some((name, weight)) => {
+                    let weight: f64 = weight
+                        .trim()
+                        .parse()
+                        .map_err(|_| format!("Invalid weight in heuristic mix: {entry}"))?;
+                    (name.trim(), weight)
+                }
+                None => (entry, 1.0),
+            };
+
+            let heuristic = self.get(name).ok_or_else(|| {
+                format!(
+                    "Unknown heuristic: {name} (available: {})",
+                    self.names().collect::<Vec<_>>().join(", ")
+                )
+            })?;
+            entries.push((heuristic, weight));
+        }
+
+        HeuristicMix::new(entries)
+    }
+}
+
+impl Default for HeuristicRegistry {
+    fn default() -> Self {
+        let mut registry = Self::new();
+        registry.register(Arc::new(H1));
+        registry.register(Arc::new(H2));
+        registry.register(Arc::new(H3));
+        registry.register(Arc::new(H4));
+        registry.register(Arc::new(H5));
+        registry.register(Arc::new(IH1));
+        registry.register(Arc::new(IH2));
+        registry.register(Arc::new(IH3));
+        registry.register(Arc::new(IH4));
+        registry.register(Arc::new(IR1));
+        registry.register(Arc::new(PR1));
+        registry.register(Arc::new(WH));
+        registry.register(Arc::new(Grasp::default()));
+        registry
+    }
+}
+
+/// Weighted mix of heuristics used to build the initial population.
+///
+/// Each heuristic builds a share of the population proportional to its weight; see
+/// [`HeuristicMix::counts`].
+#[derive(Clone)]
+pub struct HeuristicMix {
+    entries: Vec<(Arc<dyn Heuristic>, f64)>,
+}
+
+impl HeuristicMix {
+    /// Creates a mix from `(heuristic, weight)` pairs.
+    ///
+    /// # Errors
+    /// Returns an error if `entries` is empty, a weight is negative or not finite, or
+    /// all weights are zero.
+    pub fn new(entries: Vec<(Arc<dyn Heuristic>, f64)>) -> Result<Self, String> {
+        if entries.is_empty() {
+            return Err("The heuristic mix is empty".to_string());
+        }
+        if let Some((heuristic, weight)) = entries
+            .iter()
+            .find(|(_, weight)| !weight.is_finite() || *weight < 0.0)
+        {
+            return Err(format!(
+                "Invalid weight for heuristic {}: {weight}",
+                heuristic.name()
+            ));
+        }
+        if entries.iter().all(|(_, weight)| *weight == 0.0) {
+            return Err("At least one heuristic weight must be positive".to_string());
+        }
+
+        Ok(Self { entries })
+    }
+
+    /// Replaces the heuristics of the mix named like `heuristic` with it, keeping their
+    /// weights.
+    pub fn replace(&mut self, heuristic: &Arc<dyn Heuristic>) {
+        for (entry, _) in &mut self.entries {
+            if entry.name() == heuristic.name() {
+                *entry = Arc::clone(heuristic);
+            }
+        }
+    }
+
+    /// Returns the heuristics of the mix with their weights.
+    #[must_use]
+    pub fn entries(&self) -> &[(Arc<dyn Heuristic>, f64)] {
+        &self.entries
+    }
+
+    /// Splits a population of `size` chromosomes among the heuristics of the mix.
+    ///
+    /// The `i`-th count is proportional to the `i`-th weight; remainders are handed out
+    /// by the largest-remainder method, so the counts always sum to `size`.
+    #[must_use]
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    pub fn counts(&self, size: usize) -> Vec<usize> {
+        let total: f64 = self.entries.iter().map(|(_, weight)| weight).sum();
+        let quotas: Vec<f64> = self
+            .entries
+            .iter()
+            .map(|(_, weight)| weight / total * size as f64)
+            .collect();
+
+        let mut counts: Vec<usize> = quotas.iter().map(|quota| quota.floor() as usize).collect();
+        let mut by_remainder: Vec<usize> = (0..quotas.len()).collect();
+        by_remainder.sort_by(|&a, &b| {
+            (quotas[b] - quotas[b].floor()).total_cmp(&(quotas[a] - quotas[a].floor()))
+        });
+
+        let assigned: usize = counts.iter().sum();
+        for &idx in by_remainder
+            .iter()
+            .cycle()
+            .take(size.saturating_sub(assigned))
+        {
+            counts[idx] += 1;
+        }
+
+        counts
+    }
+}
+
+impl fmt::Display for HeuristicMix {
+    /// Formats the mix in the syntax accepted by [`HeuristicRegistry::parse_mix`].
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, (heuristic, weight)) in self.entries.iter().enumerate() {
+            if idx > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{}:{weight}", heuristic.name())?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for HeuristicMix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "HeuristicMix({self})")
+    }
+}
+
+impl PartialEq for HeuristicMix {
+    /// Two mixes are equal if they list the same heuristic names with the same weights.
+    fn eq(&self, other: &Self) -> bool {
+        self.entries.len() == other.entries.len()
+            && self
+                .entries
+                .iter()
+                .zip(&other.entries)
+                .all(|((a, wa), (b, wb))| a.name() == b.name() && wa == wb)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_sum_to_the_population_size() {
+        let registry = HeuristicRegistry::default();
+        for (spec, size) in [
+            ("h1:1,h2:1,h3:1", 10),
+            ("h1:0.6,h3:0.2,h4:0.2", 7),
+            ("h1:1,h2:2,h3:3,h4:4", 1),
+            ("h2:0.1", 13),
+            ("h1:1,h2:1", 0),
+        ] {
+            let counts = registry.parse_mix(spec).unwrap().counts(size);
+            assert_eq!(counts.iter().sum::<usize>(), size, "{spec}");
+        }
+
+        // O resto de 10 / 3 vai para um único heurístico.
+        let mut counts = registry.parse_mix("h1:1,h2:1,h3:1").unwrap().counts(10);
+        counts.sort_unstable();
+        assert_eq!(counts, [3, 3, 4]);
+    }
+
+    #[test]
+    fn zero_weights_get_no_chromosomes_and_missing_weights_count_as_one() {
+        let registry = HeuristicRegistry::default();
+
+        let mix = registry.parse_mix("h1:0,h2:3,h3:1").unwrap();
+        assert_eq!(mix.counts(8), [0, 6, 2]);
+
+        let mix = registry.parse_mix("h1, h2:3 ,h4").unwrap();
+        let weights: Vec<f64> = mix.entries().iter().map(|(_, weight)| *weight).collect();
+        assert_eq!(weights, [1.0, 3.0, 1.0]);
+        assert_eq!(mix.counts(10), [2, 6, 2]);
+        assert_eq!(mix, registry.parse_mix("h1:1,h2:3,h4:1").unwrap());
+    }
+
+    #[test]
+    fn parse_mix_rejects_invalid_mixes() {
+        let registry = HeuristicRegistry::default();
+        for spec in [
+            "",
+            " , ",
+            "h9:1",
+            "h1:1,nope",
+            "h1:-1",
+            "h1:1,h2:-0.5",
+            "h1:NaN",
+            "h1:inf",
+            "h1:abc",
+            "h1:0",
+            "h1:0,h2:0.0",
+        ] {
+            assert!(registry.parse_mix(spec).is_err(), "{spec:?}");
+        }
+    }
+}
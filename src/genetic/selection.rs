@@ -1,3 +1,5 @@
+use std::{fmt, str::FromStr};
+
 use rand::prelude::*;
 
 use super::{Chromosome, Population};
@@ -9,11 +11,112 @@ pub trait Selection {
     /// # Arguments
     ///
     /// * `population` - A reference to the population from which to select.
+    /// * `rng` - The random number generator driving the selection.
     ///
     /// # Returns
     ///
     /// A reference to the selected chromosome.
-    fn select<'a>(&self, population: &'a Population) -> &'a Chromosome;
+    fn select<'a>(&self, population: &'a Population, rng: &mut dyn RngCore) -> &'a Chromosome;
+
+    /// Selects `count` chromosomes from the population.
+    ///
+    /// The default implementation calls [`Selection::select`] `count` times. Schemes
+    /// that draw all chromosomes at once, or that can share work between draws,
+    /// override it.
+    fn select_many<'a>(
+        &self,
+        population: &'a Population,
+        count: usize,
+        rng: &mut dyn RngCore,
+    ) -> Vec<&'a Chromosome> {
+        (0..count).map(|_| self.select(population, rng)).collect()
+    }
+}
+
+impl<S: Selection + ?Sized> Selection for Box<S> {
+    fn select<'a>(&self, population: &'a Population, rng: &mut dyn RngCore) -> &'a Chromosome {
+        (**self).select(population, rng)
+    }
+
+    fn select_many<'a>(
+        &self,
+        population: &'a Population,
+        count: usize,
+        rng: &mut dyn RngCore,
+    ) -> Vec<&'a Chromosome> {
+        (**self).select_many(population, count, rng)
+    }
+}
+
+/// Selection schemes that can be chosen at run time.
+///
+/// Parsed from and formatted as `tournament`, `tournament-distinct`, `roulette`,
+/// `rank[:<pressure>]` and `sus`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SelectionOperator {
+    /// [`KTournament`], sampling participants with replacement.
+    Tournament,
+    /// [`DistinctTournament`], sampling participants without replacement.
+    DistinctTournament,
+    /// [`Roulette`].
+    Roulette,
+    /// [`LinearRank`] with the given selection pressure.
+    LinearRank(f64),
+    /// [`StochasticUniversal`].
+    StochasticUniversal,
+}
+
+impl SelectionOperator {
+    /// Builds the selection scheme. Tournaments use `tournament_size` participants.
+    ///
+    /// # Panics
+    /// Panics if a parameter is out of range; see the constructor of each scheme.
+    #[must_use]
+    pub fn build(self, tournament_size: usize) -> Box<dyn Selection + Send + Sync> {
+        match self {
+            Self::Tournament => Box::new(KTournament::new(tournament_size)),
+            Self::DistinctTournament => Box::new(DistinctTournament::new(tournament_size)),
+            Self::Roulette => Box::new(Roulette),
+            Self::LinearRank(pressure) => Box::new(LinearRank::new(pressure)),
+            Self::StochasticUniversal => Box::new(StochasticUniversal),
+        }
+    }
+}
+
+impl FromStr for SelectionOperator {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            None if s == "tournament" => Ok(Self::Tournament),
+            None if s == "tournament-distinct" => Ok(Self::DistinctTournament),
+            None if s == "roulette" => Ok(Self::Roulette),
+            None if s == "rank" => Ok(Self::LinearRank(1.5)),
+            None if s == "sus" => Ok(Self::StochasticUniversal),
+            Some(("rank", pressure)) => match pressure.parse() {
+                Ok(pressure) if (1.0..=2.0).contains(&pressure) => Ok(Self::LinearRank(pressure)),
+                _ => Err(format!(
+                    "Invalid rank selection pressure: {pressure} (expected a value between 1 and 2)"
+                )),
+            },
+            _ => Err(format!(
+                "Invalid selection scheme: {s} \
+                (expected tournament, tournament-distinct, roulette, rank[:<pressure>] or sus)"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for SelectionOperator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tournament => write!(f, "tournament"),
+            Self::DistinctTournament => write!(f, "tournament-distinct"),
+            Self::Roulette => write!(f, "roulette"),
+            Self::LinearRank(pressure) => write!(f, "rank:{pressure}"),
+            Self::StochasticUniversal => write!(f, "sus"),
+        }
+    }
 }
 
 /// K-Tournament selection implementation.
@@ -44,12 +147,12 @@ impl Selection for KTournament {
     /// # Arguments
     ///
     /// * `population` - A reference to the population from which to select.
+    /// * `rng` - The random number generator driving the selection.
     ///
     /// # Returns
     ///
     /// A reference to the selected chromosome.
-    fn select<'a>(&self, population: &'a Population) -> &'a Chromosome {
-        let mut rng = thread_rng();
+    fn select<'a>(&self, population: &'a Population, rng: &mut dyn RngCore) -> &'a Chromosome {
         let pop_size = population.size();
 
         let mut indices = Vec::with_capacity(self.k);
@@ -66,3 +169,313 @@ impl Selection for KTournament {
         &population.chromosomes()[best_idx]
     }
 }
+
+/// K-Tournament selection without replacement.
+///
+/// Unlike [`KTournament`], the `k` participants of a tournament are distinct, so the
+/// same chromosome cannot fill the tournament. If `k` exceeds the population size, every
+/// chromosome takes part.
+pub struct DistinctTournament {
+    k: usize,
+}
+
+impl DistinctTournament {
+    /// Creates a new instance with `k` participants per tournament.
+    ///
+    /// # Panics
+    /// Panics if `k` is zero.
+    #[inline]
+    #[must_use]
+    pub fn new(k: usize) -> Self {
+        assert!(k > 0, "Tournament size must be greater than 0");
+        Self { k }
+    }
+}
+
+impl Selection for DistinctTournament {
+    fn select<'a>(&self, population: &'a Population, rng: &mut dyn RngCore) -> &'a Chromosome {
+        let chromosomes = population.chromosomes();
+        let best_idx =
+            rand::seq::index::sample(rng, chromosomes.len(), self.k.min(chromosomes.len()))
+                .iter()
+                .min_by_key(|&idx| chromosomes[idx].fitness())
+                .unwrap_or(0);
+
+        &chromosomes[best_idx]
+    }
+}
+
+/// Fitness-proportional (roulette wheel) selection for minimisation.
+///
+/// Chromosome `i` is drawn with probability proportional to `f_max - f_i + 1`, where
+/// `f_max` is the worst fitness in the population, so better chromosomes get larger
+/// slices and the worst ones keep a small chance.
+pub struct Roulette;
+
+impl Selection for Roulette {
+    fn select<'a>(&self, population: &'a Population, rng: &mut dyn RngCore) -> &'a Chromosome {
+        self.select_many(population, 1, rng)[0]
+    }
+
+    fn select_many<'a>(
+        &self,
+        population: &'a Population,
+        count: usize,
+        rng: &mut dyn RngCore,
+    ) -> Vec<&'a Chromosome> {
+        let chromosomes = population.chromosomes();
+        let wheel = cumulative(&inverted_fitness(chromosomes));
+        (0..count)
+            .map(|_| &chromosomes[spin(&wheel, rng)])
+            .collect()
+    }
+}
+
+/// Linear rank selection.
+///
+/// Chromosomes are ranked from worst (`0`) to best (`n - 1`) and the chromosome of rank
+/// `i` is drawn with probability `(2 - s) / n + 2 i (s - 1) / (n (n - 1))`, where the
+/// selection pressure `s` is in `[1, 2]`: `1` is uniform and `2` gives the worst
+/// chromosome no chance. Unlike [`Roulette`], the pressure does not depend on the scale
+/// of the fitness values.
+pub struct LinearRank {
+    pressure: f64,
+}
+
+impl LinearRank {
+    /// Creates a new instance with the given selection pressure.
+    ///
+    /// # Panics
+    /// Panics if `pressure` is outside the range `[1.0, 2.0]`.
+    #[inline]
+    #[must_use]
+    pub fn new(pressure: f64) -> Self {
+        assert!(
+            (1.0..=2.0).contains(&pressure),
+            "Selection pressure must be between 1 and 2"
+        );
+        Self { pressure }
+    }
+}
+
+impl Selection for LinearRank {
+    fn select<'a>(&self, population: &'a Population, rng: &mut dyn RngCore) -> &'a Chromosome {
+        self.select_many(population, 1, rng)[0]
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn select_many<'a>(
+        &self,
+        population: &'a Population,
+        count: usize,
+        rng: &mut dyn RngCore,
+    ) -> Vec<&'a Chromosome> {
+        let chromosomes = population.chromosomes();
+        let n = chromosomes.len() as f64;
+
+        // Do pior para o melhor: o índice em `ranked` é o posto.
+        let mut ranked: Vec<&Chromosome> = chromosomes.iter().collect();
+        ranked.sort_by_key(|chromosome| std::cmp::Reverse(chromosome.fitness()));
+
+        let weights: Vec<f64> = (0..ranked.len())
+            .map(|rank| {
+                if ranked.len() < 2 {
+                    1.0
+                } else {
+                    (2.0 - self.pressure) / n
+                        + 2.0 * rank as f64 * (self.pressure - 1.0) / (n * (n - 1.0))
+                }
+            })
+            .collect();
+        let wheel = cumulative(&weights);
+
+        (0..count).map(|_| ranked[spin(&wheel, rng)]).collect()
+    }
+}
+
+/// Stochastic universal sampling (SUS) for minimisation.
+///
+/// Uses the same wheel as [`Roulette`], but draws all chromosomes with a single spin:
+/// `count` equally spaced pointers starting at one random offset. Every chromosome is
+/// then selected a number of times within one of its expected count, which removes most
+/// of the sampling noise of repeated roulette spins. The selected chromosomes are
+/// shuffled so that consecutive picks are not neighbours on the wheel.
+pub struct StochasticUniversal;
+
+impl Selection for StochasticUniversal {
+    fn select<'a>(&self, population: &'a Population, rng: &mut dyn RngCore) -> &'a Chromosome {
+        self.select_many(population, 1, rng)[0]
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn select_many<'a>(
+        &self,
+        population: &'a Population,
+        count: usize,
+        rng: &mut dyn RngCore,
+    ) -> Vec<&'a Chromosome> {
+        if count == 0 {
+            return Vec::new();
+        }
+
+        let chromosomes = population.chromosomes();
+        let wheel = cumulative(&inverted_fitness(chromosomes));
+        let total = wheel.last().copied().unwrap_or(0.0);
+        let step = total / count as f64;
+        let start = rng.gen_range(0.0..step);
+
+        let mut idx = 0;
+        let mut selected: Vec<&Chromosome> = (0..count)
+            .map(|j| {
+                let pointer = start + j as f64 * step;
+                while idx + 1 < wheel.len() && wheel[idx] <= pointer {
+                    idx += 1;
+                }
+                &chromosomes[idx]
+            })
+            .collect();
+        selected.shuffle(rng);
+        selected
+    }
+}
+
+/// Roulette weights `f_max - f_i + 1` of the chromosomes.
+#[allow(clippy::cast_precision_loss)]
+fn inverted_fitness(chromosomes: &[Chromosome]) -> Vec<f64> {
+    let worst = chromosomes
+        .iter()
+        .map(Chromosome::fitness)
+        .max()
+        .unwrap_or(0);
+    chromosomes
+        .iter()
+        .map(|chromosome| (worst - chromosome.fitness() + 1) as f64)
+        .collect()
+}
+
+/// Running sums of `weights`.
+fn cumulative(weights: &[f64]) -> Vec<f64> {
+    weights
+        .iter()
+        .scan(0.0, |sum, weight| {
+            *sum += weight;
+            Some(*sum)
+        })
+        .collect()
+}
+
+/// Draws an index with probability proportional to its slice of the cumulative `wheel`.
+fn spin(wheel: &[f64], rng: &mut dyn RngCore) -> usize {
+    let total = wheel.last().copied().unwrap_or(0.0);
+    let pointer = rng.gen_range(0.0..total);
+    wheel
+        .partition_point(|&sum| sum <= pointer)
+        .min(wheel.len() - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::csr::CsrGraph;
+
+    /// Roulette weights `f_max - f_i + 1` of [`population`].
+    const WEIGHTS: [usize; 5] = [10, 9, 7, 5, 1];
+
+    /// Chromosomes of fitness 1, 2, 4, 6 and 10.
+    fn population() -> Population {
+        let graph = CsrGraph::from_edges(10, &[]);
+        let chromosomes = [1, 2, 4, 6, 10]
+            .map(|fitness| {
+                let genes = (0..10).map(|v| u8::from(v < fitness)).collect();
+                Chromosome::new(genes, &graph)
+            })
+            .to_vec();
+        Population::from_chromosomes(chromosomes)
+    }
+
+    /// Number of picks of each chromosome of `population`, in order.
+    fn counts(population: &Population, picks: &[&Chromosome]) -> Vec<usize> {
+        population
+            .chromosomes()
+            .iter()
+            .map(|chromosome| {
+                picks
+                    .iter()
+                    .filter(|pick| std::ptr::eq(**pick, chromosome))
+                    .count()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn sus_picks_each_chromosome_its_expected_number_of_times() {
+        let population = population();
+        for seed in 0..20 {
+            let mut rng = StdRng::seed_from_u64(seed);
+
+            let picks = StochasticUniversal.select_many(&population, 32, &mut rng);
+            assert_eq!(counts(&population, &picks), WEIGHTS);
+
+            let picks = StochasticUniversal.select_many(&population, 16, &mut rng);
+            assert_eq!(picks.len(), 16);
+            for (count, weight) in counts(&population, &picks).into_iter().zip(WEIGHTS) {
+                assert!(count == weight / 2 || count == weight.div_ceil(2));
+            }
+        }
+    }
+
+    #[test]
+    fn roulette_picks_in_proportion_to_the_inverted_fitness() {
+        let population = population();
+        let mut rng = StdRng::seed_from_u64(0);
+        let picks = Roulette.select_many(&population, 32_000, &mut rng);
+        for (count, weight) in counts(&population, &picks).into_iter().zip(WEIGHTS) {
+            assert!(count.abs_diff(1000 * weight) < 100 + 10 * weight);
+        }
+    }
+
+    #[test]
+    fn linear_rank_picks_the_best_chromosome_most_often() {
+        let population = population();
+        let mut rng = StdRng::seed_from_u64(0);
+        for pressure in [1.5, 2.0] {
+            let picks = LinearRank::new(pressure).select_many(&population, 20_000, &mut rng);
+            let counts = counts(&population, &picks);
+            assert!(counts.windows(2).all(|pair| pair[0] > pair[1]));
+        }
+
+        // Com pressão 2, o pior cromossomo nunca é escolhido; com pressão 1, a escolha é
+        // uniforme.
+        let picks = LinearRank::new(2.0).select_many(&population, 1000, &mut rng);
+        assert_eq!(counts(&population, &picks)[4], 0);
+        let picks = LinearRank::new(1.0).select_many(&population, 20_000, &mut rng);
+        assert!(counts(&population, &picks)
+            .iter()
+            .all(|&count| count.abs_diff(4000) < 300));
+    }
+
+    #[test]
+    fn distinct_tournament_never_draws_a_participant_twice() {
+        let population = population();
+        let best = &population.chromosomes()[0];
+        let mut rng = StdRng::seed_from_u64(0);
+
+        // Com todos os cromossomos no torneio, o melhor sempre vence...
+        for _ in 0..1000 {
+            let pick = DistinctTournament::new(5).select(&population, &mut rng);
+            assert!(std::ptr::eq(pick, best));
+        }
+        // ...o que não vale com reposição.
+        let misses = (0..1000)
+            .filter(|_| !std::ptr::eq(KTournament::new(5).select(&population, &mut rng), best))
+            .count();
+        assert!(misses > 0);
+
+        // Dois participantes distintos nunca são ambos o pior.
+        let worst = &population.chromosomes()[4];
+        for _ in 0..1000 {
+            let pick = DistinctTournament::new(2).select(&population, &mut rng);
+            assert!(!std::ptr::eq(pick, worst));
+        }
+    }
+}
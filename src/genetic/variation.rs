@@ -0,0 +1,408 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use rand::prelude::*;
+
+use crate::csr::CsrGraph;
+
+use super::{Chromosome, Crossover, CrossoverOperator, Mutation, Problem};
+
+/// Smallest crossover rate reachable by the 1/5th rule.
+const MIN_CROSSOVER_RATE: f64 = 0.05;
+/// Bounds of the mutation rate under the 1/5th rule.
+const MUTATION_RATE_RANGE: (f64, f64) = (1e-4, 0.5);
+/// Factor applied to the rates by the 1/5th rule.
+const ONE_FIFTH_FACTOR: f64 = 0.85;
+/// Weight of the latest generation in the quality estimate of an operator.
+const LEARNING_RATE: f64 = 0.3;
+/// Minimum probability of choosing any operator of the portfolio.
+const MIN_PROBABILITY: f64 = 0.05;
+
+/// Variation operator with optional adaptive parameter control.
+///
+/// Combines a portfolio of crossover operators with a [`Mutation`]. For every pair of
+/// parents, one operator of the portfolio is drawn and applied with the current
+/// crossover rate, and both children are then mutated with the current mutation rate.
+/// Children are repaired with the [`Problem`] after crossover and after mutation.
+///
+/// An application counts as a success when a child is better than the mean fitness of
+/// its parents (for crossover) or than itself before mutating (for mutation). Between
+/// generations, [`Variation::adapt`] uses the successes of the last generation to:
+/// - apply the 1/5th success rule to the crossover and mutation rates, if enabled: a rate
+///   grows when more than a fifth of its applications succeeded and shrinks otherwise;
+/// - update the probability of each operator by credit assignment when the portfolio
+///   holds more than one operator: each operator keeps an exponential moving average of
+///   its success ratio, and probabilities are proportional to these averages (probability
+///   matching), with a minimum probability per operator.
+///
+/// Success counters are atomic, so the operator can be shared by the parallel offspring
+/// generation of [`super::Population`] and the counts do not depend on scheduling.
+pub struct Variation {
+    operators: Vec<(CrossoverOperator, Box<dyn Crossover + Send + Sync>)>,
+    probabilities: Vec<f64>,
+    quality: Vec<f64>,
+    crossover_rate: f64,
+    mutation: Mutation,
+    adaptive_rates: bool,
+    problem: Arc<dyn Problem>,
+    crossover_stats: Vec<Counter>,
+    mutation_stats: Counter,
+}
+
+#[derive(Default)]
+struct Counter {
+    attempts: AtomicUsize,
+    successes: AtomicUsize,
+}
+
+impl Counter {
+    fn record(&self, success: bool) {
+        self.attempts.fetch_add(1, Ordering::Relaxed);
+        if success {
+            self.successes.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Returns the success ratio since the last call and resets the counter, or `None`
+    /// if there were no attempts.
+    #[allow(clippy::cast_precision_loss)]
+    fn take_ratio(&self) -> Option<f64> {
+        let attempts = self.attempts.swap(0, Ordering::Relaxed);
+        let successes = self.successes.swap(0, Ordering::Relaxed);
+        (attempts > 0).then(|| successes as f64 / attempts as f64)
+    }
+}
+
+impl Variation {
+    /// Creates a new variation operator.
+    ///
+    /// # Parameters
+    /// - `operators: &[CrossoverOperator]`: The crossover portfolio. Operators start with
+    ///   equal probability.
+    /// - `crossover_rate: f64`: Initial probability of applying crossover to a pair.
+    /// - `mutation_rate: f64`: Initial per-gene mutation rate.
+    /// - `adaptive_rates: bool`: Adapt both rates with the 1/5th success rule. A zero
+    ///   mutation rate then starts at `1 / n`, where `n` is the number of vertices.
+    /// - `problem: Arc<dyn Problem>`: The problem repairing the children.
+    /// - `graph: &CsrGraph`: The graph the chromosomes refer to.
+    ///
+    /// # Panics
+    /// - If `operators` is empty.
+    /// - If a rate is outside the range `[0.0, 1.0]`.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn new(
+        operators: &[CrossoverOperator],
+        crossover_rate: f64,
+        mutation_rate: f64,
+        adaptive_rates: bool,
+        problem: Arc<dyn Problem>,
+        graph: &CsrGraph,
+    ) -> Self {
+        assert!(
+            !operators.is_empty(),
+            "At least one crossover operator must be provided."
+        );
+        assert!(
+            (0.0..=1.0).contains(&crossover_rate),
+            "Crossover probability must be between 0 and 1"
+        );
+
+        let mutation_rate = if adaptive_rates && mutation_rate == 0.0 {
+            (1.0 / graph.order().max(1) as f64).clamp(MUTATION_RATE_RANGE.0, MUTATION_RATE_RANGE.1)
+        } else {
+            mutation_rate
+        };
+        let share = 1.0 / operators.len() as f64;
+
+        Self {
+            operators: operators.iter().map(|&op| (op, op.build(1.0))).collect(),
+            probabilities: vec![share; operators.len()],
+            quality: vec![share; operators.len()],
+            crossover_rate,
+            mutation: Mutation::new(mutation_rate),
+            adaptive_rates,
+            problem,
+            crossover_stats: operators.iter().map(|_| Counter::default()).collect(),
+            mutation_stats: Counter::default(),
+        }
+    }
+
+    /// Returns the current crossover rate.
+    #[inline]
+    #[must_use]
+    pub fn crossover_rate(&self) -> f64 {
+        self.crossover_rate
+    }
+
+    /// Returns the current per-gene mutation rate.
+    #[inline]
+    #[must_use]
+    pub fn mutation_rate(&self) -> f64 {
+        self.mutation.rate()
+    }
+
+    /// Returns the operators of the portfolio with their current probabilities.
+    pub fn probabilities(&self) -> impl Iterator<Item = (CrossoverOperator, f64)> + '_ {
+        self.operators
+            .iter()
+            .map(|(op, _)| *op)
+            .zip(self.probabilities.iter().copied())
+    }
+
+    /// Updates the rates and operator probabilities from the successes recorded since the
+    /// last call, and resets the counters.
+    pub fn adapt(&mut self) {
+        let ratios: Vec<Option<f64>> = self
+            .crossover_stats
+            .iter()
+            .map(Counter::take_ratio)
+            .collect();
+        let mutation_ratio = self.mutation_stats.take_ratio();
+
+        if self.adaptive_rates {
+            let attempted: Vec<f64> = ratios.iter().flatten().copied().collect();
+            if !attempted.is_empty() {
+                #[allow(clippy::cast_precision_loss)]
+                let ratio = attempted.iter().sum::<f64>() / attempted.len() as f64;
+                self.crossover_rate =
+                    one_fifth(self.crossover_rate, ratio).clamp(MIN_CROSSOVER_RATE, 1.0);
+            }
+            if let Some(ratio) = mutation_ratio {
+                let rate = one_fifth(self.mutation.rate(), ratio)
+                    .clamp(MUTATION_RATE_RANGE.0, MUTATION_RATE_RANGE.1);
+                self.mutation = Mutation::new(rate);
+            }
+        }
+
+        if self.operators.len() > 1 {
+            for (quality, ratio) in self.quality.iter_mut().zip(&ratios) {
+                if let Some(ratio) = ratio {
+                    *quality += LEARNING_RATE * (ratio - *quality);
+                }
+            }
+
+            let total: f64 = self.quality.iter().sum();
+            if total > 0.0 {
+                #[allow(clippy::cast_precision_loss)]
+                let count = self.operators.len() as f64;
+                let floor = MIN_PROBABILITY.min(0.5 / count);
+                let free = 1.0 - floor * count;
+                for (probability, quality) in self.probabilities.iter_mut().zip(&self.quality) {
+                    *probability = floor + free * quality / total;
+                }
+            }
+        }
+    }
+
+    fn pick_operator(&self, rng: &mut dyn RngCore) -> usize {
+        if self.operators.len() == 1 {
+            return 0;
+        }
+
+        let mut pointer = rng.gen_range(0.0..1.0);
+        for (idx, probability) in self.probabilities.iter().enumerate() {
+            if pointer < *probability {
+                return idx;
+            }
+            pointer -= probability;
+        }
+        self.operators.len() - 1
+    }
+}
+
+impl Crossover for Variation {
+    fn crossover(
+        &self,
+        parent1: &Chromosome,
+        parent2: &Chromosome,
+        graph: &CsrGraph,
+        rng: &mut dyn RngCore,
+    ) -> (Chromosome, Chromosome) {
+        let idx = self.pick_operator(rng);
+
+        let (mut child1, mut child2) = if rng.gen_bool(self.crossover_rate) {
+            let mut children = self.operators[idx]
+                .1
+                .crossover(parent1, parent2, graph, rng);
+            self.problem.repair(&mut children.0, graph);
+            self.problem.repair(&mut children.1, graph);
+
+            // Sucesso: filho melhor que a média dos pais.
+            let parents_sum = parent1.fitness() + parent2.fitness();
+            self.crossover_stats[idx].record(2 * children.0.fitness() < parents_sum);
+            self.crossover_stats[idx].record(2 * children.1.fitness() < parents_sum);
+            children
+        } else {
+            (parent1.clone(), parent2.clone())
+        };
+
+        for child in [&mut child1, &mut child2] {
+            let before = child.fitness();
+            if self
+                .mutation
+                .mutate(child, self.problem.max_label(), graph, rng)
+            {
+                self.problem.repair(child, graph);
+                self.mutation_stats.record(child.fitness() < before);
+            }
+        }
+
+        (child1, child2)
+    }
+}
+
+/// Applies the 1/5th success rule to `rate`.
+fn one_fifth(rate: f64, success_ratio: f64) -> f64 {
+    if success_ratio > 0.2 {
+        rate / ONE_FIFTH_FACTOR
+    } else {
+        rate * ONE_FIFTH_FACTOR
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genetic::TotalRomanDomination;
+
+    fn variation(operators: &[CrossoverOperator], adaptive_rates: bool) -> Variation {
+        let graph = CsrGraph::circulant(10, &[1]);
+        Variation::new(
+            operators,
+            0.5,
+            0.1,
+            adaptive_rates,
+            Arc::new(TotalRomanDomination),
+            &graph,
+        )
+    }
+
+    fn feed(counter: &Counter, attempts: usize, successes: usize) {
+        for attempt in 0..attempts {
+            counter.record(attempt < successes);
+        }
+    }
+
+    fn assert_close(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-12, "{a} != {b}");
+    }
+
+    #[test]
+    fn one_fifth_rule_grows_rates_above_a_fifth_and_shrinks_them_otherwise() {
+        let mut variation = variation(&[CrossoverOperator::SinglePoint], true);
+
+        feed(&variation.crossover_stats[0], 10, 3);
+        feed(&variation.mutation_stats, 10, 5);
+        variation.adapt();
+        assert_close(variation.crossover_rate(), 0.5 / ONE_FIFTH_FACTOR);
+        assert_close(variation.mutation_rate(), 0.1 / ONE_FIFTH_FACTOR);
+
+        // Uma razão de exatamente 1/5 conta como fracasso.
+        feed(&variation.crossover_stats[0], 10, 2);
+        feed(&variation.mutation_stats, 10, 0);
+        variation.adapt();
+        assert_close(variation.crossover_rate(), 0.5);
+        assert_close(variation.mutation_rate(), 0.1);
+
+        // Sem tentativas, as taxas não mudam.
+        variation.adapt();
+        assert_close(variation.crossover_rate(), 0.5);
+        assert_close(variation.mutation_rate(), 0.1);
+    }
+
+    #[test]
+    fn one_fifth_rule_keeps_rates_within_their_bounds() {
+        let mut variation = variation(&[CrossoverOperator::SinglePoint], true);
+        for _ in 0..100 {
+            feed(&variation.crossover_stats[0], 4, 4);
+            feed(&variation.mutation_stats, 4, 4);
+            variation.adapt();
+            assert!(variation.crossover_rate() <= 1.0);
+            assert!(variation.mutation_rate() <= MUTATION_RATE_RANGE.1);
+        }
+        assert_close(variation.crossover_rate(), 1.0);
+        assert_close(variation.mutation_rate(), MUTATION_RATE_RANGE.1);
+
+        for _ in 0..100 {
+            feed(&variation.crossover_stats[0], 4, 0);
+            feed(&variation.mutation_stats, 4, 0);
+            variation.adapt();
+            assert!(variation.crossover_rate() >= MIN_CROSSOVER_RATE);
+            assert!(variation.mutation_rate() >= MUTATION_RATE_RANGE.0);
+        }
+        assert_close(variation.crossover_rate(), MIN_CROSSOVER_RATE);
+        assert_close(variation.mutation_rate(), MUTATION_RATE_RANGE.0);
+    }
+
+    #[test]
+    fn rates_are_fixed_without_adaptive_rates() {
+        let mut variation = variation(&[CrossoverOperator::SinglePoint], false);
+        feed(&variation.crossover_stats[0], 10, 10);
+        feed(&variation.mutation_stats, 10, 10);
+        variation.adapt();
+        assert_close(variation.crossover_rate(), 0.5);
+        assert_close(variation.mutation_rate(), 0.1);
+    }
+
+    #[test]
+    fn probability_matching_sums_to_one_above_the_floor() {
+        let portfolios = [
+            vec![
+                CrossoverOperator::SinglePoint,
+                CrossoverOperator::TwoPoint,
+                CrossoverOperator::BfsBall,
+            ],
+            (1..=12).map(CrossoverOperator::KPoint).collect(),
+        ];
+
+        for operators in portfolios {
+            let mut variation = variation(&operators, false);
+            #[allow(clippy::cast_precision_loss)]
+            let floor = MIN_PROBABILITY.min(0.5 / operators.len() as f64);
+
+            for _ in 0..50 {
+                // O primeiro operador sempre acerta e os demais nunca.
+                feed(&variation.crossover_stats[0], 10, 10);
+                for counter in &variation.crossover_stats[1..] {
+                    feed(counter, 10, 0);
+                }
+                variation.adapt();
+
+                let probabilities: Vec<f64> = variation.probabilities().map(|(_, p)| p).collect();
+                assert_close(probabilities.iter().sum(), 1.0);
+                assert!(probabilities.iter().all(|&p| p >= floor - 1e-12));
+            }
+
+            let probabilities: Vec<f64> = variation.probabilities().map(|(_, p)| p).collect();
+            assert!(probabilities[1..].iter().all(|&p| p < probabilities[0]));
+            assert!(probabilities[1..].iter().all(|&p| (p - floor).abs() < 1e-3));
+        }
+    }
+
+    #[test]
+    fn operators_without_attempts_keep_their_quality() {
+        let mut variation = variation(
+            &[
+                CrossoverOperator::SinglePoint,
+                CrossoverOperator::TwoPoint,
+                CrossoverOperator::Uniform(0.5),
+            ],
+            false,
+        );
+        let initial = variation.quality.clone();
+
+        feed(&variation.crossover_stats[0], 10, 10);
+        feed(&variation.crossover_stats[2], 10, 0);
+        variation.adapt();
+
+        assert_close(
+            variation.quality[0],
+            initial[0] + LEARNING_RATE * (1.0 - initial[0]),
+        );
+        assert_close(variation.quality[1], initial[1]);
+        assert_close(variation.quality[2], initial[2] * (1.0 - LEARNING_RATE));
+    }
+}
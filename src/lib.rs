@@ -8,9 +8,45 @@
 //!
 //! ## Modules
 //! - `chromosome`: Defines the structure and operations for chromosomes.
+//! - `csr`: Immutable compressed sparse row view of the input graph.
+//! - `solver`: Runs the genetic algorithm in generational or island mode.
+//! - `best_known`: Registry of best-known values used to report gaps and new records.
+//! - `config`: Reading and writing solver configurations as `key = value` files.
+//! - `tuning`: Racing procedure that tunes solver parameters over instance sets.
+//! - `exact`: Exact solvers for small components.
+//! - `reduction`: Safe reduction rules that shrink an instance before solving.
+//! - `treewidth`: Tree decompositions and exact dynamic programming over them.
+//! - `generators`: Seeded generators of synthetic graph families.
 
 /// Implementation of genetic operators
 pub mod genetic;
 
+/// Compact graph representation shared by the genetic operators
+pub mod csr;
+
+/// Genetic algorithm driver
+pub mod solver;
+
+/// Best-known values for benchmark instances
+pub mod best_known;
+
+/// Solver configuration files
+pub mod config;
+
+/// Automatic parameter tuning
+pub mod tuning;
+
+/// Exact solvers for small graphs
+pub mod exact;
+
+/// Instance reduction rules
+pub mod reduction;
+
+/// Dynamic programming over tree decompositions
+pub mod treewidth;
+
+/// Synthetic graph generators
+pub mod generators;
+
 /// Graph utils
 pub mod utils;
@@ -1,21 +1,28 @@
 use std::{
     env::{self},
     ffi::OsStr,
-    fs::OpenOptions,
-    io::{self, Write},
+    fs::{self, File, OpenOptions},
+    io::{self, BufRead, BufReader, Write},
     path::Path,
     process::exit,
+    str::FromStr,
     sync::Mutex,
     time::Instant,
 };
 
 use cl_total_rdga::{
-    genetic::{h1, h2, h3, h4, h5, Heuristic, KTournament, Population, SinglePoint},
-    utils::build_graph,
+    best_known::{gap, BestKnownRegistry, Standing},
+    csr::CsrGraph,
+    exact::recognize,
+    generators::{write_edge_list, GraphFamily},
+    genetic::{HeuristicRegistry, ProblemVariant},
+    solver::{solve, ComponentReport, IslandParams, Mode, SolverConfig},
+    tuning::{race, Parameter, RaceSettings},
+    utils::build_weighted_graph,
 };
 use env_logger::{Builder, Target};
-use kambo_graph::{graphs::simple::UndirectedGraph, Graph};
-use log::{debug, error, info, LevelFilter};
+use log::{debug, error, info, warn, LevelFilter};
+use rand::{rngs::StdRng, SeedableRng};
 use rayon::{
     iter::{IntoParallelIterator, ParallelIterator},
     ThreadPoolBuilder,
@@ -23,38 +30,54 @@ use rayon::{
 
 #[derive(Debug)]
 struct AlgorithmParams {
-    max_stagnant: usize,
-    generations: usize,
-    tournament_size: usize,
-    crossover_rate: f64,
-    population_factor: f64,
+    solver: SolverConfig,
     file_path: String,
+    weights_file: Option<String>,
     trials: usize,
     output_file: String,
     num_threads: usize,
+    best_known_file: Option<String>,
+}
+
+#[derive(Debug)]
+struct TuneParams {
+    base: SolverConfig,
+    instances_file: String,
+    parameters: Vec<Parameter>,
+    settings: RaceSettings,
+    output_file: String,
+}
+
+#[derive(Debug)]
+struct GenerateParams {
+    family: GraphFamily,
+    seed: u64,
+    count: usize,
+    output_file: Option<String>,
 }
 
 #[derive(Debug)]
 struct TrialResult {
     graph_name: String,
+    trial: usize,
     node_count: usize,
     edge_count: usize,
     fitness: usize,
+    best_known: Option<usize>,
     elapsed_micros: u128,
+    components: Vec<ComponentReport>,
 }
 
 impl Default for AlgorithmParams {
     fn default() -> Self {
         Self {
-            max_stagnant: 100,
-            generations: 1000,
-            tournament_size: 5,
-            crossover_rate: 0.9,
-            population_factor: 1.5,
+            solver: SolverConfig::default(),
             file_path: String::new(),
+            weights_file: None,
             trials: 1,
             output_file: String::from("results.csv"),
             num_threads: 1,
+            best_known_file: None,
         }
     }
 }
@@ -88,31 +111,95 @@ fn parse_args() -> Result<AlgorithmParams, String> {
 
     if args.len() < 2 {
         return Err("Usage: ./cl-total-rdga <graph_file> [options]\n\
+            \x20      ./cl-total-rdga tune --instances FILE --param KEY=RANGE ... [options]\n\
+            \x20      ./cl-total-rdga generate FAMILY [--seed VALUE] [--count VALUE] [--output FILE]\n\
             Options:\n\
+            --config FILE\n\
+            --weights FILE\n\
+            --problem total-roman|roman|double-roman|total-double-roman|italian|total-italian|\n\
+            \x20         independent-roman|perfect-roman\n\
             --crossover VALUE\n\
+            --crossover-op single|two|k:N|uniform[:BIAS]|bfs|region[:N]\n\
+            --crossover-portfolio OP,OP,...\n\
+            --mutation VALUE\n\
+            --adaptive-rates\n\
             --stagnation VALUE\n\
             --generations VALUE\n\
             --population VALUE\n\
             --tournament VALUE\n\
+            --selection tournament|tournament-distinct|roulette|rank[:PRESSURE]|sus\n\
             --trials VALUE\n\
-            --output FILE"
+            --output FILE\n\
+            --best-known FILE\n\
+            --mode generational|island|grasp\n\
+            --islands VALUE\n\
+            --topology ring|full\n\
+            --migration-interval VALUE\n\
+            --migration-size VALUE\n\
+            --alpha VALUE\n\
+            --heuristics NAME:WEIGHT,...\n\
+            --dedup\n\
+            --diversity-threshold VALUE\n\
+            --diversity-response immigration[:FRACTION]|restart\n\
+            --no-reduce\n\
+            --max-treewidth VALUE\n\
+            --max-dp-states VALUE\n\
+            --elimination min-degree|min-fill\n\
+            --seed VALUE"
             .to_string());
     }
 
     params.file_path = args[1].clone();
 
+    // O arquivo de configuração é aplicado antes das demais opções, que o sobrescrevem.
+    if let Some(path) = config_path(&args)? {
+        params.solver = SolverConfig::from_file(path)?;
+    }
+    let mut island_params = match &params.solver.mode {
+        Mode::Island(params) => params.clone(),
+        _ => IslandParams::default(),
+    };
+
     let mut i = 2;
     while i < args.len() {
         match args[i].as_str() {
+            "--config" => i += 2,
+            "--problem" => {
+                params.solver.problem = option_value(&args, i)?.parse()?;
+                i += 2;
+            }
             "--crossover" => {
                 if i + 1 < args.len() {
-                    params.crossover_rate = args[i + 1]
+                    params.solver.crossover_rate = args[i + 1]
                         .parse()
                         .map_err(|_| format!("Invalid crossover value: {}", args[i + 1]))?;
                     i += 2;
                 } else {
-                    params.crossover_rate = 0.75;
+                    params.solver.crossover_rate = 0.75;
+                }
+            }
+            "--crossover-op" => {
+                params.solver.crossover = option_value(&args, i)?.parse()?;
+                i += 2;
+            }
+            "--crossover-portfolio" => {
+                params.solver.crossover_portfolio = option_value(&args, i)?
+                    .split(',')
+                    .map(str::parse)
+                    .collect::<Result<_, _>>()?;
+                i += 2;
+            }
+            "--mutation" => {
+                let rate: f64 = parse_option(&args, i)?;
+                if !(0.0..=1.0).contains(&rate) {
+                    return Err("--mutation must be between 0 and 1".to_string());
                 }
+                params.solver.mutation_rate = rate;
+                i += 2;
+            }
+            "--adaptive-rates" => {
+                params.solver.adaptive_rates = true;
+                i += 1;
             }
             "--parallel" => {
                 if i + 1 < args.len() {
@@ -126,44 +213,48 @@ fn parse_args() -> Result<AlgorithmParams, String> {
             }
             "--stagnation" => {
                 if i + 1 < args.len() {
-                    params.max_stagnant = args[i + 1]
+                    params.solver.max_stagnant = args[i + 1]
                         .parse()
                         .map_err(|_| format!("Invalid stagnation value: {}", args[i + 1]))?;
                     i += 2;
                 } else {
-                    params.max_stagnant = 100;
+                    params.solver.max_stagnant = 100;
                 }
             }
             "--generations" => {
                 if i + 1 < args.len() {
-                    params.generations = args[i + 1]
+                    params.solver.generations = args[i + 1]
                         .parse()
                         .map_err(|_| format!("Invalid generations value: {}", args[i + 1]))?;
                     i += 2;
                 } else {
-                    params.generations = 1000;
+                    params.solver.generations = 1000;
                 }
             }
             "--population" => {
                 if i + 1 < args.len() {
-                    params.population_factor = args[i + 1]
+                    params.solver.population_factor = args[i + 1]
                         .parse()
                         .map_err(|_| format!("Invalid population value: {}", args[i + 1]))?;
                     i += 2;
                 } else {
-                    params.population_factor = 2.5;
+                    params.solver.population_factor = 2.5;
                 }
             }
             "--tournament" => {
                 if i + 1 < args.len() {
-                    params.tournament_size = args[i + 1]
+                    params.solver.tournament_size = args[i + 1]
                         .parse()
                         .map_err(|_| format!("Invalid tournament value: {}", args[i + 1]))?;
                     i += 2;
                 } else {
-                    params.tournament_size = 2;
+                    params.solver.tournament_size = 2;
                 }
             }
+            "--selection" => {
+                params.solver.selection = option_value(&args, i)?.parse()?;
+                i += 2;
+            }
             "--trials" => {
                 if i + 1 < args.len() {
                     params.trials = args[i + 1]
@@ -182,16 +273,339 @@ fn parse_args() -> Result<AlgorithmParams, String> {
                     return Err("Missing value for --output".to_string());
                 }
             }
+            "--weights" => {
+                params.weights_file = Some(option_value(&args, i)?.to_string());
+                i += 2;
+            }
+            "--best-known" => {
+                if i + 1 < args.len() {
+                    params.best_known_file = Some(args[i + 1].clone());
+                    i += 2;
+                } else {
+                    return Err("Missing value for --best-known".to_string());
+                }
+            }
+            "--mode" => {
+                params.solver.mode = match option_value(&args, i)? {
+                    "generational" => Mode::Generational,
+                    "island" => Mode::Island(island_params.clone()),
+                    "grasp" => Mode::Grasp,
+                    other => return Err(format!("Invalid mode: {other}")),
+                };
+                i += 2;
+            }
+            "--islands" => {
+                island_params.islands = parse_option(&args, i)?;
+                i += 2;
+            }
+            "--topology" => {
+                island_params.topology = parse_option(&args, i)?;
+                i += 2;
+            }
+            "--migration-interval" => {
+                island_params.migration_interval = parse_option(&args, i)?;
+                i += 2;
+            }
+            "--migration-size" => {
+                island_params.migration_size = parse_option(&args, i)?;
+                i += 2;
+            }
+            "--alpha" => {
+                params.solver.grasp.alpha = parse_option(&args, i)?;
+                i += 2;
+            }
+            "--heuristics" => {
+                let mix = HeuristicRegistry::default().parse_mix(option_value(&args, i)?)?;
+                params.solver.heuristics = Some(mix);
+                i += 2;
+            }
+            "--dedup" => {
+                params.solver.diversity.eliminate_duplicates = true;
+                i += 1;
+            }
+            "--diversity-threshold" => {
+                params.solver.diversity.threshold = parse_option(&args, i)?;
+                i += 2;
+            }
+            "--diversity-response" => {
+                params.solver.diversity.response = option_value(&args, i)?.parse()?;
+                i += 2;
+            }
+            "--no-reduce" => {
+                params.solver.reduce = false;
+                i += 1;
+            }
+            "--max-treewidth" => {
+                params.solver.max_treewidth = parse_option(&args, i)?;
+                i += 2;
+            }
+            "--max-dp-states" => {
+                params.solver.max_dp_states = parse_option(&args, i)?;
+                i += 2;
+            }
+            "--elimination" => {
+                params.solver.elimination = option_value(&args, i)?.parse()?;
+                i += 2;
+            }
+            "--seed" => {
+                params.solver.seed = Some(parse_option(&args, i)?);
+                i += 2;
+            }
+            _ => return Err(format!("Unknown argument: {}", args[i])),
+        }
+    }
+
+    if let Mode::Island(params) = &mut params.solver.mode {
+        *params = island_params;
+        if params.islands == 0 || params.migration_interval == 0 {
+            return Err("--islands and --migration-interval must be greater than 0".to_string());
+        }
+    }
+    if !(0.0..=1.0).contains(&params.solver.grasp.alpha) {
+        return Err("--alpha must be between 0 and 1".to_string());
+    }
+
+    Ok(params)
+}
+
+fn parse_tune_args(args: &[String]) -> Result<TuneParams, String> {
+    let mut params = TuneParams {
+        base: SolverConfig::default(),
+        instances_file: String::new(),
+        parameters: Vec::new(),
+        settings: RaceSettings::default(),
+        output_file: String::from("tuned.conf"),
+    };
+
+    if let Some(path) = config_path(args)? {
+        params.base = SolverConfig::from_file(path)?;
+    }
+
+    let mut i = 2;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--config" => {}
+            "--instances" => params.instances_file = option_value(args, i)?.to_string(),
+            "--param" => params.parameters.push(option_value(args, i)?.parse()?),
+            "--candidates" => params.settings.candidates = parse_option(args, i)?,
+            "--budget" => params.settings.budget = parse_option(args, i)?,
+            "--first-test" => params.settings.first_test = parse_option(args, i)?,
+            "--seed" => params.settings.seed = parse_option(args, i)?,
+            "--output" => params.output_file = option_value(args, i)?.to_string(),
             _ => return Err(format!("Unknown argument: {}", args[i])),
         }
+        i += 2;
+    }
+
+    if params.instances_file.is_empty() {
+        return Err("Usage: ./cl-total-rdga tune --instances FILE [options]\n\
+            Options:\n\
+            --param KEY=MIN..MAX|KEY=A|B|...   (repeatable)\n\
+            --config FILE\n\
+            --candidates VALUE\n\
+            --budget VALUE\n\
+            --first-test VALUE\n\
+            --seed VALUE\n\
+            --output FILE"
+            .to_string());
+    }
+    if params.parameters.is_empty() {
+        return Err("At least one --param is required".to_string());
     }
 
     Ok(params)
 }
 
+fn config_path(args: &[String]) -> Result<Option<&str>, String> {
+    match args.iter().position(|arg| arg == "--config") {
+        Some(i) => option_value(args, i).map(Some),
+        None => Ok(None),
+    }
+}
+
+fn read_instances(list_file: &str) -> Result<Vec<(String, CsrGraph)>, String> {
+    let text =
+        fs::read_to_string(list_file).map_err(|e| format!("Failed to read {list_file}: {e}"))?;
+
+    let mut instances = Vec::new();
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if !Path::new(line).is_file() {
+            return Err(format!("Instance file not found: {line}"));
+        }
+        let graph = build_weighted_graph(line, None)?;
+        if graph.order() == 0 {
+            return Err(format!("The graph {line} has no nodes"));
+        }
+        instances.push((graph_name(line), graph));
+    }
+    Ok(instances)
+}
+
+fn tune(args: &[String]) -> Result<(), String> {
+    let params = parse_tune_args(args)?;
+    let instances = read_instances(&params.instances_file)?;
+    info!(
+        "Tuning {} parameters on {} instances",
+        params.parameters.len(),
+        instances.len()
+    );
+    for (name, graph) in &instances {
+        debug!(
+            "Training instance {} - Nodes: {}, Edges: {}",
+            name,
+            graph.order(),
+            graph.edge_count()
+        );
+    }
+
+    let graphs: Vec<CsrGraph> = instances.into_iter().map(|(_, graph)| graph).collect();
+    let start_time = Instant::now();
+    let outcome = race(&params.base, &params.parameters, &graphs, &params.settings)?;
+
+    fs::write(&params.output_file, outcome.best.to_config())
+        .map_err(|e| format!("Failed to write {}: {e}", params.output_file))?;
+
+    info!(
+        "Race finished - Runs: {}, Blocks: {}, Survivors: {}",
+        outcome.runs,
+        outcome.blocks,
+        outcome.survivors.len()
+    );
+    println!(
+        "Race finished after {} runs on {} blocks in {:.2} seconds; {} candidates survived.",
+        outcome.runs,
+        outcome.blocks,
+        start_time.elapsed().as_secs_f64(),
+        outcome.survivors.len()
+    );
+    println!("Best configuration written to {}", params.output_file);
+
+    Ok(())
+}
+
+fn parse_generate_args(args: &[String]) -> Result<GenerateParams, String> {
+    let usage = || {
+        "Usage: ./cl-total-rdga generate FAMILY [options]\n\
+            Families:\n\
+            regular:N:R | gnp:N:P | gnm:N:M | ba:N:M\n\
+            grid:ROWS:COLS | torus:ROWS:COLS | hypercube:D | tree:N\n\
+            Options:\n\
+            --seed VALUE\n\
+            --count VALUE\n\
+            --output FILE"
+            .to_string()
+    };
+
+    let family = match args.get(2) {
+        Some(family) if !family.starts_with("--") => family.parse()?,
+        _ => return Err(usage()),
+    };
+    let mut params = GenerateParams {
+        family,
+        seed: 0,
+        count: 1,
+        output_file: None,
+    };
+
+    let mut i = 3;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--seed" => params.seed = parse_option(args, i)?,
+            "--count" => params.count = parse_option(args, i)?,
+            "--output" => params.output_file = Some(option_value(args, i)?.to_string()),
+            _ => return Err(format!("Unknown argument: {}", args[i])),
+        }
+        i += 2;
+    }
+
+    if params.count == 0 {
+        return Err("--count must be greater than 0".to_string());
+    }
+    if params.count > 1 && params.output_file.is_none() {
+        return Err("--count requires --output".to_string());
+    }
+
+    Ok(params)
+}
+
+/// Inserts `_<index>` before the extension of `path`.
+fn numbered_path(path: &str, index: usize) -> String {
+    let path = Path::new(path);
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match path.extension() {
+        Some(extension) => format!("{stem}_{index}.{}", extension.to_string_lossy()),
+        None => format!("{stem}_{index}"),
+    };
+    path.with_file_name(name).to_string_lossy().to_string()
+}
+
+fn generate(args: &[String]) -> Result<(), String> {
+    let params = parse_generate_args(args)?;
+
+    for index in 0..params.count {
+        // O i-ésimo grafo usa a semente `seed + i`, como as tentativas do solver.
+        let seed = params.seed.wrapping_add(index as u64);
+        let graph = params.family.generate(&mut StdRng::seed_from_u64(seed))?;
+        info!(
+            "Generated {} with seed {} - Nodes: {}, Edges: {}",
+            params.family,
+            seed,
+            graph.order(),
+            graph.edge_count()
+        );
+
+        let comment = [
+            format!("{} seed={seed}", params.family),
+            format!("vertices: {}, edges: {}", graph.order(), graph.edge_count()),
+        ];
+        match &params.output_file {
+            Some(output_file) => {
+                let path = if params.count > 1 {
+                    numbered_path(output_file, index + 1)
+                } else {
+                    output_file.clone()
+                };
+                let mut file =
+                    File::create(&path).map_err(|e| format!("Failed to create {path}: {e}"))?;
+                write_edge_list(&graph, &comment, &mut file)
+                    .map_err(|e| format!("Failed to write {path}: {e}"))?;
+                println!("Graph written to {path}");
+            }
+            None => write_edge_list(&graph, &comment, &mut io::stdout().lock())
+                .map_err(|e| format!("Failed to write the graph: {e}"))?,
+        }
+    }
+
+    Ok(())
+}
+
+fn option_value(args: &[String], i: usize) -> Result<&str, String> {
+    args.get(i + 1)
+        .map(String::as_str)
+        .ok_or_else(|| format!("Missing value for {}", args[i]))
+}
+
+fn parse_option<T: FromStr>(args: &[String], i: usize) -> Result<T, String> {
+    let value = option_value(args, i)?;
+    value
+        .parse()
+        .map_err(|_| format!("Invalid {} value: {}", args[i], value))
+}
+
+/// Header of the results file.
+const RESULTS_HEADER: &str =
+    "graph_name,graph_order,graph_size,fitness_value,best_known,gap(%),elapsed_time(microsecond)";
+/// Header of results files written before the best-known columns existed.
+const LEGACY_RESULTS_HEADER: &str =
+    "graph_name,graph_order,graph_size,fitness_value,elapsed_time(microsecond)";
+
 fn write_results_to_csv(results: &[TrialResult], output_file: &str) -> io::Result<()> {
     let mut file = OpenOptions::new()
         .create(true)
+        .read(true)
         .append(true)
         .open(output_file)
         .map_err(|e| {
@@ -199,23 +613,62 @@ fn write_results_to_csv(results: &[TrialResult], output_file: &str) -> io::Resul
             e
         })?;
 
-    if file.metadata()?.len() == 0 {
+    // Arquivos existentes mantêm o formato do seu cabeçalho.
+    let with_best_known = if file.metadata()?.len() == 0 {
         debug!("Creating new CSV file with header");
-        writeln!(
-            file,
-            "graph_name,graph_order,graph_size,fitness_value,elapsed_time(microsecond)"
-        )?;
-    }
+        writeln!(file, "{RESULTS_HEADER}")?;
+        true
+    } else {
+        let mut header = String::new();
+        BufReader::new(&file).read_line(&mut header)?;
+        match header.trim_end() {
+            RESULTS_HEADER => true,
+            LEGACY_RESULTS_HEADER => {
+                warn!("{output_file} has no best-known columns; they are not written");
+                false
+            }
+            other => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{output_file} has an unexpected header: {other}"),
+                ))
+            }
+        }
+    };
 
     for result in results {
         debug!("Writing result: {:?}", result);
+        if !with_best_known {
+            writeln!(
+                file,
+                "{},{},{},{},{}",
+                result.graph_name,
+                result.node_count,
+                result.edge_count,
+                result.fitness,
+                result.elapsed_micros
+            )?;
+            continue;
+        }
+
+        let (best_known, gap) = result.best_known.map_or_else(
+            || (String::new(), String::new()),
+            |best| {
+                (
+                    best.to_string(),
+                    format!("{:.2}", gap(result.fitness, best)),
+                )
+            },
+        );
         writeln!(
             file,
-            "{},{},{},{},{}",
+            "{},{},{},{},{},{},{}",
             result.graph_name,
             result.node_count,
             result.edge_count,
             result.fitness,
+            best_known,
+            gap,
             result.elapsed_micros
         )?;
     }
@@ -223,12 +676,137 @@ fn write_results_to_csv(results: &[TrialResult], output_file: &str) -> io::Resul
     Ok(())
 }
 
+fn components_file(output_file: &str) -> String {
+    Path::new(output_file)
+        .with_extension("components.csv")
+        .to_string_lossy()
+        .to_string()
+}
+
+fn write_components_to_csv(results: &[TrialResult], output_file: &str) -> io::Result<()> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(output_file)
+        .map_err(|e| {
+            error!("Failed to open components file: {}", e);
+            e
+        })?;
+
+    if file.metadata()?.len() == 0 {
+        debug!("Creating new components CSV file with header");
+        writeln!(
+            file,
+            "graph_name,trial,component,component_order,component_size,fitness_value,generations,exact,class,treewidth"
+        )?;
+    }
+
+    for result in results {
+        for (index, component) in result.components.iter().enumerate() {
+            writeln!(
+                file,
+                "{},{},{},{},{},{},{},{},{},{}",
+                result.graph_name,
+                result.trial + 1,
+                index + 1,
+                component.order,
+                component.size,
+                component.fitness,
+                component.generations,
+                component.exact,
+                component
+                    .class
+                    .map_or_else(String::new, |class| class.to_string()),
+                component
+                    .treewidth
+                    .map_or_else(String::new, |width| width.to_string())
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+fn graph_name(file_path: &str) -> String {
+    Path::new(file_path)
+        .file_stem()
+        .unwrap_or_else(|| OsStr::new("unknown"))
+        .to_string_lossy()
+        .to_string()
+}
+
+fn report_summary(graph_name: &str, results: &[TrialResult], registry: &BestKnownRegistry) {
+    let Some(best) = results.iter().map(|result| result.fitness).min() else {
+        return;
+    };
+
+    match registry.standing(graph_name, best) {
+        Standing::Unknown => {
+            info!(
+                "{}: best fitness {} (no best-known value)",
+                graph_name, best
+            );
+            println!("{graph_name}: best fitness {best}");
+        }
+        Standing::Matched => {
+            info!(
+                "{}: best fitness {} matches the best known",
+                graph_name, best
+            );
+            println!("{graph_name}: best fitness {best} (best known: {best}, gap: 0.00%)");
+        }
+        Standing::Above { gap } => {
+            let known = registry.get(graph_name).map_or(best, |entry| entry.value);
+            info!(
+                "{}: best fitness {} is {:.2}% above the best known {}",
+                graph_name, best, gap, known
+            );
+            println!("{graph_name}: best fitness {best} (best known: {known}, gap: {gap:.2}%)");
+        }
+        Standing::NewRecord { previous } => {
+            warn!(
+                "{}: NEW RECORD {} improves on the best known {}",
+                graph_name, best, previous
+            );
+            println!("{graph_name}: NEW RECORD {best} (previous best known: {previous})");
+        }
+        Standing::BelowOptimum { optimum } => {
+            error!(
+                "{}: fitness {} is below the proven optimum {}",
+                graph_name, best, optimum
+            );
+            eprintln!(
+                "{graph_name}: fitness {best} is below the proven optimum {optimum}; \
+                 the solution is invalid"
+            );
+        }
+    }
+}
+
 fn main() {
     if let Err(e) = setup_logger() {
         eprintln!("Failed to setup logger: {}", e);
         exit(1);
     }
 
+    let args: Vec<String> = env::args().collect();
+    if args.get(1).map(String::as_str) == Some("tune") {
+        if let Err(e) = tune(&args) {
+            error!("Tuning failed: {}", e);
+            eprintln!("{}", e);
+            exit(1);
+        }
+        return;
+    }
+    if args.get(1).map(String::as_str) == Some("generate") {
+        if let Err(e) = generate(&args) {
+            error!("Generation failed: {}", e);
+            eprintln!("{}", e);
+            exit(1);
+        }
+        return;
+    }
+
     let params = match parse_args() {
         Ok(p) => p,
         Err(e) => {
@@ -239,8 +817,31 @@ fn main() {
 
     info!("Starting genetic algorithm execution");
 
+    let registry = match &params.best_known_file {
+        Some(path) => match BestKnownRegistry::from_file(path) {
+            Ok(registry) => {
+                info!("Loaded {} best-known values from {}", registry.len(), path);
+                registry
+            }
+            Err(e) => {
+                error!("Failed to load best-known values: {}", e);
+                eprintln!("Failed to load best-known values from {}: {}", path, e);
+                exit(1);
+            }
+        },
+        None => BestKnownRegistry::default(),
+    };
+    let graph_name = graph_name(&params.file_path);
+
     info!("Building graph from file: {}", params.file_path);
-    let graph = build_graph(&params.file_path);
+    let graph = match build_weighted_graph(&params.file_path, params.weights_file.as_deref()) {
+        Ok(graph) => graph,
+        Err(e) => {
+            error!("Failed to load vertex costs: {}", e);
+            eprintln!("{}", e);
+            exit(1);
+        }
+    };
 
     if graph.order() == 0 {
         error!("Graph has no nodes");
@@ -248,49 +849,113 @@ fn main() {
         exit(1);
     }
 
+    if let Err(e) = params.solver.validate(&graph) {
+        error!("Invalid configuration: {}", e);
+        eprintln!("{}", e);
+        exit(1);
+    }
+
+    let component_count = graph.components().len();
     info!(
-        "Graph loaded - Nodes: {}, Edges: {}",
+        "Graph loaded - Nodes: {}, Edges: {}, Components: {}, Weighted: {}",
         graph.order(),
-        graph.edge_count()
+        graph.edge_count(),
+        component_count,
+        graph.is_weighted()
     );
 
-    let pop_size = (graph.order() as f64 / params.population_factor).round() as usize;
+    if component_count == 1 {
+        if let Some(class) = recognize(&graph) {
+            info!("Graph class: {}", class);
+        }
+    }
+
+    // Os melhores valores conhecidos valem para a dominação romana total sem custos.
+    let registry = if graph.is_weighted() {
+        if !registry.is_empty() {
+            warn!("Ignoring best-known values: the graph has vertex costs");
+        }
+        BestKnownRegistry::default()
+    } else if params.solver.problem != ProblemVariant::TotalRoman {
+        if !registry.is_empty() {
+            warn!(
+                "Ignoring best-known values: they are {} optima, not {}",
+                ProblemVariant::TotalRoman,
+                params.solver.problem
+            );
+        }
+        BestKnownRegistry::default()
+    } else {
+        registry
+    };
+    let best_known = registry.get(&graph_name).map(|best| best.value);
 
-    debug!("Using population size: {}", pop_size);
+    debug!(
+        "Using population size: {}",
+        params.solver.population_size(&graph)
+    );
 
     info!("Starting {} trials", params.trials);
     let results = Mutex::new(Vec::with_capacity(params.trials));
 
     let start_time = Instant::now();
 
-    if params.num_threads > 1 {
-        // Configurar o pool de threads com o número especificado
-        ThreadPoolBuilder::new()
-            .num_threads(params.num_threads)
-            .build_global()
-            .expect("Error building thread pool");
-
-        info!(
-            "Executing trials in parallel using {} threads",
-            params.num_threads
-        );
-        (0..params.trials).into_par_iter().for_each(|trial| {
-            execute_trial(trial, &graph, &params, &results);
-        });
+    // Pool local à execução: não altera o pool global do rayon.
+    let pool_threads = if params.num_threads > 1 {
+        params.num_threads
     } else {
-        info!("Executing trials sequentially");
-        for trial in 0..params.trials {
-            execute_trial(trial, &graph, &params, &results);
+        num_cpus::get()
+    };
+    let pool = match ThreadPoolBuilder::new().num_threads(pool_threads).build() {
+        Ok(pool) => pool,
+        Err(e) => {
+            error!("Failed to build thread pool: {}", e);
+            eprintln!("Failed to build thread pool: {}", e);
+            exit(1);
         }
-    }
+    };
+
+    pool.install(|| {
+        if params.num_threads > 1 {
+            info!(
+                "Executing trials in parallel using {} threads",
+                params.num_threads
+            );
+            (0..params.trials).into_par_iter().for_each(|trial| {
+                execute_trial(trial, &graph, &params, best_known, &results);
+            });
+        } else {
+            info!(
+                "Executing trials sequentially using {} threads per trial",
+                pool_threads
+            );
+            for trial in 0..params.trials {
+                execute_trial(trial, &graph, &params, best_known, &results);
+            }
+        }
+    });
+
+    let mut results = results.into_inner().unwrap();
+    results.sort_by_key(|result| result.trial);
+    report_summary(&graph_name, &results, &registry);
 
-    let results = results.into_inner().unwrap();
     if let Err(e) = write_results_to_csv(&results, &params.output_file) {
         error!("Failed to write results: {}", e);
         eprintln!("Failed to write results to file: {}", e);
         exit(1);
     }
 
+    // O detalhamento por componente só é gravado para grafos desconexos.
+    if results.iter().any(|result| result.components.len() > 1) {
+        let components_file = components_file(&params.output_file);
+        if let Err(e) = write_components_to_csv(&results, &components_file) {
+            error!("Failed to write component results: {}", e);
+            eprintln!("Failed to write component results to file: {}", e);
+            exit(1);
+        }
+        println!("Per-component results written to {components_file}.");
+    }
+
     let total_time = start_time.elapsed();
     info!(
         "Execution completed in {:.2} seconds",
@@ -304,79 +969,78 @@ fn main() {
 
 fn execute_trial(
     trial: usize,
-    graph: &UndirectedGraph<u32>,
+    graph: &CsrGraph,
     params: &AlgorithmParams,
+    best_known: Option<usize>,
     results: &Mutex<Vec<TrialResult>>,
 ) {
     info!("Starting trial {}", trial + 1);
     let trial_start = Instant::now();
 
-    let heuristics: Vec<Heuristic> = vec![h1, h2, h3, h4, h5, h1];
-    let crossover = SinglePoint::new(params.crossover_rate);
-    let selector = KTournament::new(params.tournament_size);
-    let pop_size = (graph.order() as f64 / params.population_factor).round() as usize;
-
-    let mut population = Population::new(pop_size, &heuristics, graph);
-    debug!("Initial population created for trial {}", trial + 1);
-
-    let mut best_solution = population
-        .best_chromosome()
-        .expect("Failed to retrieve the best individual")
-        .clone();
-
-    debug!("Initial best fitness: {}", best_solution.fitness());
-
-    let mut stagnant_generations = 0;
-    for generation in 0..params.generations {
-        population.envolve(&selector, &crossover, graph);
-        let new_best_solution = population
-            .best_chromosome()
-            .expect("Failed to retrieve the best individual")
-            .clone();
-
-        if new_best_solution.fitness() < best_solution.fitness() {
-            debug!(
-                "Trial {} - Generation {} - New best fitness: {} (improved from {})",
-                trial + 1,
-                generation + 1,
-                new_best_solution.fitness(),
-                best_solution.fitness()
-            );
-            best_solution = new_best_solution;
-            stagnant_generations = 0;
-        } else {
-            stagnant_generations += 1;
-        }
+    // Each trial gets its own seed so deterministic runs still sample independent trials.
+    let config = SolverConfig {
+        seed: params
+            .solver
+            .seed
+            .map(|seed| seed.wrapping_add(trial as u64)),
+        ..params.solver.clone()
+    };
+    let solution = solve(graph, &config);
+    let best_solution = solution.chromosome;
 
-        if stagnant_generations >= params.max_stagnant {
-            info!(
-                "Trial {} stopped at generation {} due to stagnation",
-                trial + 1,
-                generation + 1
-            );
-            break;
+    // As reduções são as mesmas em todas as tentativas: basta registrá-las uma vez.
+    if trial == 0 && params.solver.reduce {
+        match solution.reduction {
+            Some(stats) => info!(
+                "Reductions: {} - Kernel nodes: {}, Kernel edges: {}",
+                stats,
+                graph.order() - stats.removed_vertices(),
+                graph.edge_count() - stats.removed_edges
+            ),
+            None if graph.is_weighted() => {
+                info!("No reduction applies: the graph has vertex costs")
+            }
+            None => info!(
+                "No reduction rules for the {} problem",
+                params.solver.problem
+            ),
         }
     }
 
     let elapsed_time = trial_start.elapsed();
-    let graph_name = Path::new(&params.file_path)
-        .file_stem()
-        .unwrap_or_else(|| OsStr::new("unknown"))
-        .to_string_lossy()
-        .to_string();
 
     info!(
-        "Trial {} completed - Final fitness: {}, Time: {:?}",
+        "Trial {} completed - Final fitness: {}, Generations: {}, Optimal: {}, Time: {:?}",
         trial + 1,
         best_solution.fitness(),
+        solution.generations,
+        solution.components.iter().all(|component| component.exact),
         elapsed_time
     );
 
+    if solution.components.len() > 1 {
+        for (index, component) in solution.components.iter().enumerate() {
+            debug!(
+                "Trial {} component {} - Nodes: {}, Edges: {}, Fitness: {}, Generations: {}, Exact: {}",
+                trial + 1,
+                index + 1,
+                component.order,
+                component.size,
+                component.fitness,
+                component.generations,
+                component.exact
+            );
+        }
+    }
+
     results.lock().unwrap().push(TrialResult {
-        graph_name,
+        graph_name: graph_name(&params.file_path),
+        trial,
         node_count: graph.order(),
         edge_count: graph.edge_count(),
         fitness: best_solution.fitness(),
+        best_known,
         elapsed_micros: elapsed_time.as_micros(),
+        components: solution.components,
     });
 }
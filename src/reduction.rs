@@ -0,0 +1,454 @@
+use std::{collections::HashMap, fmt};
+
+use crate::{csr::CsrGraph, genetic::Chromosome, genetic::ProblemVariant};
+
+/// Statistics of the reductions applied to a graph.
+///
+/// # Fields
+/// - `forced_supports: usize`: Number of support vertices with at least two leaves, which
+///   the leaf rule labels `2`.
+/// - `removed_leaves: usize`: Number of leaves removed by the leaf rule.
+/// - `twin_classes: usize`: Number of classes of false twins (non-adjacent vertices of
+///   degree at least 2 with the same neighbourhood) reduced by the twin rule.
+/// - `removed_twins: usize`: Number of twins removed by the twin rule.
+/// - `removed_edges: usize`: Number of edges removed from the graph.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ReductionStats {
+    /// Support vertices forced to label `2`.
+    pub forced_supports: usize,
+    /// Leaves removed.
+    pub removed_leaves: usize,
+    /// Classes of false twins reduced.
+    pub twin_classes: usize,
+    /// Twins removed.
+    pub removed_twins: usize,
+    /// Edges removed.
+    pub removed_edges: usize,
+}
+
+impl ReductionStats {
+    /// Returns the number of vertices removed from the graph.
+    #[must_use]
+    pub fn removed_vertices(&self) -> usize {
+        self.removed_leaves + self.removed_twins
+    }
+}
+
+impl fmt::Display for ReductionStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} forced support vertices ({} leaves removed), {} twin classes ({} twins removed), \
+             {} vertices and {} edges removed",
+            self.forced_supports,
+            self.removed_leaves,
+            self.twin_classes,
+            self.removed_twins,
+            self.removed_vertices(),
+            self.removed_edges
+        )
+    }
+}
+
+/// A reduced instance: the kernel handed to the solver and what is needed to lift its
+/// solutions back to the original graph.
+///
+/// Two rules are applied:
+/// - Leaf rule: some optimal solution labels a support vertex of two or more leaves with
+///   `2` and at most one of its leaves positive. Two leaves are enough to force this in
+///   the kernel, so the other leaves are removed and labelled `0`.
+/// - Twin rule: for a class of `k` false twins of degree at least 2, i.e. non-adjacent
+///   vertices with the same neighbourhood `N`, some optimal solution labels at most one
+///   twin positive and some vertex of `N` with `2`, for `k >= 2` under total Roman
+///   domination and `k >= 3` under Roman domination. Keeping `k` twins of every larger
+///   class therefore preserves the optimum, and the removed twins are labelled `0`.
+#[derive(Clone, Debug)]
+pub struct Kernel {
+    graph: CsrGraph,
+    problem: ProblemVariant,
+    vertex_map: Vec<u32>,
+    /// Support vertices of the leaf rule and their kept leaves, in kernel numbering.
+    supports: Vec<(u32, [u32; 2])>,
+    /// Twins of each class kept in the kernel, in kernel numbering.
+    classes: Vec<Vec<u32>>,
+    forced: Vec<(u32, u8)>,
+    stats: ReductionStats,
+}
+
+impl Kernel {
+    /// Returns the reduced graph.
+    #[must_use]
+    pub fn graph(&self) -> &CsrGraph {
+        &self.graph
+    }
+
+    /// Returns the reduction statistics.
+    #[must_use]
+    pub fn stats(&self) -> ReductionStats {
+        self.stats
+    }
+
+    /// Returns `true` if no reduction applies, in which case the kernel is the original
+    /// graph and lifting leaves solutions unchanged.
+    #[must_use]
+    pub fn is_trivial(&self) -> bool {
+        self.supports.is_empty() && self.classes.is_empty()
+    }
+
+    /// Returns the vertex of the original graph that each kernel vertex stands for.
+    #[must_use]
+    pub fn vertex_map(&self) -> &[u32] {
+        &self.vertex_map
+    }
+
+    /// Returns the labels fixed by the reduction, in the numbering of the original graph:
+    /// `2` for the support vertices of the leaf rule and `0` for the removed leaves and
+    /// twins.
+    #[must_use]
+    pub fn forced_labels(&self) -> &[(u32, u8)] {
+        &self.forced
+    }
+
+    /// Lifts a feasible solution of the kernel to a feasible solution of the original
+    /// graph that costs at most as much.
+    ///
+    /// The kernel solution is normalised first. Each support vertex of the leaf rule is
+    /// raised to `2` and its kept leaves are lowered to `0`, except that under total Roman
+    /// domination one leaf keeps label `1` if the support vertex has no other positive
+    /// neighbour. In each twin class, the twin with the largest label is kept, the other
+    /// twins are lowered to `0` and, if no vertex of `N` is labelled `2`, one is raised to
+    /// `2`. The removed vertices are then labelled `0`.
+    ///
+    /// # Parameters
+    /// - `chromosome: &Chromosome`: A feasible solution of the kernel.
+    /// - `graph: &CsrGraph`: The original graph.
+    ///
+    /// # Returns
+    /// - A chromosome of the original graph, with tracked neighbour counts.
+    ///
+    /// # Panics
+    /// Panics if the chromosome does not label the kernel.
+    #[must_use]
+    pub fn lift(&self, chromosome: &Chromosome, graph: &CsrGraph) -> Chromosome {
+        assert_eq!(
+            chromosome.genes().len(),
+            self.graph.order(),
+            "The chromosome does not label the kernel"
+        );
+
+        let mut genes = chromosome.genes().to_vec();
+        for &(support, leaves) in &self.supports {
+            let partner = self
+                .graph
+                .neighbors(support)
+                .iter()
+                .any(|&u| !leaves.contains(&u) && genes[u as usize] > 0);
+            genes[support as usize] = 2;
+            genes[leaves[0] as usize] =
+                u8::from(self.problem == ProblemVariant::TotalRoman && !partner);
+            genes[leaves[1] as usize] = 0;
+        }
+
+        for class in &self.classes {
+            let Some(&best) = class.iter().max_by_key(|&&v| genes[v as usize]) else {
+                continue;
+            };
+            for &v in class {
+                if v != best {
+                    genes[v as usize] = 0;
+                }
+            }
+
+            // Os gêmeos têm a mesma vizinhança: a de qualquer um deles serve.
+            let common = self.graph.neighbors(best);
+            if !common.iter().any(|&u| genes[u as usize] == 2) {
+                let raised = common
+                    .iter()
+                    .copied()
+                    .find(|&u| genes[u as usize] > 0)
+                    .unwrap_or(common[0]);
+                genes[raised as usize] = 2;
+            }
+        }
+
+        let mut lifted = vec![0u8; graph.order()];
+        for (&v, &label) in self.vertex_map.iter().zip(&genes) {
+            lifted[v as usize] = label;
+        }
+
+        let mut chromosome = Chromosome::new(lifted, graph);
+        chromosome.track_neighbors(graph);
+        chromosome
+    }
+}
+
+/// Number of twins of a class kept in the kernel, or `None` if the reductions are not
+/// known to be safe for the problem.
+fn kept_twins(problem: ProblemVariant) -> Option<usize> {
+    match problem {
+        ProblemVariant::TotalRoman => Some(2),
+        ProblemVariant::Roman => Some(3),
+        _ => None,
+    }
+}
+
+/// Returns `true` if the reduction rules are implemented for `problem` on `graph`: total
+/// Roman or Roman domination on a graph without vertex costs.
+#[must_use]
+pub fn has_rules(graph: &CsrGraph, problem: ProblemVariant) -> bool {
+    kept_twins(problem).is_some() && !graph.is_weighted()
+}
+
+/// Applies the safe reduction rules to `graph`; see [`Kernel`].
+///
+/// The rules are implemented for total Roman and Roman domination on graphs without
+/// vertex costs. For other problems, or on graphs with costs, the kernel is the original
+/// graph.
+///
+/// # Parameters
+/// - `graph: &CsrGraph`: The graph to reduce.
+/// - `problem: ProblemVariant`: The problem to solve on the graph.
+///
+/// # Returns
+/// - The kernel, which may be trivial; see [`Kernel::is_trivial`].
+#[must_use]
+pub fn reduce(graph: &CsrGraph, problem: ProblemVariant) -> Kernel {
+    let identity = || Kernel {
+        graph: graph.clone(),
+        problem,
+        vertex_map: graph.vertices().collect(),
+        supports: Vec::new(),
+        classes: Vec::new(),
+        forced: Vec::new(),
+        stats: ReductionStats::default(),
+    };
+    let Some(keep) = kept_twins(problem) else {
+        return identity();
+    };
+    if graph.is_weighted() {
+        return identity();
+    }
+
+    let mut stats = ReductionStats::default();
+    let mut forced = Vec::new();
+    let mut removed = vec![false; graph.order()];
+
+    // Regra das folhas: o suporte de duas ou mais folhas recebe 2 e mantém duas folhas.
+    let mut supports = Vec::new();
+    for support in graph.vertices() {
+        let leaves: Vec<u32> = graph
+            .neighbors(support)
+            .iter()
+            .copied()
+            .filter(|&u| graph.degree(u) == 1)
+            .collect();
+        if leaves.len() < 2 {
+            continue;
+        }
+        stats.forced_supports += 1;
+        forced.push((support, 2));
+        for &leaf in &leaves[2..] {
+            removed[leaf as usize] = true;
+            stats.removed_leaves += 1;
+            stats.removed_edges += 1;
+            forced.push((leaf, 0));
+        }
+        supports.push((support, [leaves[0], leaves[1]]));
+    }
+
+    // Regra dos gêmeos falsos: vértices com a mesma vizinhança aberta, que nunca são
+    // adjacentes. As folhas já foram tratadas pela regra anterior.
+    let mut twins: HashMap<&[u32], Vec<u32>> = HashMap::new();
+    for v in graph.vertices().filter(|&v| graph.degree(v) > 1) {
+        twins.entry(graph.neighbors(v)).or_default().push(v);
+    }
+    let mut classes: Vec<Vec<u32>> = twins
+        .into_values()
+        .filter(|class| class.len() >= keep)
+        .collect();
+    if supports.is_empty() && classes.is_empty() {
+        return identity();
+    }
+    classes.sort_unstable();
+
+    stats.twin_classes = classes.len();
+    for class in &classes {
+        for &v in &class[keep..] {
+            removed[v as usize] = true;
+            stats.removed_twins += 1;
+            stats.removed_edges += graph.degree(v);
+            forced.push((v, 0));
+        }
+    }
+    forced.sort_unstable();
+
+    let vertex_map: Vec<u32> = graph.vertices().filter(|&v| !removed[v as usize]).collect();
+    let mut kernel_index = vec![u32::MAX; graph.order()];
+    for (index, &v) in (0u32..).zip(&vertex_map) {
+        kernel_index[v as usize] = index;
+    }
+
+    let index = |v: u32| kernel_index[v as usize];
+    Kernel {
+        graph: graph.induced_subgraph(&vertex_map),
+        problem,
+        supports: supports
+            .iter()
+            .map(|&(support, [first, second])| (index(support), [index(first), index(second)]))
+            .collect(),
+        classes: classes
+            .iter()
+            .map(|class| class[..keep].iter().map(|&v| index(v)).collect())
+            .collect(),
+        vertex_map,
+        forced,
+        stats,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::*;
+    use crate::exact::brute_force;
+
+    const PROBLEMS: [ProblemVariant; 2] = [ProblemVariant::TotalRoman, ProblemVariant::Roman];
+
+    fn cost(chromosome: &Chromosome) -> usize {
+        chromosome
+            .genes()
+            .iter()
+            .map(|&label| usize::from(label))
+            .sum()
+    }
+
+    /// A random graph on at most 8 vertices with pendant leaves and false twins.
+    fn random_graph(rng: &mut StdRng) -> CsrGraph {
+        let mut order = rng.gen_range(2..=4);
+        let mut edges: Vec<(u32, u32)> = (0..order)
+            .flat_map(|u| (u + 1..order).map(move |v| (u, v)))
+            .filter(|_| rng.gen_bool(0.6))
+            .collect();
+
+        while order < 8 {
+            let v = rng.gen_range(0..order);
+            let neighbors: Vec<u32> = edges
+                .iter()
+                .filter_map(|&(a, b)| (a == v).then_some(b).or((b == v).then_some(a)))
+                .collect();
+            if rng.gen_bool(0.5) || neighbors.is_empty() {
+                edges.push((v, order));
+            } else {
+                edges.extend(neighbors.iter().map(|&u| (u, order)));
+            }
+            order += 1;
+            if rng.gen_bool(0.2) {
+                break;
+            }
+        }
+        CsrGraph::from_edges(order as usize, &edges)
+    }
+
+    #[test]
+    fn leaf_rule_forces_the_support_of_a_star() {
+        let edges: Vec<(u32, u32)> = (1..6).map(|v| (0, v)).collect();
+        let graph = CsrGraph::from_edges(6, &edges);
+
+        for (problem, optimum) in [(ProblemVariant::TotalRoman, 3), (ProblemVariant::Roman, 2)] {
+            let kernel = reduce(&graph, problem);
+            assert_eq!(
+                kernel.stats(),
+                ReductionStats {
+                    forced_supports: 1,
+                    removed_leaves: 3,
+                    removed_edges: 3,
+                    ..ReductionStats::default()
+                }
+            );
+            assert_eq!(kernel.graph().order(), 3);
+            assert_eq!(kernel.forced_labels(), &[(0, 2), (3, 0), (4, 0), (5, 0)]);
+
+            let solution = brute_force(&*problem.build(), kernel.graph()).unwrap();
+            let lifted = kernel.lift(&solution, &graph);
+            assert!(problem.build().is_feasible(&lifted, &graph));
+            assert_eq!(lifted.genes()[0], 2);
+            assert_eq!(cost(&lifted), optimum);
+        }
+    }
+
+    #[test]
+    fn twin_rule_keeps_two_or_three_twins() {
+        // K_{2,6} mais a aresta 0-1: os seis vértices do lado maior são gêmeos falsos de
+        // grau 2, e 0 e 1 são adjacentes, logo não são gêmeos falsos.
+        let mut edges: Vec<(u32, u32)> = (2..8).flat_map(|v| [(0, v), (1, v)]).collect();
+        edges.push((0, 1));
+        let graph = CsrGraph::from_edges(8, &edges);
+
+        for (problem, kept) in [(ProblemVariant::TotalRoman, 2), (ProblemVariant::Roman, 3)] {
+            let kernel = reduce(&graph, problem);
+            assert_eq!(kernel.stats().twin_classes, 1);
+            assert_eq!(kernel.stats().removed_twins, 6 - kept);
+            assert_eq!(kernel.stats().removed_edges, 2 * (6 - kept));
+            assert_eq!(kernel.graph().order(), 2 + kept);
+        }
+    }
+
+    #[test]
+    fn lifting_preserves_feasibility_and_cost() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut reduced = 0;
+        for _ in 0..500 {
+            let graph = random_graph(&mut rng);
+            for problem in PROBLEMS {
+                let kernel = reduce(&graph, problem);
+                if kernel.is_trivial() {
+                    continue;
+                }
+                reduced += 1;
+                let problem_impl = problem.build();
+
+                // A solução ótima do núcleo vira uma solução ótima do grafo original.
+                let optimum = brute_force(&*problem_impl, &graph).map(|c| cost(&c));
+                let solution = brute_force(&*problem_impl, kernel.graph());
+                assert_eq!(solution.as_ref().map(cost), optimum);
+                if let Some(solution) = solution {
+                    let lifted = kernel.lift(&solution, &graph);
+                    assert!(problem_impl.is_feasible(&lifted, &graph));
+                    assert_eq!(cost(&lifted), cost(&solution));
+                    for &(v, label) in kernel.forced_labels() {
+                        assert_eq!(lifted.genes()[v as usize], label);
+                    }
+                }
+
+                // Qualquer solução viável do núcleo custa pelo menos o mesmo depois de
+                // levada de volta.
+                for _ in 0..20 {
+                    let genes = kernel
+                        .graph()
+                        .vertices()
+                        .map(|_| rng.gen_range(0..=2))
+                        .collect();
+                    let chromosome = Chromosome::new(genes, kernel.graph());
+                    if problem_impl.is_feasible(&chromosome, kernel.graph()) {
+                        let lifted = kernel.lift(&chromosome, &graph);
+                        assert!(problem_impl.is_feasible(&lifted, &graph));
+                        assert!(cost(&lifted) <= cost(&chromosome));
+                    }
+                }
+            }
+        }
+        assert!(reduced > 100);
+    }
+
+    #[test]
+    fn other_problems_and_weighted_graphs_are_not_reduced() {
+        let edges: Vec<(u32, u32)> = (1..6).map(|v| (0, v)).collect();
+        let graph = CsrGraph::from_edges(6, &edges);
+        assert!(reduce(&graph, ProblemVariant::DoubleRoman).is_trivial());
+        assert!(reduce(&graph, ProblemVariant::Italian).is_trivial());
+
+        let weighted = graph.with_costs(vec![[1, 2]; 6]);
+        assert!(reduce(&weighted, ProblemVariant::TotalRoman).is_trivial());
+    }
+}
@@ -0,0 +1,1079 @@
+use std::{fmt, str::FromStr, sync::Arc};
+
+use log::{debug, error};
+use rand::{rngs::StdRng, thread_rng, Rng, RngCore, SeedableRng};
+use rayon::{prelude::*, ThreadPool};
+
+use crate::csr::CsrGraph;
+use crate::exact::{brute_force, solve_class, GraphClass, BRUTE_FORCE_ORDER};
+use crate::genetic::{
+    grasp, prune, Chromosome, CrossoverOperator, Grasp, Heuristic, HeuristicMix, Island,
+    IslandModel, Population, Problem, ProblemVariant, SelectionOperator, Topology, Variation, WH,
+};
+use crate::reduction::{has_rules, reduce, Kernel, ReductionStats};
+use crate::treewidth::{solve_treewidth, EliminationOrder, DEFAULT_MAX_DP_STATES, MAX_DP_WIDTH};
+
+/// How the solver organises its populations.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Mode {
+    /// A single population evolved generation by generation.
+    Generational,
+    /// Several populations evolved in parallel with periodic migration.
+    Island(IslandParams),
+    /// Greedy randomized adaptive search: repeated [`grasp`] constructions improved by
+    /// [`prune`], without evolution. Parameterised by [`SolverConfig::grasp`].
+    Grasp,
+}
+
+/// Parameters of the island model.
+///
+/// # Fields
+/// - `islands: usize`: Number of islands. The population is split evenly among them.
+/// - `topology: Topology`: Which islands exchange migrants.
+/// - `migration_interval: usize`: Number of generations between migrations.
+/// - `migration_size: usize`: Number of chromosomes each island sends per migration.
+#[derive(Clone, Debug, PartialEq)]
+pub struct IslandParams {
+    /// Number of islands.
+    pub islands: usize,
+    /// Migration topology.
+    pub topology: Topology,
+    /// Generations between migrations.
+    pub migration_interval: usize,
+    /// Chromosomes sent by each island per migration.
+    pub migration_size: usize,
+}
+
+impl Default for IslandParams {
+    fn default() -> Self {
+        Self {
+            islands: 4,
+            topology: Topology::Ring,
+            migration_interval: 10,
+            migration_size: 2,
+        }
+    }
+}
+
+/// Parameters of the GRASP constructor.
+///
+/// They configure both the GRASP mode and the `grasp` heuristic of an initial-population
+/// mix. In GRASP mode, every iteration builds as many solutions as the population size in
+/// parallel, repairs them and improves them with the pruning local search. `generations`
+/// and `max_stagnant` of [`SolverConfig`] count iterations.
+///
+/// # Fields
+/// - `alpha: f64`: Greediness of the restricted candidate list, in `[0, 1]`; see [`grasp`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct GraspParams {
+    /// Greediness of the restricted candidate list.
+    pub alpha: f64,
+}
+
+impl Default for GraspParams {
+    fn default() -> Self {
+        Self { alpha: 0.2 }
+    }
+}
+
+/// How the solver reacts when the diversity of a population drops below the threshold.
+///
+/// Parsed from and formatted as `immigration[:<fraction>]` and `restart`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DiversityResponse {
+    /// Replaces this fraction of the worst chromosomes with random immigrants.
+    Immigration(f64),
+    /// Rebuilds the population with the initial heuristics, keeping the best chromosome.
+    Restart,
+}
+
+impl FromStr for DiversityResponse {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            None if s == "immigration" => Ok(Self::Immigration(0.2)),
+            None if s == "restart" => Ok(Self::Restart),
+            Some(("immigration", fraction)) => match fraction.parse() {
+                Ok(fraction) if (0.0..=1.0).contains(&fraction) => Ok(Self::Immigration(fraction)),
+                _ => Err(format!(
+                    "Invalid immigration fraction: {fraction} (expected a value between 0 and 1)"
+                )),
+            },
+            _ => Err(format!(
+                "Invalid diversity response: {s} (expected immigration[:<fraction>] or restart)"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for DiversityResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Immigration(fraction) => write!(f, "immigration:{fraction}"),
+            Self::Restart => write!(f, "restart"),
+        }
+    }
+}
+
+/// Diversity management applied after every generation.
+///
+/// Immigrants and duplicate replacements are built by the first of
+/// [`Problem::heuristics`] (`h1` for the Roman variants) and prepared for the problem.
+///
+/// # Fields
+/// - `eliminate_duplicates: bool`: Replace chromosomes whose genes repeat those of
+///   another chromosome.
+/// - `threshold: f64`: When [`Population::diversity`] drops below this value, `response`
+///   is applied. `0` disables the trigger.
+/// - `response: DiversityResponse`: What to do when the trigger fires.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DiversityParams {
+    /// Replace duplicate genotypes.
+    pub eliminate_duplicates: bool,
+    /// Diversity below which `response` is applied.
+    pub threshold: f64,
+    /// Reaction to low diversity.
+    pub response: DiversityResponse,
+}
+
+impl Default for DiversityParams {
+    fn default() -> Self {
+        Self {
+            eliminate_duplicates: false,
+            threshold: 0.0,
+            response: DiversityResponse::Immigration(0.2),
+        }
+    }
+}
+
+/// Configuration of a single run of the solver.
+///
+/// # Fields
+/// - `problem: ProblemVariant`: The Roman domination problem to solve.
+/// - `max_stagnant: usize`: Generations without improvement before stopping.
+/// - `generations: usize`: Maximum number of generations.
+/// - `tournament_size: usize`: Number of participants in each tournament.
+/// - `selection: SelectionOperator`: The selection scheme.
+/// - `crossover_rate: f64`: Probability of applying crossover to a pair of parents.
+/// - `crossover: CrossoverOperator`: The crossover operator.
+/// - `crossover_portfolio: Vec<CrossoverOperator>`: If not empty, replaces `crossover`
+///   with several operators chosen by credit assignment; see [`Variation`].
+/// - `mutation_rate: f64`: Per-gene mutation rate applied to every child.
+/// - `adaptive_rates: bool`: Adapt the crossover and mutation rates every generation with
+///   the 1/5th success rule.
+/// - `population_factor: f64`: The population size is the graph order divided by this factor.
+/// - `mode: Mode`: How populations are organised.
+/// - `heuristics: Option<HeuristicMix>`: Weighted mix of heuristics building the initial
+///   population. If `None`, [`Problem::heuristics`] build it: for the Roman variants, `h1` to
+///   `h5` build one chromosome each and `h1` builds the rest. On graphs with vertex costs,
+///   the cost-aware [`wh`](crate::genetic::wh) builds the first chromosome.
+/// - `grasp: GraspParams`: Parameters of the GRASP mode and of the `grasp` heuristic of
+///   `heuristics`.
+/// - `diversity: DiversityParams`: Duplicate elimination and low-diversity response.
+/// - `max_treewidth: usize`: Components with a tree decomposition of at most this width
+///   are solved exactly by [`solve_treewidth`]; `0` leaves them to `mode`.
+/// - `max_dp_states: usize`: State budget of [`solve_treewidth`]; components needing
+///   more states are left to `mode`.
+/// - `elimination: EliminationOrder`: Heuristic building the tree decompositions.
+/// - `reduce: bool`: Solve the kernel left by the safe reduction rules and lift its
+///   solution back; see [`reduce`].
+/// - `seed: Option<u64>`: If set, the run is deterministic: the same seed gives the same
+///   result regardless of the number of threads.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SolverConfig {
+    /// Problem to solve.
+    pub problem: ProblemVariant,
+    /// Generations without improvement before stopping.
+    pub max_stagnant: usize,
+    /// Maximum number of generations.
+    pub generations: usize,
+    /// Number of participants in each tournament.
+    pub tournament_size: usize,
+    /// Selection scheme.
+    pub selection: SelectionOperator,
+    /// Probability of applying crossover.
+    pub crossover_rate: f64,
+    /// Crossover operator.
+    pub crossover: CrossoverOperator,
+    /// Crossover operators chosen by credit assignment.
+    pub crossover_portfolio: Vec<CrossoverOperator>,
+    /// Per-gene mutation rate.
+    pub mutation_rate: f64,
+    /// Adapt the rates with the 1/5th success rule.
+    pub adaptive_rates: bool,
+    /// Graph order divided by this factor gives the population size.
+    pub population_factor: f64,
+    /// Population organisation.
+    pub mode: Mode,
+    /// Heuristics building the initial population.
+    pub heuristics: Option<HeuristicMix>,
+    /// GRASP constructor parameters.
+    pub grasp: GraspParams,
+    /// Diversity management.
+    pub diversity: DiversityParams,
+    /// Largest treewidth solved exactly.
+    pub max_treewidth: usize,
+    /// States stored by the treewidth dynamic program.
+    pub max_dp_states: usize,
+    /// Tree decomposition heuristic.
+    pub elimination: EliminationOrder,
+    /// Apply the reduction rules before solving.
+    pub reduce: bool,
+    /// Seed for reproducible runs.
+    pub seed: Option<u64>,
+}
+
+impl Default for SolverConfig {
+    fn default() -> Self {
+        Self {
+            problem: ProblemVariant::TotalRoman,
+            max_stagnant: 100,
+            generations: 1000,
+            tournament_size: 5,
+            selection: SelectionOperator::Tournament,
+            crossover_rate: 0.9,
+            crossover: CrossoverOperator::SinglePoint,
+            crossover_portfolio: Vec::new(),
+            mutation_rate: 0.0,
+            adaptive_rates: false,
+            population_factor: 1.5,
+            mode: Mode::Generational,
+            heuristics: None,
+            grasp: GraspParams::default(),
+            diversity: DiversityParams::default(),
+            max_treewidth: 6,
+            max_dp_states: DEFAULT_MAX_DP_STATES,
+            elimination: EliminationOrder::MinFill,
+            reduce: true,
+            seed: None,
+        }
+    }
+}
+
+impl SolverConfig {
+    /// Checks that the configuration can run on `graph`.
+    ///
+    /// # Errors
+    /// Returns an error if `graph` carries vertex costs and the problem uses labels above
+    /// `2`, for which no costs are defined, or if `max_treewidth` exceeds [`MAX_DP_WIDTH`].
+    pub fn validate(&self, graph: &CsrGraph) -> Result<(), String> {
+        if self.max_treewidth > MAX_DP_WIDTH {
+            return Err(format!(
+                "The maximum treewidth must be at most {MAX_DP_WIDTH}, got {}",
+                self.max_treewidth
+            ));
+        }
+        if graph.is_weighted() && self.problem.build().max_label() > 2 {
+            return Err(format!(
+                "Vertex costs are only defined for labels 1 and 2; the {} problem uses label {}",
+                self.problem,
+                self.problem.build().max_label()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns the population size used for `graph`.
+    #[must_use]
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    pub fn population_size(&self, graph: &CsrGraph) -> usize {
+        (graph.order() as f64 / self.population_factor).round() as usize
+    }
+
+    /// Builds the variation operator (crossover and mutation) of a population.
+    fn variation(&self, problem: &Arc<dyn Problem>, graph: &CsrGraph) -> Variation {
+        let operators = if self.crossover_portfolio.is_empty() {
+            std::slice::from_ref(&self.crossover)
+        } else {
+            &self.crossover_portfolio
+        };
+        Variation::new(
+            operators,
+            self.crossover_rate,
+            self.mutation_rate,
+            self.adaptive_rates,
+            Arc::clone(problem),
+            graph,
+        )
+    }
+
+    /// Returns `true` if the variation operators change from one generation to the next.
+    fn is_adaptive(&self) -> bool {
+        self.adaptive_rates || self.crossover_portfolio.len() > 1
+    }
+
+    /// Builds an initial population of `size` chromosomes with the configured heuristics.
+    fn initial_population(
+        &self,
+        size: usize,
+        problem: &dyn Problem,
+        graph: &CsrGraph,
+        rng: &mut dyn RngCore,
+    ) -> Population {
+        if let Some(mix) = &self.heuristics {
+            let grasp: Arc<dyn Heuristic> = Arc::new(Grasp::new(self.grasp.alpha));
+            let mut mix = mix.clone();
+            mix.replace(&grasp);
+            return Population::from_mix(size, &mix, problem, graph, rng);
+        }
+
+        let mut heuristics = problem.heuristics();
+        if graph.is_weighted() {
+            heuristics.insert(0, Arc::new(WH));
+        }
+        Population::new(size, &heuristics, problem, graph, rng)
+    }
+}
+
+/// Result of a run of the solver.
+///
+/// # Fields
+/// - `chromosome: Chromosome`: The best chromosome found.
+/// - `generations: usize`: Number of generations executed; the largest over the
+///   components.
+/// - `components: Vec<ComponentReport>`: One report per connected component, ordered by
+///   smallest vertex.
+/// - `reduction: Option<ReductionStats>`: The reductions applied before solving, all zero
+///   if no rule fired, or `None` if [`SolverConfig::reduce`] is off or the graph and
+///   problem have no reduction rules; see [`has_rules`].
+#[derive(Clone, Debug)]
+pub struct Solution {
+    /// Best chromosome found.
+    pub chromosome: Chromosome,
+    /// Number of generations executed.
+    pub generations: usize,
+    /// Per-component breakdown.
+    pub components: Vec<ComponentReport>,
+    /// Reductions applied before solving.
+    pub reduction: Option<ReductionStats>,
+}
+
+/// Result of solving one connected component.
+///
+/// # Fields
+/// - `order: usize`: Number of vertices of the component.
+/// - `size: usize`: Number of edges of the component.
+/// - `fitness: usize`: Cost of the labelling of the component.
+/// - `generations: usize`: Number of generations executed, `0` if the component was not
+///   solved by the genetic algorithm.
+/// - `exact: bool`: The component was solved to optimality by
+///   [`solve_class`](crate::exact::solve_class), [`brute_force`](crate::exact::brute_force)
+///   or [`solve_treewidth`].
+/// - `class: Option<GraphClass>`: The class of the component, if it was solved by
+///   [`solve_class`](crate::exact::solve_class).
+/// - `treewidth: Option<usize>`: The width of the tree decomposition, if the component was
+///   solved by [`solve_treewidth`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ComponentReport {
+    /// Number of vertices.
+    pub order: usize,
+    /// Number of edges.
+    pub size: usize,
+    /// Cost of the labelling.
+    pub fitness: usize,
+    /// Number of generations executed.
+    pub generations: usize,
+    /// Solved to optimality.
+    pub exact: bool,
+    /// Recognised graph class.
+    pub class: Option<GraphClass>,
+    /// Width of the tree decomposition used.
+    pub treewidth: Option<usize>,
+}
+
+/// Runs the genetic algorithm on `graph` with the given configuration.
+///
+/// With [`SolverConfig::reduce`], the solver runs on the kernel left by the safe
+/// reduction rules and lifts its solution back to `graph`. The component reports still
+/// describe the components of `graph` and the cost of the lifted labelling on them; only
+/// `class` and `treewidth` refer to the reduced component that was solved.
+///
+/// The domination problems are additive over connected components, so a disconnected
+/// graph is split into its components, which are solved independently and in parallel
+/// and whose labellings are stitched back together. Trees, cycles and complete
+/// (bipartite) components are solved exactly by [`solve_class`], as are components of at
+/// most [`BRUTE_FORCE_ORDER`] vertices and components whose tree decomposition is at most
+/// [`SolverConfig::max_treewidth`] wide. With a seed, the `i`-th component
+/// is solved with `seed + i`.
+///
+/// The final labelling is checked with [`Problem::is_feasible`].
+///
+/// # Panics
+/// - If the graph has no vertices.
+/// - If the configuration is invalid for the graph; see [`SolverConfig::validate`].
+///
+/// A final labelling that is infeasible although the graph has no isolated vertex (the
+/// only case in which some variants, the total ones, have no feasible labelling) points
+/// to a bug in a repair operator. It is logged as an error, and debug builds panic.
+#[must_use]
+pub fn solve(graph: &CsrGraph, config: &SolverConfig) -> Solution {
+    assert!(graph.order() > 0, "The graph has no vertices");
+    if let Err(e) = config.validate(graph) {
+        panic!("{e}");
+    }
+
+    let kernel =
+        (config.reduce && has_rules(graph, config.problem)).then(|| reduce(graph, config.problem));
+    let mut solution = match kernel.as_ref().filter(|kernel| !kernel.is_trivial()) {
+        Some(kernel) => {
+            let solution = solve_components(kernel.graph(), config);
+            let chromosome = kernel.lift(&solution.chromosome, graph);
+            Solution {
+                components: lift_reports(kernel, &solution.components, graph, &chromosome),
+                chromosome,
+                ..solution
+            }
+        }
+        None => solve_components(graph, config),
+    };
+    solution.reduction = kernel.as_ref().map(Kernel::stats);
+
+    let feasible = config
+        .problem
+        .build()
+        .is_feasible(&solution.chromosome, graph)
+        || graph.vertices().any(|v| graph.degree(v) == 0);
+    if !feasible {
+        error!(
+            "The solver returned an infeasible {} labelling",
+            config.problem
+        );
+    }
+    debug_assert!(
+        feasible,
+        "The solver returned an infeasible {} labelling",
+        config.problem
+    );
+    solution
+}
+
+/// Turns the reports of the kernel components into reports of the components of `graph`
+/// they were reduced from, priced on the lifted `chromosome`.
+///
+/// The rules never remove a whole component nor split one, so every component of `graph`
+/// has exactly one reduced counterpart.
+fn lift_reports(
+    kernel: &Kernel,
+    reports: &[ComponentReport],
+    graph: &CsrGraph,
+    chromosome: &Chromosome,
+) -> Vec<ComponentReport> {
+    let components = graph.components();
+    let mut component_of = vec![0; graph.order()];
+    for (idx, vertices) in components.iter().enumerate() {
+        for &v in vertices {
+            component_of[v as usize] = idx;
+        }
+    }
+
+    let mut lifted: Vec<(usize, ComponentReport)> = kernel
+        .graph()
+        .components()
+        .iter()
+        .zip(reports)
+        .map(|(reduced, report)| {
+            let idx = component_of[kernel.vertex_map()[reduced[0] as usize] as usize];
+            let vertices = &components[idx];
+            let report = ComponentReport {
+                order: vertices.len(),
+                size: vertices.iter().map(|&v| graph.degree(v)).sum::<usize>() / 2,
+                fitness: vertices
+                    .iter()
+                    .map(|&v| graph.cost(v, chromosome.genes()[v as usize]))
+                    .sum(),
+                ..*report
+            };
+            (idx, report)
+        })
+        .collect();
+    debug_assert_eq!(lifted.len(), components.len());
+
+    lifted.sort_unstable_by_key(|&(idx, _)| idx);
+    lifted.into_iter().map(|(_, report)| report).collect()
+}
+
+/// Solves each connected component of `graph` and stitches the labellings together.
+fn solve_components(graph: &CsrGraph, config: &SolverConfig) -> Solution {
+    let components = graph.components();
+    if components.len() == 1 {
+        return solve_component(graph, config);
+    }
+    debug!("Solving {} connected components", components.len());
+
+    let solutions: Vec<Solution> = components
+        .par_iter()
+        .enumerate()
+        .map(|(idx, vertices)| {
+            let config = SolverConfig {
+                seed: config.seed.map(|seed| seed.wrapping_add(idx as u64)),
+                ..config.clone()
+            };
+            solve_component(&graph.induced_subgraph(vertices), &config)
+        })
+        .collect();
+
+    // Junta as rotulações das componentes na numeração original.
+    let mut genes = vec![0u8; graph.order()];
+    for (vertices, solution) in components.iter().zip(&solutions) {
+        for (&v, &label) in vertices.iter().zip(solution.chromosome.genes()) {
+            genes[v as usize] = label;
+        }
+    }
+    let mut chromosome = Chromosome::new(genes, graph);
+    chromosome.track_neighbors(graph);
+
+    Solution {
+        chromosome,
+        generations: solutions.iter().map(|s| s.generations).max().unwrap_or(0),
+        components: solutions
+            .iter()
+            .flat_map(|s| s.components.clone())
+            .collect(),
+        reduction: None,
+    }
+}
+
+/// Solves a connected graph: exactly if it belongs to a class solved by [`solve_class`], is
+/// small enough or has a narrow tree decomposition, with the configured mode otherwise.
+fn solve_component(graph: &CsrGraph, config: &SolverConfig) -> Solution {
+    let mut rng: Box<dyn RngCore> = match config.seed {
+        Some(seed) => Box::new(StdRng::seed_from_u64(seed)),
+        None => Box::new(thread_rng()),
+    };
+
+    let problem = config.problem.build();
+
+    let constructed = |chromosome| Solution {
+        chromosome,
+        generations: 0,
+        components: Vec::new(),
+        reduction: None,
+    };
+
+    let mut exact = false;
+    let mut treewidth = None;
+    let class = solve_class(config.problem, graph);
+    let mut solution = if let Some((_, chromosome)) = &class {
+        exact = true;
+        constructed(chromosome.clone())
+    } else if graph.order() <= BRUTE_FORCE_ORDER {
+        if let Some(chromosome) = brute_force(&*problem, graph) {
+            exact = true;
+            constructed(chromosome)
+        } else {
+            // Sem rotulação viável, como um vértice isolado nas variantes totais.
+            constructed(immigrant(&*problem, graph, &mut *rng))
+        }
+    } else if let Some((width, chromosome)) = solve_treewidth(
+        config.problem,
+        graph,
+        config.elimination,
+        config.max_treewidth,
+        config.max_dp_states,
+    ) {
+        exact = true;
+        treewidth = Some(width);
+        constructed(chromosome)
+    } else {
+        match &config.mode {
+            Mode::Generational => solve_generational(graph, config, &problem, &mut *rng),
+            Mode::Island(params) => solve_islands(graph, config, params, &problem, &mut *rng),
+            Mode::Grasp => solve_grasp(graph, config, &*problem, &mut *rng),
+        }
+    };
+
+    solution.components = vec![ComponentReport {
+        order: graph.order(),
+        size: graph.edge_count(),
+        fitness: solution.chromosome.fitness(),
+        generations: solution.generations,
+        exact,
+        class: class.map(|(class, _)| class),
+        treewidth,
+    }];
+    solution
+}
+
+/// Runs [`solve`] inside the given rayon thread pool.
+///
+/// All parallel work of the run (offspring generation and island evolution) executes on
+/// the threads of `pool`, so applications can bound the solver without touching the
+/// global rayon pool. [`solve`] itself uses whichever pool it is called from.
+///
+/// # Panics
+/// Panics under the same conditions as [`solve`].
+#[must_use]
+pub fn solve_in(pool: &ThreadPool, graph: &CsrGraph, config: &SolverConfig) -> Solution {
+    pool.install(|| solve(graph, config))
+}
+
+fn solve_generational(
+    graph: &CsrGraph,
+    config: &SolverConfig,
+    problem: &Arc<dyn Problem>,
+    rng: &mut dyn RngCore,
+) -> Solution {
+    let mut variation = config.variation(problem, graph);
+    let selector = config.selection.build(config.tournament_size);
+
+    let size = config.population_size(graph);
+    let mut population = config.initial_population(size, &**problem, graph, rng);
+
+    run(config, |generation| {
+        if generation > 0 {
+            match config.seed {
+                Some(_) => {
+                    population.envolve_seeded(&selector, &variation, &**problem, graph, rng.gen());
+                }
+                None => population.envolve(&selector, &variation, &**problem, graph),
+            }
+            manage_diversity(&mut population, config, &**problem, graph, rng, generation);
+            if config.is_adaptive() {
+                adapt(&mut variation, generation, None);
+            }
+        }
+        population
+            .best_chromosome()
+            .expect("Failed to retrieve the best individual")
+            .clone()
+    })
+}
+
+fn solve_islands(
+    graph: &CsrGraph,
+    config: &SolverConfig,
+    params: &IslandParams,
+    problem: &Arc<dyn Problem>,
+    rng: &mut dyn RngCore,
+) -> Solution {
+    assert!(params.islands > 0, "At least one island must be provided.");
+
+    let island_size = config
+        .population_size(graph)
+        .div_ceil(params.islands)
+        .max(2);
+    let islands = (0..params.islands)
+        .map(|_| {
+            Island::new(
+                config.initial_population(island_size, &**problem, graph, rng),
+                config.selection.build(config.tournament_size),
+                config.variation(problem, graph),
+            )
+        })
+        .collect();
+
+    let mut model = IslandModel::new(
+        islands,
+        params.topology,
+        params.migration_interval,
+        params.migration_size,
+    );
+
+    run(config, |generation| {
+        if generation > 0 {
+            match config.seed {
+                Some(_) => model.envolve_seeded(&**problem, graph, rng.gen()),
+                None => model.envolve(&**problem, graph),
+            }
+            for (idx, island) in model.islands_mut().iter_mut().enumerate() {
+                let population = island.population_mut();
+                manage_diversity(population, config, &**problem, graph, rng, generation);
+                if config.is_adaptive() {
+                    adapt(island.crossover_mut(), generation, Some(idx));
+                }
+            }
+        }
+        model
+            .best_chromosome()
+            .expect("Failed to retrieve the best individual")
+            .clone()
+    })
+}
+
+fn solve_grasp(
+    graph: &CsrGraph,
+    config: &SolverConfig,
+    problem: &dyn Problem,
+    rng: &mut dyn RngCore,
+) -> Solution {
+    let params = &config.grasp;
+    assert!(
+        (0.0..=1.0).contains(&params.alpha),
+        "Alpha must be between 0 and 1"
+    );
+
+    let batch = config.population_size(graph).max(1) as u64;
+    let construct = |rng: &mut dyn RngCore| {
+        let mut chromosome = grasp(graph, params.alpha, rng);
+        problem.prepare(&mut chromosome, graph);
+        prune(&mut chromosome, problem, graph);
+        chromosome
+    };
+
+    run(config, |_| {
+        let seed = config.seed.map(|_| rng.gen::<u64>());
+        (0..batch)
+            .into_par_iter()
+            .map(|idx| match seed {
+                Some(seed) => construct(&mut StdRng::seed_from_u64(seed.wrapping_add(idx))),
+                None => construct(&mut thread_rng()),
+            })
+            .min_by_key(Chromosome::fitness)
+            .expect("Failed to build a GRASP solution")
+    })
+}
+
+/// Applies the diversity management of `config` to `population` after `generation`.
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn manage_diversity(
+    population: &mut Population,
+    config: &SolverConfig,
+    problem: &dyn Problem,
+    graph: &CsrGraph,
+    rng: &mut dyn RngCore,
+    generation: usize,
+) {
+    let params = &config.diversity;
+
+    if params.eliminate_duplicates {
+        let replaced = population.replace_duplicates(|| immigrant(problem, graph, rng));
+        if replaced > 0 {
+            debug!("Generation {generation} - Replaced {replaced} duplicate chromosomes");
+        }
+    }
+
+    if params.threshold > 0.0 {
+        let diversity = population.diversity();
+        if diversity < params.threshold {
+            debug!(
+                "Generation {generation} - Diversity {diversity:.4} below {}, applying {}",
+                params.threshold, params.response
+            );
+            match params.response {
+                DiversityResponse::Immigration(fraction) => {
+                    let count = (population.size() as f64 * fraction).ceil() as usize;
+                    let immigrants = (0..count).map(|_| immigrant(problem, graph, rng)).collect();
+                    population.replace_worst(immigrants);
+                }
+                DiversityResponse::Restart => {
+                    let best = population.best_chromosome().cloned();
+                    *population = config.initial_population(population.size(), problem, graph, rng);
+                    population.replace_worst(best.into_iter().collect());
+                }
+            }
+        }
+    }
+}
+
+/// Adapts `variation` after `generation` and logs the new rates and operator
+/// probabilities. `island` identifies the population in the island model.
+fn adapt(variation: &mut Variation, generation: usize, island: Option<usize>) {
+    variation.adapt();
+
+    let probabilities: Vec<String> = variation
+        .probabilities()
+        .map(|(operator, probability)| format!("{operator}={probability:.3}"))
+        .collect();
+    let island = island
+        .map(|idx| format!(" - Island {idx}"))
+        .unwrap_or_default();
+    debug!(
+        "Generation {generation}{island} - Crossover rate {:.4}, mutation rate {:.6}, operators [{}]",
+        variation.crossover_rate(),
+        variation.mutation_rate(),
+        probabilities.join(", ")
+    );
+}
+
+/// A chromosome built by the first of [`Problem::heuristics`], or by [`WH`] on graphs with
+/// vertex costs as in [`SolverConfig::initial_population`], and prepared with
+/// [`Problem::prepare`].
+fn immigrant(problem: &dyn Problem, graph: &CsrGraph, rng: &mut dyn RngCore) -> Chromosome {
+    let mut chromosome = if graph.is_weighted() {
+        WH.generate(graph, Some(rng))
+    } else {
+        problem.heuristics()[0].generate(graph, Some(rng))
+    };
+    problem.prepare(&mut chromosome, graph);
+    chromosome
+}
+
+/// Drives the generation loop shared by all modes.
+///
+/// `step(generation)` evolves the populations (generation `0` is the initial one)
+/// and returns the current best chromosome.
+fn run<F>(config: &SolverConfig, mut step: F) -> Solution
+where
+    F: FnMut(usize) -> Chromosome,
+{
+    let mut best_solution = step(0);
+    debug!("Initial best fitness: {}", best_solution.fitness());
+
+    let mut stagnant_generations = 0;
+    let mut generations = 0;
+    for generation in 1..=config.generations {
+        generations = generation;
+        let new_best_solution = step(generation);
+
+        if new_best_solution.fitness() < best_solution.fitness() {
+            debug!(
+                "Generation {} - New best fitness: {} (improved from {})",
+                generation,
+                new_best_solution.fitness(),
+                best_solution.fitness()
+            );
+            best_solution = new_best_solution;
+            stagnant_generations = 0;
+        } else {
+            stagnant_generations += 1;
+        }
+
+        if stagnant_generations >= config.max_stagnant {
+            debug!("Stopped at generation {generation} due to stagnation");
+            break;
+        }
+    }
+
+    Solution {
+        chromosome: best_solution,
+        generations,
+        components: Vec::new(),
+        reduction: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::genetic::wh;
+
+    const VARIANTS: [ProblemVariant; 8] = [
+        ProblemVariant::TotalRoman,
+        ProblemVariant::Roman,
+        ProblemVariant::DoubleRoman,
+        ProblemVariant::TotalDoubleRoman,
+        ProblemVariant::Italian,
+        ProblemVariant::TotalItalian,
+        ProblemVariant::IndependentRoman,
+        ProblemVariant::PerfectRoman,
+    ];
+
+    /// A grid, a random graph and a pendant-rich tree side by side.
+    fn test_graph() -> CsrGraph {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut edges = BTreeSet::new();
+        // Grade 4 x 5 nos vértices 0 a 19.
+        for v in 0..20 {
+            if v % 5 < 4 {
+                edges.insert((v, v + 1));
+            }
+            if v < 15 {
+                edges.insert((v, v + 5));
+            }
+        }
+        // Ciclo com cordas aleatórias nos vértices 20 a 44.
+        for v in 20..45 {
+            edges.insert((v, 20 + (v - 19) % 25));
+        }
+        while edges.len() < 31 + 25 + 35 {
+            let u = rng.gen_range(20..45);
+            let v = rng.gen_range(20..45);
+            if u < v {
+                edges.insert((u, v));
+            }
+        }
+        // Árvore aleatória nos vértices 45 a 74.
+        for v in 46..75 {
+            edges.insert((rng.gen_range(45..v), v));
+        }
+        CsrGraph::from_edges(75, &edges.into_iter().collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn every_variant_and_mode_returns_a_feasible_labelling() {
+        let graph = test_graph();
+        let modes = [
+            Mode::Generational,
+            Mode::Island(IslandParams::default()),
+            Mode::Grasp,
+        ];
+        for problem in VARIANTS {
+            for mode in &modes {
+                let config = SolverConfig {
+                    problem,
+                    mode: mode.clone(),
+                    generations: 20,
+                    seed: Some(1),
+                    ..SolverConfig::default()
+                };
+                let solution = solve(&graph, &config);
+                assert!(problem.build().is_feasible(&solution.chromosome, &graph));
+                assert_eq!(
+                    solution.chromosome.fitness(),
+                    solution.components.iter().map(|c| c.fitness).sum::<usize>()
+                );
+                assert_eq!(
+                    solve(&graph, &config).chromosome.genes(),
+                    solution.chromosome.genes()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn reports_of_reduced_graphs_describe_the_lifted_labelling() {
+        // Duas componentes cujo menor vértice some no kernel: gêmeos de K_{2,5} e folhas
+        // de uma estrela, ao lado de um ciclo que não se reduz.
+        let mut edges: Vec<(u32, u32)> = (0..5).flat_map(|t| [(t, 5), (t, 6)]).collect();
+        edges.extend((7..12).map(|leaf| (leaf, 12)));
+        edges.extend((13..20).map(|v| (v, if v == 19 { 13 } else { v + 1 })));
+        let graph = CsrGraph::from_edges(20, &edges);
+        let components = graph.components();
+
+        for problem in [ProblemVariant::TotalRoman, ProblemVariant::Roman] {
+            let config = SolverConfig {
+                problem,
+                seed: Some(2),
+                ..SolverConfig::default()
+            };
+            let solution = solve(&graph, &config);
+            assert!(solution.reduction.is_some_and(|s| s.removed_vertices() > 0));
+            assert_eq!(solution.components.len(), components.len());
+            for (vertices, report) in components.iter().zip(&solution.components) {
+                let size = vertices.iter().map(|&v| graph.degree(v)).sum::<usize>() / 2;
+                let fitness: usize = vertices
+                    .iter()
+                    .map(|&v| usize::from(solution.chromosome.genes()[v as usize]))
+                    .sum();
+                assert_eq!((report.order, report.size), (vertices.len(), size));
+                assert_eq!(report.fitness, fitness);
+            }
+        }
+    }
+
+    #[test]
+    fn isolated_vertices_do_not_fail_the_feasibility_check() {
+        let graph = CsrGraph::from_edges(4, &[(0, 1), (1, 2)]);
+        for problem in VARIANTS {
+            let config = SolverConfig {
+                problem,
+                seed: Some(0),
+                ..SolverConfig::default()
+            };
+            let solution = solve(&graph, &config);
+            assert_eq!(solution.components.len(), 2);
+        }
+    }
+
+    #[test]
+    fn reports_why_no_reduction_applies() {
+        let reduction = |graph: &CsrGraph, problem, reduce| {
+            let config = SolverConfig {
+                problem,
+                reduce,
+                seed: Some(0),
+                ..SolverConfig::default()
+            };
+            solve(graph, &config).reduction
+        };
+
+        // C7 não tem folhas nem gêmeos: as regras rodam, mas nenhuma dispara.
+        let cycle = CsrGraph::from_edges(7, &(0..7).map(|v| (v, (v + 1) % 7)).collect::<Vec<_>>());
+        assert_eq!(
+            reduction(&cycle, ProblemVariant::TotalRoman, true),
+            Some(ReductionStats::default())
+        );
+        assert_eq!(reduction(&cycle, ProblemVariant::TotalRoman, false), None);
+        assert_eq!(reduction(&cycle, ProblemVariant::DoubleRoman, true), None);
+
+        let star = CsrGraph::from_edges(4, &[(0, 1), (0, 2), (0, 3)]);
+        assert!(
+            reduction(&star, ProblemVariant::Roman, true).is_some_and(|s| s.forced_supports == 1)
+        );
+        let weighted = star.with_costs(vec![[1, 2]; 4]);
+        assert_eq!(reduction(&weighted, ProblemVariant::Roman, true), None);
+    }
+
+    #[test]
+    fn low_diversity_brings_in_immigrants_or_restarts_around_the_best() {
+        let graph = CsrGraph::circulant(12, &[1, 3]);
+        let problem = ProblemVariant::Roman.build();
+        let twos = Chromosome::new(vec![2; 12], &graph);
+        let config = |response| SolverConfig {
+            diversity: DiversityParams {
+                eliminate_duplicates: false,
+                threshold: 0.5,
+                response,
+            },
+            ..SolverConfig::default()
+        };
+        let mut rng = StdRng::seed_from_u64(38);
+
+        // Dez cópias idênticas: diversidade 0, abaixo do limiar.
+        let mut population = Population::from_chromosomes(vec![twos.clone(); 10]);
+        let immigration = config(DiversityResponse::Immigration(0.25));
+        manage_diversity(
+            &mut population,
+            &immigration,
+            &*problem,
+            &graph,
+            &mut rng,
+            1,
+        );
+        let immigrants = population
+            .chromosomes()
+            .iter()
+            .filter(|c| c.genes() != twos.genes())
+            .count();
+        assert_eq!(immigrants, 3);
+
+        // A população recomeça do zero, mas o melhor cromossomo sobrevive.
+        let mut best = twos.clone();
+        best.set_label(0, 0, &graph);
+        let mut chromosomes = vec![twos; 9];
+        chromosomes.push(best.clone());
+        let mut population = Population::from_chromosomes(chromosomes);
+        manage_diversity(
+            &mut population,
+            &config(DiversityResponse::Restart),
+            &*problem,
+            &graph,
+            &mut rng,
+            1,
+        );
+        assert_eq!(population.size(), 10);
+        assert!(population
+            .chromosomes()
+            .iter()
+            .any(|c| c.genes() == best.genes()));
+        assert!(population.best_chromosome().unwrap().fitness() <= best.fitness());
+    }
+
+    #[test]
+    fn immigrants_of_weighted_graphs_are_built_by_wh() {
+        let mut rng = StdRng::seed_from_u64(45);
+        let graph = CsrGraph::circulant(10, &[1, 2]);
+        let costs = (0..10).map(|v| [v + 1, 2 * (v + 1)]).collect();
+        let weighted = graph.clone().with_costs(costs);
+        let problem = ProblemVariant::Roman.build();
+
+        assert_eq!(
+            immigrant(&*problem, &weighted, &mut rng).genes(),
+            wh(&weighted).genes()
+        );
+        assert!(problem.is_feasible(&immigrant(&*problem, &graph, &mut rng), &graph));
+    }
+}
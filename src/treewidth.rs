@@ -0,0 +1,783 @@
+use std::{
+    cmp::Reverse,
+    collections::{BTreeSet, BinaryHeap, HashMap},
+    fmt,
+    str::FromStr,
+};
+
+use crate::{
+    csr::CsrGraph,
+    exact::required,
+    genetic::{Chromosome, ProblemVariant},
+};
+
+/// Largest width accepted by [`solve_decomposition`]: a state packs three bits per bag
+/// vertex into a `u64`.
+pub const MAX_DP_WIDTH: usize = 20;
+
+/// Default bound on the number of states [`solve_decomposition`] stores, which keeps its
+/// peak memory around 128 MiB.
+pub const DEFAULT_MAX_DP_STATES: usize = 1 << 21;
+
+/// Heuristic choosing the elimination ordering from which [`decompose`] builds a tree
+/// decomposition.
+///
+/// Parsed from and formatted as `min-degree` and `min-fill`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EliminationOrder {
+    /// Eliminates a vertex of minimum degree.
+    MinDegree,
+    /// Eliminates a vertex whose neighbourhood needs the fewest edges to become a clique,
+    /// breaking ties by minimum degree.
+    #[default]
+    MinFill,
+}
+
+impl FromStr for EliminationOrder {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "min-degree" => Ok(Self::MinDegree),
+            "min-fill" => Ok(Self::MinFill),
+            _ => Err(format!(
+                "Invalid elimination order: {s} (expected min-degree or min-fill)"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for EliminationOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MinDegree => write!(f, "min-degree"),
+            Self::MinFill => write!(f, "min-fill"),
+        }
+    }
+}
+
+/// A tree decomposition: bags of vertices arranged in a tree such that every edge lies in
+/// some bag and the bags containing any vertex form a subtree.
+///
+/// # Fields
+/// - `bags: Vec<Vec<u32>>`: The bags, each sorted in increasing order.
+/// - `parent: Vec<Option<usize>>`: The parent of each bag; the root has none.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TreeDecomposition {
+    bags: Vec<Vec<u32>>,
+    parent: Vec<Option<usize>>,
+}
+
+impl TreeDecomposition {
+    /// Returns the width: the size of the largest bag minus one.
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.bags
+            .iter()
+            .map(Vec::len)
+            .max()
+            .unwrap_or(0)
+            .saturating_sub(1)
+    }
+
+    /// Returns the bags.
+    #[must_use]
+    pub fn bags(&self) -> &[Vec<u32>] {
+        &self.bags
+    }
+
+    /// Returns the parent of bag `index`, or `None` for the root.
+    #[must_use]
+    pub fn parent(&self, index: usize) -> Option<usize> {
+        self.parent[index]
+    }
+}
+
+/// Builds a tree decomposition of `graph` by eliminating vertices in the order chosen by
+/// the heuristic.
+///
+/// Eliminating a vertex creates a bag with the vertex and its remaining neighbours, which
+/// are then made pairwise adjacent. The bag's parent is the bag of the neighbour eliminated
+/// first afterwards.
+///
+/// # Parameters
+/// - `graph: &CsrGraph`: The graph to decompose.
+/// - `order: EliminationOrder`: The elimination heuristic.
+/// - `max_width: usize`: The elimination stops as soon as a bag exceeds this width.
+///
+/// # Returns
+/// - The decomposition, or `None` if the heuristic could not keep the width within
+///   `max_width` or the graph is empty.
+#[must_use]
+pub fn decompose(
+    graph: &CsrGraph,
+    order: EliminationOrder,
+    max_width: usize,
+) -> Option<TreeDecomposition> {
+    let mut adjacency: Vec<BTreeSet<u32>> = graph
+        .vertices()
+        .map(|v| graph.neighbors(v).iter().copied().collect())
+        .collect();
+    let score = |adjacency: &[BTreeSet<u32>], v: u32| {
+        let neighbors = &adjacency[v as usize];
+        let fill = match order {
+            EliminationOrder::MinDegree => 0,
+            EliminationOrder::MinFill => neighbors
+                .iter()
+                .map(|&a| {
+                    neighbors
+                        .range(a + 1..)
+                        .filter(|&b| !adjacency[a as usize].contains(b))
+                        .count()
+                })
+                .sum(),
+        };
+        (fill, neighbors.len())
+    };
+
+    let mut scores: Vec<(usize, usize)> = graph.vertices().map(|v| score(&adjacency, v)).collect();
+    let mut heap: BinaryHeap<_> = graph
+        .vertices()
+        .map(|v| Reverse((scores[v as usize], v)))
+        .collect();
+    let mut position = vec![usize::MAX; graph.order()];
+    let mut bags: Vec<Vec<u32>> = Vec::with_capacity(graph.order());
+    let mut eliminated = Vec::with_capacity(graph.order());
+
+    while let Some(Reverse((vertex_score, v))) = heap.pop() {
+        if position[v as usize] != usize::MAX || vertex_score != scores[v as usize] {
+            continue;
+        }
+        let neighbors: Vec<u32> = adjacency[v as usize].iter().copied().collect();
+        if neighbors.len() > max_width {
+            return None;
+        }
+
+        position[v as usize] = bags.len();
+        for &a in &neighbors {
+            adjacency[a as usize].remove(&v);
+            for &b in &neighbors {
+                if a != b {
+                    adjacency[a as usize].insert(b);
+                }
+            }
+        }
+
+        // O preenchimento muda também para os vizinhos dos vizinhos do vértice eliminado.
+        let mut affected: BTreeSet<u32> = neighbors.iter().copied().collect();
+        if order == EliminationOrder::MinFill {
+            for &a in &neighbors {
+                affected.extend(adjacency[a as usize].iter().copied());
+            }
+        }
+        for u in affected {
+            let updated = score(&adjacency, u);
+            if updated != scores[u as usize] {
+                scores[u as usize] = updated;
+                heap.push(Reverse((updated, u)));
+            }
+        }
+
+        let mut bag = neighbors;
+        bag.push(v);
+        bag.sort_unstable();
+        bags.push(bag);
+        eliminated.push(v);
+    }
+
+    let root = bags.len().checked_sub(1)?;
+    let parent = bags
+        .iter()
+        .zip(&eliminated)
+        .enumerate()
+        .map(|(index, (bag, &v))| {
+            let next = bag
+                .iter()
+                .filter(|&&u| u != v)
+                .map(|&u| position[u as usize])
+                .min();
+            // Em grafos desconexos, as raízes das demais componentes ficam sob a última.
+            next.or((index != root).then_some(root))
+        })
+        .collect();
+
+    Some(TreeDecomposition { bags, parent })
+}
+
+/// Operation of a node of the nice tree decomposition.
+#[derive(Clone, Copy, Debug)]
+enum Operation {
+    /// Empty bag.
+    Leaf,
+    /// Adds `vertex` at `position` of the child's bag.
+    Introduce {
+        child: usize,
+        vertex: u32,
+        position: usize,
+    },
+    /// Removes a vertex of the child's bag.
+    Forget { child: usize },
+    /// Merges two children with the same bag.
+    Join { left: usize, right: usize },
+}
+
+/// Best cost of a state and the child states it comes from.
+#[derive(Clone, Copy, Debug)]
+struct Entry {
+    cost: usize,
+    left: u64,
+    right: u64,
+}
+
+/// A node of the nice tree decomposition.
+///
+/// A state holds three bits per bag vertex, in bag order: the label in the upper two
+/// and, in the lowest, whether the vertex is already satisfied by a vertex of the
+/// subtree. The costs of the states are only kept until the parent node is built; the
+/// back-pointers to the child states are kept to rebuild the labelling.
+#[derive(Debug)]
+struct Node {
+    bag: Vec<u32>,
+    operation: Operation,
+    costs: HashMap<u64, usize>,
+    left: HashMap<u64, u64>,
+    right: HashMap<u64, u64>,
+}
+
+fn slot_label(state: u64, position: usize) -> u8 {
+    ((state >> (3 * position + 1)) & 3) as u8
+}
+
+fn slot_satisfied(state: u64, position: usize) -> bool {
+    (state >> (3 * position)) & 1 == 1
+}
+
+fn insert_slot(state: u64, position: usize, slot: u64) -> u64 {
+    let low = state & ((1 << (3 * position)) - 1);
+    let high = state >> (3 * position);
+    low | (slot << (3 * position)) | (high << (3 * position + 3))
+}
+
+fn remove_slot(state: u64, position: usize) -> u64 {
+    let low = state & ((1 << (3 * position)) - 1);
+    low | ((state >> (3 * position + 3)) << (3 * position))
+}
+
+/// Builds the nice tree decomposition node by node, filling the tables on the way.
+///
+/// Every method returns `None` once more than `max_states` states have been stored.
+struct Dp<'a> {
+    problem: ProblemVariant,
+    graph: &'a CsrGraph,
+    nodes: Vec<Node>,
+    states: usize,
+    max_states: usize,
+}
+
+impl Dp<'_> {
+    fn push(
+        &mut self,
+        bag: Vec<u32>,
+        operation: Operation,
+        mut table: HashMap<u64, Entry>,
+    ) -> Option<usize> {
+        prune(&mut table, bag.len());
+        self.states += table.len();
+        if self.states > self.max_states {
+            return None;
+        }
+
+        let mut node = Node {
+            bag,
+            operation,
+            costs: HashMap::with_capacity(table.len()),
+            left: HashMap::with_capacity(table.len()),
+            right: HashMap::new(),
+        };
+        for (state, entry) in table {
+            node.costs.insert(state, entry.cost);
+            if !matches!(operation, Operation::Leaf) {
+                node.left.insert(state, entry.left);
+            }
+            if matches!(operation, Operation::Join { .. }) {
+                node.right.insert(state, entry.right);
+            }
+        }
+        self.nodes.push(node);
+        Some(self.nodes.len() - 1)
+    }
+
+    /// Takes the costs of a node, which are no longer needed once its parent is built.
+    fn take_costs(&mut self, id: usize) -> HashMap<u64, usize> {
+        std::mem::take(&mut self.nodes[id].costs)
+    }
+
+    fn leaf(&mut self) -> Option<usize> {
+        let table = HashMap::from([(
+            0,
+            Entry {
+                cost: 0,
+                left: 0,
+                right: 0,
+            },
+        )]);
+        self.push(Vec::new(), Operation::Leaf, table)
+    }
+
+    fn introduce(&mut self, child: usize, vertex: u32) -> Option<usize> {
+        let costs = self.take_costs(child);
+        let child_bag = &self.nodes[child].bag;
+        let position = child_bag.partition_point(|&u| u < vertex);
+        let adjacent: Vec<usize> = (0..child_bag.len())
+            .filter(|&i| self.graph.contains_edge(child_bag[i], vertex))
+            .collect();
+
+        let mut table: HashMap<u64, Entry> = HashMap::new();
+        for (&state, &cost) in &costs {
+            for label in 0..=2u8 {
+                let mut satisfied = required(self.problem, label).is_none();
+                let mut next = state;
+                for &i in &adjacent {
+                    let neighbor = slot_label(state, i);
+                    if required(self.problem, label).is_some_and(|r| neighbor >= r) {
+                        satisfied = true;
+                    }
+                    if required(self.problem, neighbor).is_some_and(|r| label >= r) {
+                        next |= 1 << (3 * i);
+                    }
+                }
+                let next =
+                    insert_slot(next, position, u64::from(label) << 1 | u64::from(satisfied));
+                relax(
+                    &mut table,
+                    next,
+                    Entry {
+                        cost: cost + self.graph.cost(vertex, label),
+                        left: state,
+                        right: 0,
+                    },
+                );
+            }
+        }
+
+        let mut bag = child_bag.clone();
+        bag.insert(position, vertex);
+        let operation = Operation::Introduce {
+            child,
+            vertex,
+            position,
+        };
+        self.push(bag, operation, table)
+    }
+
+    fn forget(&mut self, child: usize, vertex: u32) -> Option<usize> {
+        let costs = self.take_costs(child);
+        let child_bag = &self.nodes[child].bag;
+        let position = child_bag.partition_point(|&u| u < vertex);
+
+        let mut table: HashMap<u64, Entry> = HashMap::new();
+        for (&state, &cost) in &costs {
+            if slot_satisfied(state, position) {
+                relax(
+                    &mut table,
+                    remove_slot(state, position),
+                    Entry {
+                        cost,
+                        left: state,
+                        right: 0,
+                    },
+                );
+            }
+        }
+
+        let mut bag = child_bag.clone();
+        bag.remove(position);
+        self.push(bag, Operation::Forget { child }, table)
+    }
+
+    fn join(&mut self, left: usize, right: usize) -> Option<usize> {
+        let left_costs = self.take_costs(left);
+        let right_costs = self.take_costs(right);
+        let bag = self.nodes[left].bag.clone();
+        let flags = (0..bag.len()).fold(0u64, |mask, i| mask | 1 << (3 * i));
+        let bag_cost = |state: u64| -> usize {
+            bag.iter()
+                .enumerate()
+                .map(|(i, &v)| self.graph.cost(v, slot_label(state, i)))
+                .sum()
+        };
+
+        let mut by_labels: HashMap<u64, Vec<(u64, usize)>> = HashMap::new();
+        for (&state, &cost) in &right_costs {
+            by_labels
+                .entry(state & !flags)
+                .or_default()
+                .push((state, cost));
+        }
+
+        let mut table: HashMap<u64, Entry> = HashMap::new();
+        for (&state, &left_cost) in &left_costs {
+            let Some(matching) = by_labels.get(&(state & !flags)) else {
+                continue;
+            };
+            let shared = bag_cost(state);
+            for &(other, cost) in matching {
+                relax(
+                    &mut table,
+                    state | other,
+                    Entry {
+                        cost: left_cost + cost - shared,
+                        left: state,
+                        right: other,
+                    },
+                );
+            }
+        }
+
+        self.push(bag, Operation::Join { left, right }, table)
+    }
+}
+
+/// Keeps the cheaper entry of a state. Ties go to the smaller child states, so the result
+/// does not depend on the iteration order of the tables.
+fn relax(table: &mut HashMap<u64, Entry>, state: u64, entry: Entry) {
+    table
+        .entry(state)
+        .and_modify(|best| {
+            if (entry.cost, entry.left, entry.right) < (best.cost, best.left, best.right) {
+                *best = entry;
+            }
+        })
+        .or_insert(entry);
+}
+
+/// Removes the states dominated by a state with the same labels, at most the same cost
+/// and a superset of the satisfied vertices.
+fn prune(table: &mut HashMap<u64, Entry>, bag_size: usize) {
+    let flags = (0..bag_size).fold(0u64, |mask, i| mask | 1 << (3 * i));
+    let mut by_labels: HashMap<u64, Vec<(usize, u64)>> = HashMap::new();
+    for (&state, entry) in table.iter() {
+        by_labels
+            .entry(state & !flags)
+            .or_default()
+            .push((entry.cost, state));
+    }
+
+    for mut group in by_labels.into_values() {
+        group.sort_unstable();
+        let mut kept: Vec<u64> = Vec::with_capacity(group.len());
+        for (_, state) in group {
+            if kept
+                .iter()
+                .any(|&better| better & state & flags == state & flags)
+            {
+                table.remove(&state);
+            } else {
+                kept.push(state);
+            }
+        }
+    }
+}
+
+/// Finds a minimum-cost labelling of `graph` by dynamic programming over a tree
+/// decomposition.
+///
+/// The decomposition is processed as a nice tree decomposition, made of leaf, introduce,
+/// forget and join nodes. A state labels the vertices of a bag and records which of them
+/// are already satisfied; a vertex can only be forgotten once satisfied. The running time
+/// is linear in the order and exponential in the width. The costs of a node are dropped
+/// once its parent is built, and only back-pointers to the child states are kept, whose
+/// total number is bounded by `max_states`.
+///
+/// # Parameters
+/// - `problem: ProblemVariant`: The problem to solve. Only total Roman and Roman
+///   domination are supported.
+/// - `graph: &CsrGraph`: The graph, possibly with vertex costs.
+/// - `decomposition: &TreeDecomposition`: A tree decomposition of `graph`, e.g. from
+///   [`decompose`].
+/// - `max_states: usize`: The largest number of states stored over all nodes.
+///
+/// # Returns
+/// - An optimal chromosome with tracked neighbour counts, or `None` if the problem is not
+///   supported, no labelling is feasible or the tables exceed `max_states` states.
+///
+/// # Panics
+/// Panics if the width of the decomposition exceeds [`MAX_DP_WIDTH`].
+#[must_use]
+pub fn solve_decomposition(
+    problem: ProblemVariant,
+    graph: &CsrGraph,
+    decomposition: &TreeDecomposition,
+    max_states: usize,
+) -> Option<Chromosome> {
+    assert!(
+        decomposition.width() <= MAX_DP_WIDTH,
+        "The decomposition is wider than {MAX_DP_WIDTH}"
+    );
+    if !matches!(problem, ProblemVariant::TotalRoman | ProblemVariant::Roman) {
+        return None;
+    }
+
+    let bags = decomposition.bags();
+    let mut children = vec![Vec::new(); bags.len()];
+    let mut root = None;
+    for index in 0..bags.len() {
+        match decomposition.parent(index) {
+            Some(parent) => children[parent].push(index),
+            None => root = Some(index),
+        }
+    }
+    let root = root?;
+
+    let mut dp = Dp {
+        problem,
+        graph,
+        nodes: Vec::new(),
+        states: 0,
+        max_states,
+    };
+    let mut result = vec![usize::MAX; bags.len()];
+    let mut stack = vec![(root, false)];
+    while let Some((index, expanded)) = stack.pop() {
+        if !expanded {
+            stack.push((index, true));
+            stack.extend(children[index].iter().map(|&child| (child, false)));
+            continue;
+        }
+
+        let bag = &bags[index];
+        let mut node = None;
+        for &child in &children[index] {
+            let mut id = result[child];
+            for &v in &bags[child] {
+                if bag.binary_search(&v).is_err() {
+                    id = dp.forget(id, v)?;
+                }
+            }
+            for &v in bag {
+                if bags[child].binary_search(&v).is_err() {
+                    id = dp.introduce(id, v)?;
+                }
+            }
+            node = Some(match node {
+                Some(joined) => dp.join(joined, id)?,
+                None => id,
+            });
+        }
+        result[index] = if let Some(id) = node {
+            id
+        } else {
+            let mut id = dp.leaf()?;
+            for &v in bag {
+                id = dp.introduce(id, v)?;
+            }
+            id
+        };
+    }
+
+    let mut id = result[root];
+    for &v in &bags[root] {
+        id = dp.forget(id, v)?;
+    }
+    dp.nodes[id].costs.get(&0)?;
+
+    let mut genes = vec![0u8; graph.order()];
+    let mut stack = vec![(id, 0u64)];
+    while let Some((id, state)) = stack.pop() {
+        let node = &dp.nodes[id];
+        match node.operation {
+            Operation::Leaf => {}
+            Operation::Introduce {
+                child,
+                vertex,
+                position,
+            } => {
+                genes[vertex as usize] = slot_label(state, position);
+                stack.push((child, node.left[&state]));
+            }
+            Operation::Forget { child } => stack.push((child, node.left[&state])),
+            Operation::Join { left, right } => {
+                stack.push((left, node.left[&state]));
+                stack.push((right, node.right[&state]));
+            }
+        }
+    }
+
+    let mut chromosome = Chromosome::new(genes, graph);
+    chromosome.track_neighbors(graph);
+    Some(chromosome)
+}
+
+/// Solves `graph` exactly if the heuristic finds a tree decomposition of width at most
+/// `max_width` and the dynamic program fits in `max_states` states.
+///
+/// Graphs with more than `max_width * order` edges are rejected without decomposing them,
+/// since every graph of treewidth `k` has fewer edges.
+///
+/// # Parameters
+/// - `problem: ProblemVariant`: The problem to solve; see [`solve_decomposition`].
+/// - `graph: &CsrGraph`: The graph, possibly with vertex costs.
+/// - `order: EliminationOrder`: The elimination heuristic of [`decompose`].
+/// - `max_width: usize`: The largest width worth solving, at most [`MAX_DP_WIDTH`].
+/// - `max_states: usize`: The state budget of [`solve_decomposition`].
+///
+/// # Returns
+/// - The width of the decomposition and an optimal chromosome, or `None` if the problem is
+///   not supported, the width exceeds `max_width`, the budget is exceeded or no labelling
+///   is feasible.
+#[must_use]
+pub fn solve_treewidth(
+    problem: ProblemVariant,
+    graph: &CsrGraph,
+    order: EliminationOrder,
+    max_width: usize,
+    max_states: usize,
+) -> Option<(usize, Chromosome)> {
+    let max_width = max_width.min(MAX_DP_WIDTH);
+    if !matches!(problem, ProblemVariant::TotalRoman | ProblemVariant::Roman)
+        || graph.edge_count() > max_width * graph.order()
+    {
+        return None;
+    }
+    let decomposition = decompose(graph, order, max_width)?;
+    let chromosome = solve_decomposition(problem, graph, &decomposition, max_states)?;
+    Some((decomposition.width(), chromosome))
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::*;
+    use crate::exact::testing::{assert_brute_force_optimal, with_random_costs};
+
+    const PROBLEMS: [ProblemVariant; 2] = [ProblemVariant::TotalRoman, ProblemVariant::Roman];
+    const ORDERS: [EliminationOrder; 2] = [EliminationOrder::MinDegree, EliminationOrder::MinFill];
+
+    /// Checks that every edge lies in a bag and that the bags holding a vertex are
+    /// connected: they form a forest with a single root.
+    fn assert_valid(graph: &CsrGraph, decomposition: &TreeDecomposition) {
+        let bags = decomposition.bags();
+        for u in graph.vertices() {
+            for &v in graph.neighbors(u) {
+                assert!(
+                    bags.iter().any(|bag| bag.contains(&u) && bag.contains(&v)),
+                    "The edge {u}-{v} is not covered"
+                );
+            }
+
+            let roots = (0..bags.len())
+                .filter(|&i| bags[i].contains(&u))
+                .filter(|&i| {
+                    decomposition
+                        .parent(i)
+                        .map_or(true, |parent| !bags[parent].contains(&u))
+                })
+                .count();
+            assert_eq!(roots, 1, "The bags of vertex {u} are not connected");
+        }
+        assert_eq!(
+            (0..bags.len())
+                .filter(|&i| decomposition.parent(i).is_none())
+                .count(),
+            1
+        );
+    }
+
+    fn assert_optimal(graph: &CsrGraph) {
+        for order in ORDERS {
+            let decomposition = decompose(graph, order, MAX_DP_WIDTH).unwrap();
+            assert_valid(graph, &decomposition);
+
+            for problem in PROBLEMS {
+                let solved = solve_treewidth(problem, graph, order, MAX_DP_WIDTH, usize::MAX);
+                assert_brute_force_optimal(problem, graph, solved.as_ref().map(|(_, c)| c));
+                if let Some((width, _)) = solved {
+                    assert_eq!(width, decomposition.width());
+                }
+            }
+        }
+    }
+
+    fn grid(rows: u32, cols: u32) -> CsrGraph {
+        let edges: Vec<(u32, u32)> = (0..rows * cols)
+            .flat_map(|v| {
+                let right = (v % cols + 1 < cols).then_some((v, v + 1));
+                let down = (v + cols < rows * cols).then_some((v, v + cols));
+                right.into_iter().chain(down)
+            })
+            .collect();
+        CsrGraph::from_edges((rows * cols) as usize, &edges)
+    }
+
+    fn random_graph(order: u32, probability: f64, rng: &mut StdRng) -> CsrGraph {
+        let edges: Vec<(u32, u32)> = (0..order)
+            .flat_map(|u| (u + 1..order).map(move |v| (u, v)))
+            .filter(|_| rng.gen_bool(probability))
+            .collect();
+        CsrGraph::from_edges(order as usize, &edges)
+    }
+
+    fn random_tree(order: u32, rng: &mut StdRng) -> CsrGraph {
+        let edges: Vec<(u32, u32)> = (1..order).map(|v| (rng.gen_range(0..v), v)).collect();
+        CsrGraph::from_edges(order as usize, &edges)
+    }
+
+    #[test]
+    fn matches_brute_force_on_paths_cycles_and_grids() {
+        for n in 2..=8u32 {
+            let path: Vec<(u32, u32)> = (1..n).map(|v| (v - 1, v)).collect();
+            assert_optimal(&CsrGraph::from_edges(n as usize, &path));
+            if n >= 3 {
+                let mut cycle = path;
+                cycle.push((0, n - 1));
+                assert_optimal(&CsrGraph::from_edges(n as usize, &cycle));
+            }
+        }
+
+        let mut rng = StdRng::seed_from_u64(0);
+        for (rows, cols) in [(2, 2), (2, 3), (2, 4), (3, 2)] {
+            let grid = grid(rows, cols);
+            assert_optimal(&grid);
+            assert_optimal(&with_random_costs(grid, &mut rng));
+        }
+    }
+
+    #[test]
+    fn matches_brute_force_on_random_graphs() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..300 {
+            let order = rng.gen_range(2..=8);
+            let probability = rng.gen_range(0.2..0.8);
+            let graph = random_graph(order, probability, &mut rng);
+            assert_optimal(&graph);
+            assert_optimal(&with_random_costs(graph, &mut rng));
+        }
+    }
+
+    #[test]
+    fn decompositions_of_larger_graphs_are_valid() {
+        let mut rng = StdRng::seed_from_u64(2);
+        for graph in [
+            grid(6, 7),
+            random_tree(50, &mut rng),
+            random_graph(60, 0.05, &mut rng),
+        ] {
+            for order in ORDERS {
+                assert_valid(&graph, &decompose(&graph, order, MAX_DP_WIDTH).unwrap());
+            }
+        }
+
+        assert_eq!(decompose(&grid(6, 7), EliminationOrder::MinFill, 3), None);
+    }
+
+    #[test]
+    fn gives_up_beyond_the_state_budget() {
+        let grid = grid(4, 8);
+        let order = EliminationOrder::MinFill;
+        let problem = ProblemVariant::TotalRoman;
+        assert!(solve_treewidth(problem, &grid, order, MAX_DP_WIDTH, usize::MAX).is_some());
+        assert!(solve_treewidth(problem, &grid, order, MAX_DP_WIDTH, 100).is_none());
+        assert!(solve_treewidth(problem, &grid, order, 1, usize::MAX).is_none());
+    }
+}
@@ -0,0 +1,448 @@
+use std::{fmt, str::FromStr};
+
+use log::info;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use rayon::prelude::*;
+
+use crate::{
+    csr::CsrGraph,
+    solver::{solve, SolverConfig},
+};
+
+/// Normal quantile of `0.95`, used for the chi-square critical value.
+const Z_95: f64 = 1.644_853_6;
+/// Normal quantile of `0.975`, used for the two-sided t critical value.
+const Z_975: f64 = 1.959_964;
+
+/// Range of values a tuned parameter can take.
+///
+/// Parsed from `MIN..MAX` (integers if both bounds are integers, reals otherwise) or
+/// from `A|B|C` for a categorical parameter.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParamRange {
+    /// Integers in `min..=max`.
+    Integer {
+        /// Smallest value.
+        min: i64,
+        /// Largest value.
+        max: i64,
+    },
+    /// Reals in `min..=max`.
+    Real {
+        /// Smallest value.
+        min: f64,
+        /// Largest value.
+        max: f64,
+    },
+    /// One of the given values.
+    Categorical(Vec<String>),
+}
+
+impl ParamRange {
+    /// Draws a value uniformly from the range, formatted for [`SolverConfig::set`].
+    fn sample(&self, rng: &mut StdRng) -> String {
+        match self {
+            Self::Integer { min, max } => rng.gen_range(*min..=*max).to_string(),
+            Self::Real { min, max } => format!("{:.4}", rng.gen_range(*min..=*max)),
+            Self::Categorical(values) => values
+                .choose(rng)
+                .cloned()
+                .expect("Categorical ranges are not empty"),
+        }
+    }
+}
+
+impl FromStr for ParamRange {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some((min, max)) = s.split_once("..") {
+            if let (Ok(min), Ok(max)) = (min.parse::<i64>(), max.parse::<i64>()) {
+                return if min <= max {
+                    Ok(Self::Integer { min, max })
+                } else {
+                    Err(format!("Empty range: {s}"))
+                };
+            }
+            return match (min.parse::<f64>(), max.parse::<f64>()) {
+                (Ok(min), Ok(max)) if min <= max => Ok(Self::Real { min, max }),
+                _ => Err(format!("Invalid range: {s}")),
+            };
+        }
+
+        let values: Vec<String> = s
+            .split('|')
+            .filter(|v| !v.is_empty())
+            .map(String::from)
+            .collect();
+        if values.is_empty() {
+            Err(format!("Invalid range: {s}"))
+        } else {
+            Ok(Self::Categorical(values))
+        }
+    }
+}
+
+impl fmt::Display for ParamRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Integer { min, max } => write!(f, "{min}..{max}"),
+            Self::Real { min, max } => write!(f, "{min}..{max}"),
+            Self::Categorical(values) => write!(f, "{}", values.join("|")),
+        }
+    }
+}
+
+/// A parameter to tune: a [`SolverConfig::set`] key and its range.
+///
+/// Parsed from `KEY=RANGE`, e.g. `crossover_rate=0.6..1.0` or `selection=tournament|sus`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Parameter {
+    /// Configuration key.
+    pub key: String,
+    /// Values the parameter can take.
+    pub range: ParamRange,
+}
+
+impl FromStr for Parameter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (key, range) = s
+            .split_once('=')
+            .ok_or_else(|| format!("Invalid parameter: {s} (expected KEY=RANGE)"))?;
+        Ok(Self {
+            key: key.trim().to_string(),
+            range: range.trim().parse()?,
+        })
+    }
+}
+
+/// Settings of a race.
+///
+/// # Fields
+/// - `candidates: usize`: Number of configurations entering the race, including the base
+///   configuration.
+/// - `budget: usize`: Maximum number of solver runs.
+/// - `first_test: usize`: Number of instances every candidate is evaluated on before the
+///   first elimination test.
+/// - `seed: u64`: Seed for sampling candidates, ordering instances and seeding runs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RaceSettings {
+    /// Number of candidate configurations.
+    pub candidates: usize,
+    /// Maximum number of solver runs.
+    pub budget: usize,
+    /// Instances evaluated before the first elimination test.
+    pub first_test: usize,
+    /// Seed of the race.
+    pub seed: u64,
+}
+
+impl Default for RaceSettings {
+    fn default() -> Self {
+        Self {
+            candidates: 16,
+            budget: 500,
+            first_test: 5,
+            seed: 0,
+        }
+    }
+}
+
+/// Result of a race.
+///
+/// # Fields
+/// - `best: SolverConfig`: The surviving configuration with the best mean rank.
+/// - `survivors: Vec<SolverConfig>`: All configurations still in the race, best first.
+/// - `runs: usize`: Number of solver runs performed.
+/// - `blocks: usize`: Number of instance evaluations (blocks) performed.
+#[derive(Clone, Debug)]
+pub struct RaceOutcome {
+    /// Best configuration.
+    pub best: SolverConfig,
+    /// Surviving configurations, best first.
+    pub survivors: Vec<SolverConfig>,
+    /// Solver runs performed.
+    pub runs: usize,
+    /// Instance evaluations performed.
+    pub blocks: usize,
+}
+
+/// Tunes `parameters` of `base` on `instances` with an F-race.
+///
+/// The race starts with `base` and `settings.candidates - 1` configurations whose
+/// parameters are drawn uniformly from their ranges. In every step (block), all
+/// surviving candidates solve the next instance with a common seed; instances are
+/// visited in random order and revisited with new seeds once exhausted. After
+/// `settings.first_test` blocks, a Friedman test on the ranks of the candidates is run
+/// after every block; if it rejects equality at the 95% level, the candidates whose rank
+/// sum is significantly worse than the best one (Conover's post-hoc test) are
+/// eliminated. The race stops when one candidate is left or the next block would exceed
+/// the budget.
+///
+/// # Errors
+/// Returns an error if there are no instances, if fewer than two candidates are
+/// requested, if a parameter key or sampled value is rejected by [`SolverConfig::set`],
+/// or if a candidate cannot run on an instance; see [`SolverConfig::validate`].
+pub fn race(
+    base: &SolverConfig,
+    parameters: &[Parameter],
+    instances: &[CsrGraph],
+    settings: &RaceSettings,
+) -> Result<RaceOutcome, String> {
+    if instances.is_empty() {
+        return Err("At least one training instance is required".to_string());
+    }
+    if settings.candidates < 2 {
+        return Err("At least two candidates are required".to_string());
+    }
+
+    let mut rng = StdRng::seed_from_u64(settings.seed);
+
+    let mut candidates = vec![base.clone()];
+    while candidates.len() < settings.candidates {
+        let mut candidate = base.clone();
+        for parameter in parameters {
+            candidate.set(&parameter.key, &parameter.range.sample(&mut rng))?;
+        }
+        candidates.push(candidate);
+    }
+    for candidate in &candidates {
+        for graph in instances {
+            candidate.validate(graph)?;
+        }
+    }
+
+    let mut alive: Vec<usize> = (0..candidates.len()).collect();
+    // Resultados por candidato, um por bloco desde o início da corrida.
+    let mut results: Vec<Vec<usize>> = vec![Vec::new(); candidates.len()];
+    let mut order: Vec<usize> = (0..instances.len()).collect();
+    let mut runs = 0;
+    let mut blocks = 0;
+
+    while alive.len() > 1 && runs + alive.len() <= settings.budget {
+        if blocks % order.len() == 0 {
+            order.shuffle(&mut rng);
+        }
+        let instance = order[blocks % order.len()];
+        let seed: u64 = rng.gen();
+
+        let fitness: Vec<usize> = alive
+            .par_iter()
+            .map(|&candidate| {
+                let mut config = candidates[candidate].clone();
+                config.seed = Some(seed);
+                solve(&instances[instance], &config).chromosome.fitness()
+            })
+            .collect();
+
+        for (&candidate, value) in alive.iter().zip(fitness) {
+            results[candidate].push(value);
+        }
+        runs += alive.len();
+        blocks += 1;
+
+        if blocks >= settings.first_test {
+            let table: Vec<&[usize]> = alive.iter().map(|&c| results[c].as_slice()).collect();
+            let keep = friedman_survivors(&table);
+            if keep.len() < alive.len() {
+                alive = keep.into_iter().map(|idx| alive[idx]).collect();
+                info!(
+                    "Race block {blocks}: {} candidates left after {runs} runs",
+                    alive.len()
+                );
+            }
+        }
+    }
+
+    let table: Vec<&[usize]> = alive.iter().map(|&c| results[c].as_slice()).collect();
+    let sums = rank_sums(&table);
+    let mut ranked: Vec<usize> = (0..alive.len()).collect();
+    ranked.sort_by(|&a, &b| sums[a].total_cmp(&sums[b]));
+
+    let survivors: Vec<SolverConfig> = ranked
+        .iter()
+        .map(|&idx| candidates[alive[idx]].clone())
+        .collect();
+
+    Ok(RaceOutcome {
+        best: survivors[0].clone(),
+        survivors,
+        runs,
+        blocks,
+    })
+}
+
+/// Ranks of the candidates in every block, lower fitness first; ties get the mean rank.
+///
+/// `table[c][b]` is the result of candidate `c` in block `b`. Returns `ranks[c][b]`.
+#[allow(clippy::cast_precision_loss)]
+fn ranks(table: &[&[usize]]) -> Vec<Vec<f64>> {
+    let blocks = table.first().map_or(0, |row| row.len());
+    let mut ranks = vec![vec![0.0; blocks]; table.len()];
+
+    for block in 0..blocks {
+        let mut order: Vec<usize> = (0..table.len()).collect();
+        order.sort_by_key(|&candidate| table[candidate][block]);
+
+        let mut start = 0;
+        while start < order.len() {
+            let mut end = start + 1;
+            while end < order.len() && table[order[end]][block] == table[order[start]][block] {
+                end += 1;
+            }
+            // Postos de 1 a k; empates recebem a média dos postos ocupados.
+            let rank = (start + end + 1) as f64 / 2.0;
+            for &candidate in &order[start..end] {
+                ranks[candidate][block] = rank;
+            }
+            start = end;
+        }
+    }
+
+    ranks
+}
+
+fn rank_sums(table: &[&[usize]]) -> Vec<f64> {
+    ranks(table).iter().map(|row| row.iter().sum()).collect()
+}
+
+/// Returns the indices of the candidates that survive a Friedman test followed by
+/// Conover's post-hoc comparison with the best candidate, both at the 95% level.
+#[allow(clippy::cast_precision_loss)]
+fn friedman_survivors(table: &[&[usize]]) -> Vec<usize> {
+    let k = table.len();
+    let b = table.first().map_or(0, |row| row.len());
+    let all: Vec<usize> = (0..k).collect();
+    if k < 2 || b < 2 {
+        return all;
+    }
+
+    let ranks = ranks(table);
+    let sums: Vec<f64> = ranks.iter().map(|row| row.iter().sum()).collect();
+    let (kf, bf) = (k as f64, b as f64);
+
+    let a: f64 = ranks.iter().flatten().map(|r| r * r).sum();
+    let c = bf * kf * (kf + 1.0).powi(2) / 4.0;
+    let sum_squares: f64 = sums.iter().map(|r| r * r).sum();
+    if a - c <= 0.0 {
+        // Todos os candidatos empatados em todos os blocos.
+        return all;
+    }
+
+    let statistic = (kf - 1.0) * (sum_squares - bf * c) / (a - c);
+    if statistic <= chi_square_quantile(kf - 1.0) {
+        return all;
+    }
+
+    let df = (bf - 1.0) * (kf - 1.0);
+    let difference = t_quantile(df) * (2.0 * bf * (a - sum_squares / bf) / df).sqrt();
+    let best = sums.iter().copied().fold(f64::INFINITY, f64::min);
+
+    all.into_iter()
+        .filter(|&candidate| sums[candidate] - best <= difference)
+        .collect()
+}
+
+/// Wilson-Hilferty approximation of the 95% quantile of the chi-square distribution.
+fn chi_square_quantile(df: f64) -> f64 {
+    let h = 2.0 / (9.0 * df);
+    df * (1.0 - h + Z_95 * h.sqrt()).powi(3)
+}
+
+/// Cornish-Fisher approximation of the 97.5% quantile of Student's t distribution.
+fn t_quantile(df: f64) -> f64 {
+    let z = Z_975;
+    z + (z.powi(3) + z) / (4.0 * df)
+        + (5.0 * z.powi(5) + 16.0 * z.powi(3) + 3.0 * z) / (96.0 * df * df)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(table: &[Vec<usize>]) -> Vec<&[usize]> {
+        table.iter().map(Vec::as_slice).collect()
+    }
+
+    fn assert_relative(approximation: f64, exact: f64, tolerance: f64) {
+        assert!(
+            ((approximation - exact) / exact).abs() < tolerance,
+            "{approximation} != {exact}"
+        );
+    }
+
+    #[test]
+    fn tied_candidates_get_mid_ranks() {
+        let table = [vec![5, 1, 4], vec![3, 1, 4], vec![5, 2, 4], vec![1, 1, 4]];
+        let ranks = ranks(&rows(&table));
+
+        assert_eq!(
+            ranks,
+            [
+                [3.5, 2.0, 2.5],
+                [2.0, 2.0, 2.5],
+                [3.5, 4.0, 2.5],
+                [1.0, 2.0, 2.5]
+            ]
+        );
+        // Os postos de cada bloco somam k(k + 1) / 2.
+        for block in 0..3 {
+            assert!((ranks.iter().map(|row| row[block]).sum::<f64>() - 10.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn all_tied_candidates_survive() {
+        let table = vec![vec![7; 12]; 5];
+        assert_eq!(friedman_survivors(&rows(&table)), [0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn a_candidate_that_always_loses_is_the_only_one_eliminated() {
+        // Os três primeiros candidatos alternam os três primeiros lugares; o último perde
+        // em todos os blocos.
+        let table: Vec<Vec<usize>> = (0..4)
+            .map(|candidate| {
+                (0..9)
+                    .map(|block| match candidate {
+                        3 => 100,
+                        _ => (candidate + block) % 3 + 10,
+                    })
+                    .collect()
+            })
+            .collect();
+        assert_eq!(friedman_survivors(&rows(&table)), [0, 1, 2]);
+
+        // Com poucos blocos, a diferença não é significativa.
+        let short: Vec<Vec<usize>> = table.iter().map(|row| row[..2].to_vec()).collect();
+        assert_eq!(friedman_survivors(&rows(&short)), [0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn quantiles_match_tabulated_values() {
+        // Tabelas de 95% da qui-quadrado e de 97,5% da t de Student. As aproximações
+        // perdem precisão com um grau de liberdade (qui-quadrado) ou menos de cinco (t).
+        for (df, exact) in [
+            (2.0, 5.991),
+            (3.0, 7.815),
+            (5.0, 11.070),
+            (10.0, 18.307),
+            (20.0, 31.410),
+        ] {
+            assert_relative(chi_square_quantile(df), exact, 0.01);
+        }
+        for (df, exact) in [
+            (5.0, 2.571),
+            (10.0, 2.228),
+            (20.0, 2.086),
+            (30.0, 2.042),
+            (60.0, 2.000),
+        ] {
+            assert_relative(t_quantile(df), exact, 0.01);
+        }
+        assert_relative(chi_square_quantile(3.0), 7.815, 0.006);
+        assert_relative(t_quantile(10.0), 2.228, 0.002);
+    }
+}
@@ -1,11 +1,13 @@
 use std::{
     collections::HashMap,
-    fs::File,
+    fs::{self, File},
     io::{self, BufRead},
 };
 
 use kambo_graph::{graphs::simple::UndirectedGraph, Graph, GraphMut};
 
+use crate::csr::CsrGraph;
+
 /// Builds an undirected graph from a file.
 ///
 /// # Arguments
@@ -14,13 +16,14 @@ use kambo_graph::{graphs::simple::UndirectedGraph, Graph, GraphMut};
 ///
 /// # File Format
 /// Each line in the file should represent an edge in the format `u v`, where `u` and `v` are vertices.
-/// Lines that are empty or start with `#` are ignored.
+/// Lines that are empty or start with `#` are ignored, and so are vertex-cost lines
+/// `v c1 c2`; see [`read_vertex_costs`].
 ///
 /// # Errors
 ///
 /// This function will panic if:
 /// - The file cannot be opened.
-/// - A line in the file does not have exactly two values.
+/// - A line in the file does not have two or three values.
 /// - A vertex cannot be parsed as an integer.
 /// - An edge cannot be added to the graph due to invalid vertices or duplicate edges.
 ///
@@ -30,6 +33,94 @@ use kambo_graph::{graphs::simple::UndirectedGraph, Graph, GraphMut};
 /// This function panics if the input format is invalid.
 #[must_use]
 pub fn build_graph(file_path: &str) -> UndirectedGraph<u32> {
+    read_graph(file_path).0
+}
+
+/// Builds a [`CsrGraph`] from an edge list and attaches vertex costs, if there are any.
+///
+/// The costs are read by [`read_vertex_costs`] from `costs_file` or, if it is `None`,
+/// from the vertex-cost lines of `graph_file` itself. Vertices without a cost line cost
+/// `1` and `2`, as on unweighted graphs. Without any cost line the graph is unweighted.
+///
+/// # Errors
+/// Returns an error if the costs cannot be read, if `costs_file` has no cost lines, or
+/// if a cost line names a vertex that appears in no edge.
+///
+/// # Panics
+/// Panics under the same conditions as [`build_graph`].
+pub fn build_weighted_graph(
+    graph_file: &str,
+    costs_file: Option<&str>,
+) -> Result<CsrGraph, String> {
+    let (graph, ids) = read_graph(graph_file);
+    let csr = CsrGraph::from(&graph);
+
+    let costs = read_vertex_costs(costs_file.unwrap_or(graph_file))?;
+    if costs.is_empty() {
+        return match costs_file {
+            Some(path) => Err(format!("No vertex costs found in {path}")),
+            None => Ok(csr),
+        };
+    }
+
+    let mut table = vec![[1, 2]; csr.order()];
+    for (vertex, cost) in costs {
+        let index = ids
+            .get(&vertex)
+            .ok_or_else(|| format!("Vertex {vertex} has costs but no edges in {graph_file}"))?;
+        table[*index as usize] = cost;
+    }
+
+    Ok(csr.with_costs(table))
+}
+
+/// Reads vertex costs from a file.
+///
+/// # File Format
+/// Each cost line has the format `v c1 c2`: vertex `v` (as numbered in the edge list)
+/// costs `c1` with label `1` and `c2` with label `2`. Costs are non-negative integers.
+/// Edge lines `u v`, empty lines and lines starting with `#` are ignored, so the costs
+/// may be extra lines of the edge list itself.
+///
+/// # Returns
+/// The costs `[c1, c2]` of every vertex with a cost line; a later line for the same
+/// vertex replaces an earlier one.
+///
+/// # Errors
+/// Returns an error if the file cannot be read, if a line does not have two or three
+/// values, or if a value is not a non-negative integer.
+pub fn read_vertex_costs(file_path: &str) -> Result<HashMap<u32, [u32; 2]>, String> {
+    let text =
+        fs::read_to_string(file_path).map_err(|e| format!("Failed to read {file_path}: {e}"))?;
+
+    let mut costs = HashMap::new();
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        let parse = |value: &str| {
+            value
+                .parse::<u32>()
+                .map_err(|_| format!("{file_path}:{}: invalid value {value}", number + 1))
+        };
+        match parts[..] {
+            [_, _] => {}
+            [vertex, c1, c2] => {
+                costs.insert(parse(vertex)?, [parse(c1)?, parse(c2)?]);
+            }
+            _ => return Err(format!("{file_path}:{}: invalid line: {line}", number + 1)),
+        }
+    }
+
+    Ok(costs)
+}
+
+/// Reads an edge list and returns the normalized graph together with the normalized
+/// index of every vertex of the file.
+fn read_graph(file_path: &str) -> (UndirectedGraph<u32>, HashMap<u32, u32>) {
     let file = File::open(file_path).expect("Failed to open the file");
     let reader = io::BufReader::new(file);
 
@@ -46,7 +137,14 @@ pub fn build_graph(file_path: &str) -> UndirectedGraph<u32> {
         }
 
         let parts: Vec<&str> = line.split_whitespace().collect();
-        assert!(parts.len() == 2, "Invalid format on line: {line}");
+        assert!(
+            parts.len() == 2 || parts.len() == 3,
+            "Invalid format on line: {line}"
+        );
+        // Linhas com três valores são custos de vértices; veja `read_vertex_costs`.
+        if parts.len() == 3 {
+            continue;
+        }
 
         let u: u32 = parts[0].parse().expect("Failed to parse vertex 'u'");
         let v: u32 = parts[1].parse().expect("Failed to parse vertex 'v'");
@@ -66,8 +164,9 @@ pub fn build_graph(file_path: &str) -> UndirectedGraph<u32> {
 /// * `graph` - A mutable reference to an `UndirectedGraph<usize>` to normalize.
 ///
 /// # Returns
-/// A new `UndirectedGraph<usize>` with normalized indices.
-fn normalize_graph(graph: &UndirectedGraph<u32>) -> UndirectedGraph<u32> {
+/// A new `UndirectedGraph<usize>` with normalized indices, and the new index of every
+/// original vertex.
+fn normalize_graph(graph: &UndirectedGraph<u32>) -> (UndirectedGraph<u32>, HashMap<u32, u32>) {
     let mut vertex_map = HashMap::new();
     let mut new_index = 0;
 
@@ -93,5 +192,5 @@ fn normalize_graph(graph: &UndirectedGraph<u32>) -> UndirectedGraph<u32> {
         }
     }
 
-    normalized_graph
+    (normalized_graph, vertex_map)
 }